@@ -0,0 +1,478 @@
+// Copyright (C) 2021 by Andy Gozas <andy@gozas.me>
+//
+// This file is part of Dusk API.
+//
+// Dusk API is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Dusk API is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Dusk API.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Module, containing the C ABI plugin declaration, that lets a
+//! plugin be used by a program, built with a different compiler
+//! or a different Dusk API version
+//!
+//! [`FreightDeclaration`] and everything the [`Freight`] trait
+//! passes around are Rust structures, the layout of which is only
+//! the same when both sides are built with the same compiler and the
+//! same [`LAYOUT_FINGERPRINT`]. [`AbiFreightDeclaration`] only holds C
+//! ABI functions and passes everything else as bytes, encoded the
+//! way [`FreightServer`] expects, so it can be read by any program
+//! that uses the same [`ABI_VERSION`].
+//!
+//! Every plugin exported with [`export_freight!`] exports both
+//! declarations. [`FreightProxy::load`] uses the Rust one, when the
+//! compiler versions and layout fingerprints match, and the C ABI one
+//! otherwise. [`FreightProxy::load_strict`] only ever uses the Rust
+//! one.
+
+use crate::*;
+use crate::wire::*;
+
+/// Version of the C ABI declaration and of the encoding of the calls
+/// passed through it. Plugins are only loaded through the C ABI if
+/// their version is exactly the same
+pub static ABI_VERSION: u32 = 1;
+
+/// Number, every handle of a shared proxy starts with, so that
+/// anything else is not mistaken for one
+const ABI_HANDLE_MAGIC: u64 = 0x6475_736b_6861_6e64;
+
+/// A byte buffer, allocated on one side of the C ABI, which has to
+/// be freed with the `free_buffer` function of the same side
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct AbiBuffer {
+
+    /// Pointer to the first byte
+    pub ptr: *mut u8,
+
+    /// Amount of bytes in the buffer
+    pub len: usize,
+
+    /// Amount of bytes allocated
+    pub cap: usize,
+}
+
+/// A C ABI table of functions, used to call a [`Freight`] through a
+/// [`FreightServer`], that holds it
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct AbiFreight {
+
+    /// Pointer to the [`FreightServer`], only ever used by the
+    /// functions of the same table
+    pub instance: *mut std::ffi::c_void,
+
+    /// Function, that passes the encoded call to the
+    /// [`FreightServer::handle`] and returns the encoded reply
+    pub call: extern "C" fn (
+        *mut std::ffi::c_void,
+        *const u8,
+        usize,
+    ) -> AbiBuffer,
+
+    /// Function, that frees the replies returned by `call`
+    pub free_buffer: extern "C" fn (AbiBuffer),
+
+    /// Function, that drops the [`FreightServer`] along with the
+    /// freight it holds
+    pub destroy: extern "C" fn (*mut std::ffi::c_void),
+}
+
+/// A C ABI structure, exported by the plugin, containing the
+/// functions needed to describe and create its freight
+///
+/// This structure must only be built by [`export_freight!`] macro
+/// in plugins. And its fields are only read by
+/// [`FreightProxy::load`] function when loading the plugin
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct AbiFreightDeclaration {
+
+    /// Version of the C ABI, the plugin was built with
+    pub abi_version: u32,
+
+    /// Function, that returns the encoded [`FreightDescription`]
+    pub describe: extern "C" fn () -> AbiBuffer,
+
+    /// Function, that registers the freight and returns the table
+    /// of functions used to call it
+    pub create: extern "C" fn () -> AbiFreight,
+
+    /// Function, that frees the buffers returned by `describe`
+    pub free_buffer: extern "C" fn (AbiBuffer),
+}
+
+/// Everything, that is exported in the [`FreightDeclaration`],
+/// except for the register function, read through the C ABI
+#[derive(Clone, Debug)]
+pub struct FreightDescription {
+
+    /// Name of the freight
+    pub name: String,
+
+    /// Version of the freight
+    pub freight_version: Version,
+
+    /// The earliest plugin version, for which the code could have
+    /// been designed, and still be run with this version of plugin
+    pub backwards_compat_version: Version,
+
+    /// Dusk API version, the plugin was built with
+    pub api_version: String,
+
+    /// Fingerprint of the layout of the core structures, the plugin
+    /// was built with (see [`LAYOUT_FINGERPRINT`])
+    pub layout_fingerprint: u64,
+
+    /// Rust compiler version, the plugin was built with
+    pub rustc_version: String,
+}
+
+impl FreightDescription {
+
+    /// Read the description of the freight, exported with the
+    /// declaration provided
+    ///
+    /// # Safety
+    ///
+    /// The declaration has to be exported by [`export_freight!`]
+    /// and the library it is located in has to stay loaded
+    pub unsafe fn from_abi (
+        declaration: &AbiFreightDeclaration,
+    ) -> Result<FreightDescription, Error> {
+
+        if declaration.abi_version != ABI_VERSION {
+            return Err(ImportError (
+                    "Dusk ABI version mismatch".to_string()
+            ));
+        }
+
+        let data: Vec<u8> = read_abi_buffer(
+            (declaration.describe)(),
+            declaration.free_buffer);
+        decode(&data)
+    }
+
+    /// Check if the plugin was built with the same compiler version
+    /// and layout of the core structures (see [`LAYOUT_FINGERPRINT`])
+    /// as the program that is using it, so it can be used without
+    /// the C ABI
+    pub fn is_native (self: &Self) -> bool {
+        self.rustc_version == RUSTC_VERSION &&
+            self.layout_fingerprint == LAYOUT_FINGERPRINT
+    }
+}
+
+impl Wire for FreightDescription {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.name)?;
+        writer.write(&self.freight_version)?;
+        writer.write(&self.backwards_compat_version)?;
+        writer.write(&self.api_version)?;
+        writer.write(&self.layout_fingerprint)?;
+        writer.write(&self.rustc_version)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<FreightDescription, Error> {
+
+        Ok(FreightDescription {
+            name: reader.read()?,
+            freight_version: reader.read()?,
+            backwards_compat_version: reader.read()?,
+            api_version: reader.read()?,
+            layout_fingerprint: reader.read()?,
+            rustc_version: reader.read()?,
+        })
+    }
+}
+
+/// Turn the vector into a buffer, that can be passed through the
+/// C ABI and freed with [`abi_free_buffer`]
+fn into_abi_buffer (data: Vec<u8>) -> AbiBuffer {
+    let mut data: std::mem::ManuallyDrop<Vec<u8>> =
+        std::mem::ManuallyDrop::new(data);
+    AbiBuffer {
+        ptr: data.as_mut_ptr(),
+        len: data.len(),
+        cap: data.capacity(),
+    }
+}
+
+/// Copy the buffer, gotten through the C ABI, and free it with the
+/// function provided
+unsafe fn read_abi_buffer (
+    buffer: AbiBuffer,
+    free_buffer: extern "C" fn (AbiBuffer),
+) -> Vec<u8> {
+
+    let result: Vec<u8> =
+        std::slice::from_raw_parts(buffer.ptr, buffer.len).to_vec();
+    free_buffer(buffer);
+    return result;
+}
+
+/// Free the buffer, built by this side of the C ABI
+///
+/// Only needed by [`export_freight!`]
+#[doc(hidden)]
+pub extern "C" fn abi_free_buffer (buffer: AbiBuffer) {
+    unsafe {
+        drop(Vec::from_raw_parts(buffer.ptr, buffer.len, buffer.cap));
+    }
+}
+
+/// Encode the description of the freight
+///
+/// Only needed by [`export_freight!`]
+#[doc(hidden)]
+pub fn abi_describe (
+    name: &str,
+    freight_version: Version,
+    backwards_compat_version: Version,
+) -> AbiBuffer {
+
+    let description: FreightDescription = FreightDescription {
+        name: name.to_string(),
+        freight_version: freight_version,
+        backwards_compat_version: backwards_compat_version,
+        api_version: API_VERSION.to_string(),
+        layout_fingerprint: LAYOUT_FINGERPRINT,
+        rustc_version: RUSTC_VERSION.to_string(),
+    };
+    match encode(&description) {
+        Ok(data) => into_abi_buffer(data),
+        Err(_) => into_abi_buffer(Vec::new()),
+    }
+}
+
+/// A [`FreightRegistrar`] implementor, that only keeps the freight
+/// registered
+#[derive(Debug, Default)]
+struct CollectingRegistrar {
+    freight: Option<Box<dyn Freight>>,
+}
+
+impl FreightRegistrar for CollectingRegistrar {
+    fn register_freight (
+        self: &mut Self,
+        freight: Box<dyn Freight>,
+    ) {
+        self.freight = Some(freight);
+    }
+}
+
+/// Register the freight with the function provided and build the
+/// C ABI table of functions, used to call it
+///
+/// Only needed by [`export_freight!`]
+#[doc(hidden)]
+pub fn abi_create (
+    register: fn (&mut dyn FreightRegistrar),
+) -> AbiFreight {
+
+    let mut registrar: CollectingRegistrar = CollectingRegistrar::default();
+    register(&mut registrar);
+    let freight: Box<dyn Freight> = match registrar.freight {
+        Some(freight) => freight,
+        None => Box::new(EmptyFreight{}),
+    };
+    abi_serve(freight)
+}
+
+/// Put the freight into a [`FreightServer`] and build the C ABI
+/// table of functions, used to call it
+///
+/// The server is kept in a [`std::cell::RefCell`], so that a call,
+/// made from inside of another one, fails instead of using the server
+/// twice at the same time
+fn abi_serve (freight: Box<dyn Freight>) -> AbiFreight {
+    let server: Box<std::cell::RefCell<FreightServer>> = Box::new(
+        std::cell::RefCell::new(
+            FreightServer::with_proxy_opener(freight, open_abi_proxy)));
+    AbiFreight {
+        instance: Box::into_raw(server) as *mut std::ffi::c_void,
+        call: abi_freight_call,
+        free_buffer: abi_free_buffer,
+        destroy: abi_freight_destroy,
+    }
+}
+
+/// Pass the encoded call to the [`FreightServer`]
+extern "C" fn abi_freight_call (
+    instance: *mut std::ffi::c_void,
+    request: *const u8,
+    len: usize,
+) -> AbiBuffer {
+
+    // SAFETY: the instance was built by `abi_serve` and is only
+    // destroyed after the last call
+    let server: &std::cell::RefCell<FreightServer> =
+        unsafe { &*(instance as *const std::cell::RefCell<FreightServer>) };
+    // SAFETY: the other side passes a request it owns for the whole
+    // call
+    let request: &[u8] = unsafe { std::slice::from_raw_parts(request, len) };
+
+    let reply: Vec<u8> = match server.try_borrow_mut() {
+        Ok(mut server) => server.handle(request),
+        Err(_) => {
+            let mut writer: WireWriter = WireWriter::new();
+            let error: Error = RuntimeError(
+                "Plugin called from inside of its own call".to_string()
+            );
+            match writer.write(&Err::<(), Error>(error)) {
+                Ok(()) => writer.buffer,
+                Err(_) => Vec::new(),
+            }
+        },
+    };
+    into_abi_buffer(reply)
+}
+
+/// Drop the [`FreightServer`]
+extern "C" fn abi_freight_destroy (instance: *mut std::ffi::c_void) {
+    // SAFETY: the instance was built by `abi_serve` and the other side
+    // destroys it only once, after the last call
+    unsafe {
+        drop(Box::from_raw(
+                instance as *mut std::cell::RefCell<FreightServer>));
+    }
+}
+
+/// Open a proxy, shared by [`AbiChannel::share_proxy`], by its
+/// handle
+///
+/// The handle holds the addresses of the table of functions, so it
+/// is only accepted if it starts with [`ABI_HANDLE_MAGIC`] and was
+/// built in this very process
+fn open_abi_proxy (handle: &[u8]) -> Result<Box<dyn Freight>, Error> {
+    let values: Vec<u64> = decode(handle)?;
+    if values.len() != 6 ||
+        values[0] != ABI_HANDLE_MAGIC ||
+        values[1] != std::process::id() as u64 {
+
+        return Err(ValueError(
+                "Invalid shared plugin handle".to_string()
+        ));
+    }
+
+    // SAFETY: the handle was built by `AbiChannel::share_proxy` of
+    // the program, that loaded this plugin, in the same process, from
+    // the table, built by `abi_serve`, so the addresses are the ones
+    // of the functions of that table and stay valid until `destroy`
+    // is called by the channel below
+    let freight: AbiFreight = unsafe {
+        AbiFreight {
+            instance: values[2] as usize as *mut std::ffi::c_void,
+            call: std::mem::transmute::<
+                usize,
+                extern "C" fn (*mut std::ffi::c_void, *const u8, usize)
+                    -> AbiBuffer
+            >(values[3] as usize),
+            free_buffer: std::mem::transmute::<
+                usize,
+                extern "C" fn (AbiBuffer)
+            >(values[4] as usize),
+            destroy: std::mem::transmute::<
+                usize,
+                extern "C" fn (*mut std::ffi::c_void)
+            >(values[5] as usize),
+        }
+    };
+    let channel: AbiChannel = unsafe { AbiChannel::new(freight, None) };
+    return Ok(Box::new(RemoteFreight::new(Box::new(channel))));
+}
+
+/// A [`FreightChannel`], that passes the calls through the C ABI
+/// table of functions of a freight
+///
+/// The freight is destroyed once the channel is dropped
+#[derive(Debug)]
+pub struct AbiChannel {
+
+    /// The table of functions
+    freight: AbiFreight,
+
+    /// Library the functions are located in, if it was loaded as a
+    /// plugin. Must stay the last field, so it is dropped after the
+    /// freight is destroyed
+    _lib: Option<std::sync::Arc<libloading::Library>>,
+}
+
+impl AbiChannel {
+
+    /// Create a channel, that uses the table of functions provided
+    ///
+    /// # Safety
+    ///
+    /// The table has to be built by [`AbiFreightDeclaration::create`]
+    /// and the functions in it have to stay loaded for as long as the
+    /// channel is used, which is guaranteed if the library they are
+    /// located in is provided
+    pub unsafe fn new (
+        freight: AbiFreight,
+        lib: Option<std::sync::Arc<libloading::Library>>,
+    ) -> AbiChannel {
+
+        AbiChannel {
+            freight: freight,
+            _lib: lib,
+        }
+    }
+}
+
+impl FreightChannel for AbiChannel {
+    fn exchange (
+        self: &mut Self,
+        request: Vec<u8>,
+    ) -> Result<Vec<u8>, Error> {
+
+        let reply: AbiBuffer = (self.freight.call)(
+            self.freight.instance,
+            request.as_ptr(),
+            request.len());
+        return Ok(unsafe {
+            read_abi_buffer(reply, self.freight.free_buffer)
+        });
+    }
+
+    // Both sides are located in the same process, so the proxy is
+    // put into a FreightServer of this side, and the other side gets
+    // the addresses of its table of functions
+    fn share_proxy (
+        self: &mut Self,
+        proxy: std::rc::Rc<FreightProxy>,
+    ) -> Result<Vec<u8>, Error> {
+
+        let freight: AbiFreight = abi_serve(Box::new(proxy));
+        let values: Vec<u64> = vec![
+            ABI_HANDLE_MAGIC,
+            std::process::id() as u64,
+            freight.instance as usize as u64,
+            freight.call as usize as u64,
+            freight.free_buffer as usize as u64,
+            freight.destroy as usize as u64,
+        ];
+        encode(&values)
+    }
+}
+
+impl Drop for AbiChannel {
+    fn drop (self: &mut Self) {
+        (self.freight.destroy)(self.freight.instance);
+    }
+}
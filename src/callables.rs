@@ -23,8 +23,8 @@ use crate::*;
 //macro_rules! register_callable_scheme {
 //}
 //
-//// TODO: macro that takes arguments as Arc<Mutex<Box<dyn Any>>> and
-//// calls the underlying function
+// TODO: macro that takes arguments as Arc<Mutex<Box<dyn Any>>> and
+// calls the underlying function
 //#[macro_export]
 //macro_rules! call_clone_unwrap {
 //}
@@ -147,6 +147,60 @@ impl std::fmt::Debug for ConstArgsCallable {
     }
 }
 
+/// Dusk callable, that wraps a callable imported from a plugin
+/// library, making sure that library is not unloaded while the
+/// callable is in use
+///
+/// Every [`Object`] returned by the underlying callable is pinned
+/// to the library as well (see [`PinLibrary`])
+#[derive(Clone)]
+pub struct LibraryCallable {
+    callable: Box<dyn DuskCallable>,
+
+    /// Must be declared after the callable, so it is dropped after
+    /// the callable
+    lib: std::sync::Arc<libloading::Library>,
+}
+
+impl LibraryCallable {
+
+    /// Wrap the callable, located in the library provided
+    pub fn new (
+        callable: Box<dyn DuskCallable>,
+        lib: &std::sync::Arc<libloading::Library>,
+    ) -> LibraryCallable {
+
+        LibraryCallable {
+            callable: callable,
+            lib: lib.clone(),
+        }
+    }
+}
+
+impl DuskCallable for LibraryCallable {
+    fn call (
+        self: &mut Self,
+        args: Vec<Object>
+    ) -> Result<Object, Error> {
+
+        let mut result: Object = self.callable.call(args)?;
+        result.pin_library(&self.lib);
+        return Ok(result);
+    }
+}
+
+impl std::fmt::Debug for LibraryCallable {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        f.debug_struct("LibraryCallable")
+            .field("callable", &self.callable)
+            .finish()
+    }
+}
+
 /// A default callable: does not call anything, always returns
 /// [`Error::NotImplementedError`]
 #[derive(Copy, Clone, Debug)]
@@ -24,6 +24,187 @@
 /// before the action can be performed, and carries an InterplugRequest,
 /// identifying such dependency
 ///
+/// Add [`crate::PluginHost`], that loads any amount of plugins,
+/// initializes them in dependency order and answers their interplugin
+/// requests with other loaded plugins. Make a [`crate::FreightProxy`]
+/// usable when shared through [`std::rc::Rc`]
+///
+/// Add [`crate::PluginDiscovery`] to list the plugins found in a set
+/// of directories, reading either their declarations or manifest files
+/// placed next to them, without registering them
+///
+/// Add [`crate::PluginHost::reload`] to replace a plugin with a new
+/// build of its library while the program keeps running, with
+/// [`crate::Freight::export_state`] and [`crate::Freight::import_state`]
+/// to hand its state over and [`crate::Freight::interplug_replace`] to
+/// notify the plugins that depend on it. The old instance is kept, if
+/// its state can not be handed over. [`crate::PluginHost::replace`]
+/// does the same with an instance, that is already loaded
+///
+/// Pin every function, type, module, trait definition, callable and
+/// object, gotten from a [`crate::FreightProxy`], to the library it
+/// was loaded from (see [`crate::PinLibrary`]), so the library can no
+/// longer be unloaded while any of them is still in use. Objects of
+/// such types are created with [`crate::Type::generate`]
+///
+/// Export a C ABI declaration ([`crate::AbiFreightDeclaration`]) along
+/// with the Rust one, so a plugin built with a different compiler or
+/// Dusk API version is no longer rejected, but used through
+/// [`crate::RemoteFreight`], which passes every call encoded as bytes.
+/// The exact version match is still available as
+/// [`crate::FreightProxy::load_strict`]
+///
+/// Add [`crate::FreightProxy::spawn`] to run a plugin in a child
+/// process, served with [`crate::serve_pipe`] and called through its
+/// standard input and output, so that a plugin, that crashes or hangs,
+/// can no longer take the program down with it. The child process is
+/// killed, if it does not reply within a timeout, which applies from
+/// the very first message
+///
+/// Add [`crate::export_static_freight!`] and
+/// [`crate::STATIC_FREIGHTS`], a registry of plugins linked into the
+/// program statically, collected at link time, so that plugins can be
+/// listed and loaded with [`crate::FreightProxy::load_static`] on the
+/// systems that do not allow runtime dynamic library loading
+///
+/// Add [`crate::LibraryVerifier`] to check a plugin library against
+/// an allow list of SHA-256 digests or a detached ed25519 signature
+/// before it is loaded with [`crate::FreightProxy::load_verified`],
+/// and a new error type: [`crate::Error::IntegrityError`], returned
+/// when the check fails. The library is loaded from a private copy of
+/// the checked contents ([`crate::VerifiedLibrary`]), so it can not be
+/// replaced between the check and the load
+///
+/// Add [`crate::SyncFreightProxy`], a proxy shared with
+/// [`std::sync::Arc`], so that one loaded plugin can be called from
+/// many threads. Only the plugins, that register their freight with
+/// [`crate::FreightRegistrar::register_sync_freight`] (see
+/// [`crate::register_sync_freight!`]), can be loaded this way, and
+/// other thread safe proxies are provided to them through the new
+/// [`crate::Freight::interplug_provide_sync`]
+///
+/// Make [`crate::Version`] parse from and print as `1.2.3.4` and add
+/// [`crate::VersionReq`], a version requirement supporting `^`, `~`,
+/// comparisons, ranges and wildcards. Interplugin requests now carry
+/// a requirement instead of a single version (a single version is
+/// turned into one with `.into()`), and the [`crate::PluginHost`]
+/// provides the greatest loaded version, that matches it
+///
+/// Take the backwards compatibility version into account: a plugin
+/// fits a request if any version from its backwards compatibility
+/// version up to its own version matches the requirement, so a
+/// single requested version is now matched exactly against that
+/// range. Add [`crate::InterplugRequest::check_provider`],
+/// [`crate::PluginHost::check_provider`] and
+/// [`crate::PluginCandidate::check_request`], explaining with
+/// [`crate::Rejection`] reasons why a plugin does not fit
+///
+/// Add [`crate::LAYOUT_FINGERPRINT`], computed from the sizes,
+/// alignments and layout versions of every structure, shared between
+/// a plugin and the program using it, and of the [`crate::Freight`]
+/// trait object, and export it from every plugin library on its own,
+/// so it is checked before the [`crate::FreightDeclaration`] is
+/// read. Plugins are now used directly if
+/// the fingerprints match, instead of requiring exactly the same
+/// Dusk API version, so a patch release does not break them
+///
+/// Add pre-release tags and build metadata to [`crate::Version`]
+/// (`1.2-rc.1+nightly.20210510`), ordered like in semantic
+/// versioning. Pre-releases are only matched by requirements, that
+/// ask for them, the [`crate::PluginHost`] can be told never or
+/// always to use them with [`crate::PreReleasePolicy`], and the
+/// latest version is now always a stable one, if there is any
+///
+/// Add [`crate::Resolver`], that plans how to answer the requests of
+/// a plugin and of every plugin it needs, given the
+/// [`crate::AvailablePlugin`] list, backtracking through the
+/// alternatives and the matching versions. The resulting
+/// [`crate::Resolution`] lists the initialization order, the provider
+/// of every fulfilled request and the requests to deny. The
+/// [`crate::PluginHost`] answers requests following such a plan, so
+/// it tries another alternative, if a plugin fails to initialize, and
+/// provides nothing, unless every crucial request can be fulfilled
+///
+/// Add [`crate::Resolver::explain`] and [`crate::PluginHost::explain`],
+/// building an [`crate::Explanation`] tree for every request of a
+/// plugin, that lists each plugin considered for it and every
+/// reason it was rejected, including missing functions and missing
+/// trait implementations, and prints as an indented tree
+///
+/// Make [`crate::FreightProxy`] and [`crate::SyncFreightProxy`]
+/// remember the requests provided and denied since the plugin was
+/// last initialized, and add
+/// [`crate::FreightProxy::get_function_availability`] and
+/// [`crate::FreightProxy::get_available_functions`], checking the
+/// dependencies of every function against them (see
+/// [`crate::Function::availability`] and
+/// [`crate::InterplugRequest::status`])
+///
+/// Add [`crate::PluginHost::call`], that answers the request, a
+/// function returned in [`crate::Error::DependencyError`], with the
+/// plugins of the host and calls the function once again, so the
+/// dependencies, that are only needed at call time, are provided
+/// transparently. If the request can not be fulfilled, the error
+/// explains why (see [`crate::Resolver::explain_request`])
+///
+/// Add [`crate::TraitProxy::find`], that finds every implementation
+/// of the requested trait by the types of a plugin, checking the
+/// version of the trait and the requested methods, and returns it
+/// with the function links filled in. The [`crate::PluginHost`] now
+/// fulfills trait requests by providing a plugin with such an
+/// implementation, initializing it first, if needed (see
+/// [`crate::PluginHost::find_trait_implementors`])
+///
+/// Add [`crate::DependencyGraph`], that can be written in the
+/// Graphviz DOT language or as JSON, showing the plugins, the
+/// requests they made and how each of them was answered (see
+/// [`crate::PluginHost::dependency_graph`])
+///
+/// Add [`crate::PluginDatabase`], with [`crate::DirectoryDatabase`]
+/// and [`crate::MemoryDatabase`] implementations, and
+/// [`crate::PluginLocator`], that finds the library, a requested
+/// plugin name refers to, searching the database of the requesting
+/// plugin first, then the default user configured databases, and
+/// then all the known ones, going from the oldest to the newest
+///
+/// Add [`crate::Freight::shutdown`], the counterpart of
+/// [`crate::Freight::init`], and [`crate::Freight::interplug_withdraw`],
+/// telling a plugin to drop the plugin it was provided, as it is about
+/// to be unloaded. The [`crate::PluginHost`] shuts its plugins down
+/// in reverse dependency order when dropped (see
+/// [`crate::PluginHost::shutdown_all`]), and one plugin at a time with
+/// [`crate::PluginHost::shutdown`]
+///
+/// Add [`crate::WeakFreightProxy`], a handle, that does not keep the
+/// plugin from being dropped and fails to upgrade once it is
+/// unloaded, provided through [`crate::Freight::interplug_provide_weak`].
+/// When plugins depend on each other, the [`crate::PluginHost`] and the
+/// [`crate::Resolver`] no longer reject the request, that closes the
+/// cycle, but fulfill it with such a handle instead
+///
+/// Add [`crate::LimitationSchema`], a list of the settings a plugin
+/// can be limited in, along with their defaults, ranges and units,
+/// declared through [`crate::Freight::get_limitation_schema`]. The
+/// limitations, that do not fit the schema, are no longer passed to
+/// the plugin, but reported through
+/// [`crate::FreightProxy::get_rejected_limitations`], and for the
+/// limitations of a [`crate::PluginHost`], through
+/// [`crate::PluginHost::all_rejected_limitations`]
+///
+/// Add [`crate::Freight::get_effective_limitations`], that reports
+/// the value a plugin actually uses for each of its settings, along
+/// with the limitations of the setting, flagged as applied, clamped
+/// or rejected (see [`crate::LimitationStatus`]). Plugins may keep
+/// track of them with [`crate::AppliedLimitations`], while nothing is
+/// reported for the ones, that do not
+///
+/// Add [`crate::Limitation::Set`], that sets a setting, that is not a
+/// number, to a [`crate::LimitationValue`], such as a path, a string,
+/// a list of strings, a flag or a duration. The main program and the
+/// plugins may read such values with [`crate::Limitation::find_value`]
+/// and [`crate::AppliedLimitations::get_value`], and schemas declare
+/// such settings with [`crate::LimitationSetting::with_value`]
+///
 pub mod r0_2_1 {}
 
 /// Release 0.2.0 (2021-05-03)
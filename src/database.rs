@@ -0,0 +1,461 @@
+// Copyright (C) 2021 by Andy Gozas <andy@gozas.me>
+//
+// This file is part of Dusk API.
+//
+// Dusk API is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Dusk API is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Dusk API.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Module, containing everything needed to keep plugins in databases
+//! and to find the library, a requested plugin name refers to
+
+use crate::*;
+
+/// Trait, that represents a database of plugins, inside of which the
+/// plugin name is uniquely identifying the plugin, though several
+/// versions of it may be present
+pub trait PluginDatabase: std::fmt::Debug {
+
+    /// Get the name of the database
+    fn name (self: &Self) -> &str;
+
+    /// List every plugin in the database
+    ///
+    /// # Safety
+    ///
+    /// Databases may open the libraries to read their declarations
+    /// (see [`PluginDiscovery::discover`])
+    unsafe fn candidates (
+        self: &Self,
+    ) -> Result<Vec<PluginCandidate>, Error>;
+}
+
+/// A [`PluginDatabase`], backed by a directory, every plugin library
+/// in which is a part of the database (see [`PluginDiscovery`])
+#[derive(Clone, Debug)]
+pub struct DirectoryDatabase {
+
+    /// The name of the database
+    pub name: String,
+
+    /// The discovery, used to list the plugins in the directory
+    pub discovery: PluginDiscovery,
+}
+
+impl DirectoryDatabase {
+
+    /// Create a database, backed by the directory provided
+    pub fn new (
+        name: String,
+        dir: std::path::PathBuf,
+    ) -> DirectoryDatabase {
+
+        DirectoryDatabase {
+            name: name,
+            discovery: PluginDiscovery::new(vec![dir]),
+        }
+    }
+}
+
+impl PluginDatabase for DirectoryDatabase {
+    fn name (self: &Self) -> &str {
+        &self.name
+    }
+
+    unsafe fn candidates (
+        self: &Self,
+    ) -> Result<Vec<PluginCandidate>, Error> {
+
+        self.discovery.discover()
+    }
+}
+
+/// A [`PluginDatabase`], that is kept in memory, for example to list
+/// the plugins linked into the program statically, or the ones known
+/// from a previous discovery
+#[derive(Clone, Debug, Default)]
+pub struct MemoryDatabase {
+
+    /// The name of the database
+    pub name: String,
+
+    /// The plugins in the database
+    pub plugins: Vec<PluginCandidate>,
+}
+
+impl MemoryDatabase {
+
+    /// Create an empty database
+    pub fn new (name: String) -> MemoryDatabase {
+        MemoryDatabase {
+            name: name,
+            plugins: Vec::new(),
+        }
+    }
+
+    /// Add a plugin to the database
+    pub fn add (
+        self: &mut Self,
+        plugin: PluginCandidate,
+    ) {
+
+        self.plugins.push(plugin);
+    }
+}
+
+impl PluginDatabase for MemoryDatabase {
+    fn name (self: &Self) -> &str {
+        &self.name
+    }
+
+    unsafe fn candidates (
+        self: &Self,
+    ) -> Result<Vec<PluginCandidate>, Error> {
+
+        Ok(self.plugins.clone())
+    }
+}
+
+/// A plugin found by the [`PluginLocator`], along with the ID of the
+/// database it was found in
+#[derive(Clone, Debug)]
+pub struct LocatedPlugin {
+
+    /// ID of the database, the plugin was found in
+    pub database: usize,
+
+    /// The plugin
+    pub candidate: PluginCandidate,
+}
+
+/// A structure, that stores every known [`PluginDatabase`] and finds
+/// the library, a requested plugin name refers to
+///
+/// Databases are stored in the order they became known, so the
+/// position of the database in that order is used as its ID. When a
+/// plugin is requested, it is first searched for in the database of
+/// the requesting plugin, then in the default user configured
+/// databases, in the order they are listed in, and then in all the
+/// known ones, going from the oldest to the newest. The first
+/// database, that has a plugin fitting the request, is used, and the
+/// latest fitting version from it is chosen
+///
+/// # Example
+///
+/// ```
+/// use dusk_api::*;
+///
+/// let mut locator: PluginLocator = PluginLocator::new();
+/// let mut own: MemoryDatabase = MemoryDatabase::new("own".to_string());
+/// let mut system: MemoryDatabase = MemoryDatabase::new("system".to_string());
+/// for (database, version) in [(&mut own, "1.1"), (&mut system, "1.4")] {
+///     database.add(PluginCandidate {
+///         path: format!("{}/libstorage.so", database.name).into(),
+///         from_manifest: true,
+///         name: "storage".to_string(),
+///         freight_version: version.parse().unwrap(),
+///         backwards_compat_version: "1.0".parse().unwrap(),
+///         api_version: API_VERSION.to_string(),
+///         layout_fingerprint: Some(LAYOUT_FINGERPRINT),
+///         rustc_version: RUSTC_VERSION.to_string(),
+///         abi_version: None,
+///         linked: false,
+///     });
+/// }
+/// let own: usize = locator.add(Box::new(own));
+/// let system: usize = locator.add_default(Box::new(system));
+///
+/// let request: InterplugRequest = PlugRequestAll {
+///     plugin: "storage".to_string(),
+///     version: "^1".parse().unwrap(),
+/// };
+/// let found: LocatedPlugin =
+///     unsafe { locator.locate(Some(own), &request) }.unwrap().unwrap();
+/// assert_eq!(found.database, own);
+/// let found: LocatedPlugin =
+///     unsafe { locator.locate(None, &request) }.unwrap().unwrap();
+/// assert_eq!(found.database, system);
+/// ```
+#[derive(Debug, Default)]
+pub struct PluginLocator {
+
+    /// IDs of the default user configured databases, in the order
+    /// they should be searched
+    pub defaults: Vec<usize>,
+
+    /// How the pre-release versions of the plugins should be treated
+    pub pre_releases: PreReleasePolicy,
+
+    /// Every known database
+    databases: Vec<Box<dyn PluginDatabase>>,
+}
+
+impl PluginLocator {
+
+    /// Create a locator without any databases
+    pub fn new () -> PluginLocator {
+        PluginLocator {
+            defaults: Vec::new(),
+            pre_releases: PreReleasePolicy::default(),
+            databases: Vec::new(),
+        }
+    }
+
+    /// Add a known database, returning its ID
+    pub fn add (
+        self: &mut Self,
+        database: Box<dyn PluginDatabase>,
+    ) -> usize {
+
+        self.databases.push(database);
+        return self.databases.len() - 1;
+    }
+
+    /// Add a known database and make it one of the default user
+    /// configured ones, searched after all the defaults added
+    /// before, returning its ID
+    pub fn add_default (
+        self: &mut Self,
+        database: Box<dyn PluginDatabase>,
+    ) -> usize {
+
+        let id: usize = self.add(database);
+        self.defaults.push(id);
+        return id;
+    }
+
+    /// Get the amount of known databases
+    pub fn len (self: &Self) -> usize {
+        self.databases.len()
+    }
+
+    /// Check if there are no known databases
+    pub fn is_empty (self: &Self) -> bool {
+        self.databases.is_empty()
+    }
+
+    /// Get the database with the ID provided
+    pub fn get (
+        self: &Self,
+        id: usize,
+    ) -> Option<&dyn PluginDatabase> {
+
+        self.databases.get(id).map(|database| database.as_ref())
+    }
+
+    /// Get the ID of the database with the name provided
+    pub fn get_id (
+        self: &Self,
+        name: &str,
+    ) -> Option<usize> {
+
+        self.databases.iter().position(|database| database.name() == name)
+    }
+
+    /// Get the IDs of the databases in the order they are searched
+    /// for the plugins, requested by a plugin from the database with
+    /// the ID provided, if any
+    pub fn search_order (
+        self: &Self,
+        own: Option<usize>,
+    ) -> Vec<usize> {
+
+        let mut result: Vec<usize> = Vec::new();
+        let ids = own.into_iter()
+            .chain(self.defaults.iter().copied())
+            .chain(0..self.databases.len());
+        for id in ids {
+            if id < self.databases.len() && !result.contains(&id) {
+                result.push(id);
+            }
+        }
+        return result;
+    }
+
+    /// Find the plugin, fitting the request provided, made by a plugin
+    /// from the database with the ID provided, if any
+    ///
+    /// Only the requests for a specific plugin or trait can be
+    /// fulfilled this way, every other request is never found
+    ///
+    /// # Safety
+    ///
+    /// See [`PluginDatabase::candidates`]
+    pub unsafe fn locate (
+        self: &Self,
+        own: Option<usize>,
+        request: &InterplugRequest,
+    ) -> Result<Option<LocatedPlugin>, Error> {
+
+        for id in self.search_order(own) {
+            let mut result: Option<PluginCandidate> = None;
+            for candidate in self.databases[id].candidates()? {
+                if !self.fits(&candidate, request) {
+                    continue;
+                }
+                let preferred: bool = match &result {
+                    Some(found) => candidate.freight_version
+                        .is_preferred_to(&found.freight_version),
+                    None => true,
+                };
+                if preferred {
+                    result = Some(candidate);
+                }
+            }
+            if let Some(candidate) = result {
+                return Ok(Some(LocatedPlugin {
+                    database: id,
+                    candidate: candidate,
+                }));
+            }
+        }
+        return Ok(None);
+    }
+
+    /// Find the plugin, fitting the request provided, in the same
+    /// order as [`PluginLocator::locate`] does and load it
+    ///
+    /// # Safety
+    ///
+    /// See [`PluginDatabase::candidates`] and [`FreightProxy::load`]
+    pub unsafe fn load (
+        self: &Self,
+        own: Option<usize>,
+        request: &InterplugRequest,
+    ) -> Result<FreightProxy, Error> {
+
+        match self.locate(own, request)? {
+            Some(found) => found.candidate.load(),
+            None => Err(ImportError(
+                    format!(
+                        "No plugin fitting the request {} was found \
+                        in any database",
+                        request,
+                    ))),
+        }
+    }
+
+    /// Check if the plugin provided fits the request, taking the
+    /// pre-release policy into account
+    fn fits (
+        self: &Self,
+        candidate: &PluginCandidate,
+        request: &InterplugRequest,
+    ) -> bool {
+
+        let mut reasons: Vec<Rejection> = match candidate.check_request(request) {
+            Ok(()) => Vec::new(),
+            Err(reasons) => reasons,
+        };
+        self.pre_releases.apply(&candidate.freight_version, &mut reasons);
+        return reasons.is_empty();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Function, that makes a database with the name provided, that
+    /// has the "storage" plugin of every version provided
+    fn test_database (
+        name: &str,
+        versions: &[&str],
+    ) -> Box<dyn PluginDatabase> {
+
+        let mut database: MemoryDatabase = MemoryDatabase::new(name.to_string());
+        for version in versions {
+            database.add(PluginCandidate {
+                path: format!("{}/{}/libstorage.so", name, version).into(),
+                from_manifest: true,
+                name: "storage".to_string(),
+                freight_version: version.parse().unwrap(),
+                backwards_compat_version: version.parse().unwrap(),
+                api_version: API_VERSION.to_string(),
+                layout_fingerprint: Some(LAYOUT_FINGERPRINT),
+                rustc_version: RUSTC_VERSION.to_string(),
+                abi_version: None,
+                linked: false,
+            });
+        }
+        return Box::new(database);
+    }
+
+    /// Function, that locates the "storage" plugin, matching the
+    /// requirement provided, returning the database and the version
+    /// found
+    fn locate (
+        locator: &PluginLocator,
+        own: Option<usize>,
+        requirement: &str,
+    ) -> Option<(usize, String)> {
+
+        let request: InterplugRequest = PlugRequestAll {
+            plugin: "storage".to_string(),
+            version: requirement.parse().unwrap(),
+        };
+        unsafe { locator.locate(own, &request) }.unwrap()
+            .map(|found| (found.database, found.candidate.freight_version.to_string()))
+    }
+
+    #[test]
+    fn search_order () {
+        let mut locator: PluginLocator = PluginLocator::new();
+        for name in &["first", "second", "third", "fourth"] {
+            locator.add(test_database(name, &[]));
+        }
+        locator.defaults = vec![2, 1];
+
+        assert_eq!(locator.search_order(None), vec![2, 1, 0, 3]);
+        assert_eq!(locator.search_order(Some(3)), vec![3, 2, 1, 0]);
+        assert_eq!(locator.search_order(Some(1)), vec![1, 2, 0, 3]);
+        assert_eq!(locator.search_order(Some(7)), vec![2, 1, 0, 3]);
+        assert_eq!(locator.get_id("third"), Some(2));
+    }
+
+    #[test]
+    fn lookup_order () {
+        let mut locator: PluginLocator = PluginLocator::new();
+        let oldest: usize = locator.add(test_database("oldest", &["1.0", "1.1"]));
+        let newest: usize = locator.add(test_database("newest", &["1.3"]));
+        let own: usize = locator.add(test_database("own", &["1.2", "2.0"]));
+        let default: usize =
+            locator.add_default(test_database("default", &["1.5", "1.4-rc.1"]));
+
+        // The own database first, choosing the latest fitting version
+        assert_eq!(locate(&locator, Some(own), "^1"), Some((own, "1.2.0.0".to_string())));
+        assert_eq!(locate(&locator, Some(own), "*"), Some((own, "2.0.0.0".to_string())));
+
+        // Then the defaults, then all the known ones from the oldest
+        assert_eq!(locate(&locator, None, "^1"), Some((default, "1.5.0.0".to_string())));
+        assert_eq!(locate(&locator, Some(own), "<1.2"),
+            Some((oldest, "1.1.0.0".to_string())));
+        assert_eq!(locate(&locator, None, "~1.3"), Some((newest, "1.3.0.0".to_string())));
+        assert_eq!(locate(&locator, None, "^3"), None);
+    }
+
+    #[test]
+    fn pre_release_lookup () {
+        let mut locator: PluginLocator = PluginLocator::new();
+        let stable: usize = locator.add(test_database("stable", &["1.3"]));
+        let nightly: usize = locator.add_default(test_database("nightly", &["1.4-rc.1"]));
+
+        assert_eq!(locate(&locator, None, "^1.2"), Some((stable, "1.3.0.0".to_string())));
+        assert_eq!(locate(&locator, None, ">=1.4-rc.1"),
+            Some((nightly, "1.4.0.0-rc.1".to_string())));
+
+        locator.pre_releases = PreReleasePolicy::Always;
+        assert_eq!(locate(&locator, None, "^1.2").map(|found| found.0), Some(nightly));
+        locator.pre_releases = PreReleasePolicy::Never;
+        assert_eq!(locate(&locator, None, ">=1.4-rc.1"), None);
+    }
+}
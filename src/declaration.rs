@@ -43,6 +43,14 @@ pub struct FreightDeclaration {
     /// Api version as a static string
     pub api_version: String,
 
+    /// Fingerprint of the layout of the core structures, the plugin
+    /// was built with (see [`LAYOUT_FINGERPRINT`])
+    ///
+    /// This field can only be read, once the layouts are known to
+    /// match, so plugin libraries also export the fingerprint on its
+    /// own (see [`export_freight!`]), which is checked first
+    pub layout_fingerprint: u64,
+
     /// Version of the freight being imported
     pub freight_version: Version,
 
@@ -70,6 +78,7 @@ impl std::fmt::Debug for FreightDeclaration {
         f.debug_struct("FreightDeclaration")
             .field("rustc_version", &self.rustc_version)
             .field("api_version", &self.api_version)
+            .field("layout_fingerprint", &self.layout_fingerprint)
             .field("freight_version", &self.freight_version)
             .field("backwards_compat_version",
                 &self.backwards_compat_version)
@@ -84,6 +93,13 @@ impl std::fmt::Debug for FreightDeclaration {
 /// wrapper [`export_plugin`] to make sure the plugin is
 /// compatible with the program using it
 ///
+/// The macro exports both a [`FreightDeclaration`], used when the
+/// program was built with the same compiler and layout of the core
+/// structures (see [`LAYOUT_FINGERPRINT`]),
+/// and an [`AbiFreightDeclaration`], used otherwise. The layout
+/// fingerprint is exported on its own as well, as a plain number,
+/// that can be read and compared before anything else is
+///
 /// To learn more about structure, required to register the
 /// plugins behavior, see [`Freight`] trait documentation
 ///
@@ -135,30 +151,82 @@ impl std::fmt::Debug for FreightDeclaration {
 #[macro_export]
 macro_rules! export_freight {
     ($name:expr, $version:expr, $register:expr) => {
+        #[doc(hidden)]
+        #[no_mangle]
+        pub static freight_layout_fingerprint: u64
+            = $crate::LAYOUT_FINGERPRINT;
+
         #[doc(hidden)]
         #[no_mangle]
         pub static freight_declaration: $crate::FreightDeclaration
             = $crate::FreightDeclaration {
                 rustc_version: $crate::RUSTC_VERSION,
                 api_version: $crate::API_VERSION,
+                layout_fingerprint: $crate::LAYOUT_FINGERPRINT,
                 freight_version: $version,
                 backwards_compat_version: $version,
                 name: $name,
                 register: $register,
             };
+
+        #[doc(hidden)]
+        #[no_mangle]
+        pub static abi_freight_declaration: $crate::AbiFreightDeclaration
+            = $crate::AbiFreightDeclaration {
+                abi_version: $crate::ABI_VERSION,
+                describe: {
+                    extern "C" fn describe () -> $crate::AbiBuffer {
+                        $crate::abi_describe($name, $version, $version)
+                    }
+                    describe
+                },
+                create: {
+                    extern "C" fn create () -> $crate::AbiFreight {
+                        $crate::abi_create($register)
+                    }
+                    create
+                },
+                free_buffer: $crate::abi_free_buffer,
+            };
     };
     ($name:expr, $version:expr, $back_version:expr, $register:expr) => {
+        #[doc(hidden)]
+        #[no_mangle]
+        pub static freight_layout_fingerprint: u64
+            = $crate::LAYOUT_FINGERPRINT;
+
         #[doc(hidden)]
         #[no_mangle]
         pub static freight_declaration: $crate::FreightDeclaration
             = $crate::FreightDeclaration {
                 rustc_version: $crate::RUSTC_VERSION,
                 api_version: $crate::API_VERSION,
+                layout_fingerprint: $crate::LAYOUT_FINGERPRINT,
                 freight_version: $version,
                 backwards_compat_version: $back_version,
                 name: $name,
                 register: $register,
             };
+
+        #[doc(hidden)]
+        #[no_mangle]
+        pub static abi_freight_declaration: $crate::AbiFreightDeclaration
+            = $crate::AbiFreightDeclaration {
+                abi_version: $crate::ABI_VERSION,
+                describe: {
+                    extern "C" fn describe () -> $crate::AbiBuffer {
+                        $crate::abi_describe($name, $version, $back_version)
+                    }
+                    describe
+                },
+                create: {
+                    extern "C" fn create () -> $crate::AbiFreight {
+                        $crate::abi_create($register)
+                    }
+                    create
+                },
+                free_buffer: $crate::abi_free_buffer,
+            };
     };
 }
 
@@ -191,6 +259,30 @@ macro_rules! register_freight {
     };
 }
 
+/// A macro, which can be used instead of [`register_freight!`] to
+/// create a registry function for a freight, that may be moved to
+/// another thread, so the plugin can also be loaded as a
+/// [`SyncFreightProxy`]
+///
+/// # Example
+///
+/// ``` rust, ignore
+/// dusk_api::register_sync_freight!(MyFreight, my_reg_fn);
+/// dusk_api::export_freight!(
+///     "test",
+///     Version {major: 1, minor: 23, ..Default::default() },
+///     my_reg_fn);
+/// ```
+#[macro_export]
+macro_rules! register_sync_freight {
+    ($freight: expr, $name: ident) => {
+        #[doc(hidden)]
+        pub fn $name (registrar: &mut dyn $crate::FreightRegistrar) {
+            registrar.register_sync_freight(Box::new($freight));
+        }
+    };
+}
+
 /// A macro, which can be used to make exporting of a struct
 /// easier
 ///
@@ -0,0 +1,836 @@
+// Copyright (C) 2021 by Andy Gozas <andy@gozas.me>
+//
+// This file is part of Dusk API.
+//
+// Dusk API is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Dusk API is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Dusk API.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Module, containing everything needed to find plugins in the
+//! file system and find out what they are before loading them
+
+use crate::*;
+
+/// Extension, appended to the full library file name to get the
+/// name of the manifest file, describing that library
+///
+/// For example, the manifest for `libtest_plug.so` has to be named
+/// `libtest_plug.so.manifest` and be located in the same directory
+pub static MANIFEST_EXTENSION: &str = "manifest";
+
+/// A structure, describing a plugin library, that was found, but
+/// not loaded yet
+///
+/// It contains everything that is exported in the
+/// [`FreightDeclaration`] except for the register function, so
+/// a plugin may be chosen by its name and versions before it is
+/// loaded with [`PluginCandidate::load`]
+#[derive(Clone, Debug)]
+pub struct PluginCandidate {
+
+    /// Path to the plugin library
+    pub path: std::path::PathBuf,
+
+    /// Whether the information was read from the manifest file,
+    /// instead of the library itself
+    pub from_manifest: bool,
+
+    /// Name of the plugin
+    pub name: String,
+
+    /// Version of the plugin
+    pub freight_version: Version,
+
+    /// The earliest plugin version, for which the code could have
+    /// been designed, and still be run with this version of plugin
+    pub backwards_compat_version: Version,
+
+    /// Dusk API version, the plugin was built with
+    pub api_version: String,
+
+    /// Fingerprint of the layout of the core structures, the plugin
+    /// was built with (see [`LAYOUT_FINGERPRINT`]), if it is known
+    pub layout_fingerprint: Option<u64>,
+
+    /// Rust compiler version, the plugin was built with
+    pub rustc_version: String,
+
+    /// Version of the C ABI declaration the plugin exports, if it
+    /// exports one (see [`AbiFreightDeclaration`])
+    pub abi_version: Option<u32>,
+
+    /// Whether the plugin is linked into the program statically (see
+    /// [`STATIC_FREIGHTS`]), in which case the path is the path of
+    /// the program itself
+    pub linked: bool,
+}
+
+impl PluginCandidate {
+
+    /// Build a candidate from a [`FreightDeclaration`] of a library,
+    /// located at the path provided
+    pub fn from_declaration (
+        path: &std::path::Path,
+        declaration: &FreightDeclaration,
+    ) -> PluginCandidate {
+
+        PluginCandidate {
+            path: path.to_path_buf(),
+            from_manifest: false,
+            name: declaration.name.clone(),
+            freight_version: declaration.freight_version.clone(),
+            backwards_compat_version:
+                declaration.backwards_compat_version.clone(),
+            api_version: declaration.api_version.clone(),
+            layout_fingerprint: Some(declaration.layout_fingerprint),
+            rustc_version: declaration.rustc_version.clone(),
+            abi_version: None,
+            linked: false,
+        }
+    }
+
+    /// Build a candidate from the [`FreightDeclaration`] of a plugin,
+    /// linked into the program statically
+    pub fn from_static (
+        declaration: &FreightDeclaration,
+    ) -> PluginCandidate {
+
+        let path: std::path::PathBuf = match std::env::current_exe() {
+            Ok(path) => path,
+            Err(_) => std::path::PathBuf::new(),
+        };
+        let mut result: PluginCandidate =
+            PluginCandidate::from_declaration(&path, declaration);
+        result.linked = true;
+        return result;
+    }
+
+    /// Build a candidate from a [`FreightDescription`], read from
+    /// the C ABI declaration of a library, located at the path
+    /// provided
+    pub fn from_description (
+        path: &std::path::Path,
+        description: &FreightDescription,
+    ) -> PluginCandidate {
+
+        PluginCandidate {
+            path: path.to_path_buf(),
+            from_manifest: false,
+            name: description.name.clone(),
+            freight_version: description.freight_version.clone(),
+            backwards_compat_version:
+                description.backwards_compat_version.clone(),
+            api_version: description.api_version.clone(),
+            layout_fingerprint: Some(description.layout_fingerprint),
+            rustc_version: description.rustc_version.clone(),
+            abi_version: Some(ABI_VERSION),
+            linked: false,
+        }
+    }
+
+    /// Read the candidate from the manifest file, located at
+    /// `manifest_path`, describing the library located at
+    /// `lib_path`
+    ///
+    /// The manifest is a text file, every line of which is either
+    /// empty, a comment starting with `#`, or a `key = value` pair.
+    /// All of the keys `name`, `freight_version`, `api_version` and
+    /// `rustc_version` are required, `backwards_compat_version`
+    /// defaults to the `freight_version`, `layout_fingerprint` (see
+    /// [`LAYOUT_FINGERPRINT`]) may be omitted, in which case the Dusk
+    /// API version has to match exactly, `abi_version` should only
+    /// be there if the plugin exports the C ABI declaration. Versions
+    /// are written as numbers separated by dots (e.g. `1.23.0.0`, see
+    /// [`Version`])
+    ///
+    /// # Example
+    /// ``` text
+    /// name = test
+    /// freight_version = 1.23.0.0
+    /// backwards_compat_version = 0.6.0.0
+    /// api_version = 0.2.0
+    /// rustc_version = 1.52.1
+    /// layout_fingerprint = 6473893489302843213
+    /// abi_version = 1
+    /// ```
+    pub fn from_manifest (
+        lib_path: &std::path::Path,
+        manifest_path: &std::path::Path,
+    ) -> Result<PluginCandidate, Error> {
+
+        let text: String = match std::fs::read_to_string(manifest_path) {
+            Ok(text) => text,
+            Err(err) => return Err(OsError(
+                    format!(
+                        "Could not read manifest {}: {}",
+                        manifest_path.display(),
+                        err,
+                    ))),
+        };
+
+        let mut values: std::collections::HashMap<String, String> =
+            std::collections::HashMap::new();
+        for line in text.lines() {
+            let line: &str = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.find('=') {
+                Some(pos) => {
+                    values.insert(
+                        line[..pos].trim().to_string(),
+                        line[pos + 1..].trim().to_string());
+                },
+                None => return Err(ImportError(
+                        format!(
+                            "Invalid line in manifest {}: {}",
+                            manifest_path.display(),
+                            line,
+                        ))),
+            }
+        }
+
+        let get = |key: &str| -> Result<String, Error> {
+            match values.get(key) {
+                Some(value) => Ok(value.clone()),
+                None => Err(ImportError(
+                        format!(
+                            "Manifest {} does not contain {}",
+                            manifest_path.display(),
+                            key,
+                        ))),
+            }
+        };
+
+        let freight_version: Version =
+            get("freight_version")?.parse()?;
+        let backwards_compat_version: Version =
+            match values.get("backwards_compat_version") {
+                Some(value) => value.parse()?,
+                None => freight_version.clone(),
+            };
+        let layout_fingerprint: Option<u64> =
+            match values.get("layout_fingerprint") {
+                Some(value) => match value.parse::<u64>() {
+                    Ok(fingerprint) => Some(fingerprint),
+                    Err(_) => return Err(ImportError(
+                            format!(
+                                "Invalid layout_fingerprint in manifest {}: {}",
+                                manifest_path.display(),
+                                value,
+                            ))),
+                },
+                None => None,
+            };
+        let abi_version: Option<u32> =
+            match values.get("abi_version") {
+                Some(value) => match value.parse::<u32>() {
+                    Ok(version) => Some(version),
+                    Err(_) => return Err(ImportError(
+                            format!(
+                                "Invalid abi_version in manifest {}: {}",
+                                manifest_path.display(),
+                                value,
+                            ))),
+                },
+                None => None,
+            };
+
+        return Ok(PluginCandidate {
+            path: lib_path.to_path_buf(),
+            from_manifest: true,
+            name: get("name")?,
+            freight_version: freight_version,
+            backwards_compat_version: backwards_compat_version,
+            api_version: get("api_version")?,
+            layout_fingerprint: layout_fingerprint,
+            rustc_version: get("rustc_version")?,
+            abi_version: abi_version,
+            linked: false,
+        });
+    }
+
+    /// Read the candidate from the library, located at the path
+    /// provided
+    ///
+    /// # Safety
+    ///
+    /// The library is opened to read its [`FreightDeclaration`], which
+    /// means that any initialization code the library has is run. The
+    /// register function of the plugin is never called though, and the
+    /// library is closed right after the declaration is read.
+    pub unsafe fn from_library (
+        lib_path: &std::path::Path,
+    ) -> Result<PluginCandidate, Error> {
+
+        let lib: libloading::Library =
+            match libloading::Library::new(lib_path) {
+                Ok(library) => library,
+                Err(lib_err) => return Err(LoadingError(lib_err)),
+            };
+
+        // The C ABI declaration can be read whatever compiler the
+        // plugin was built with, so it is preferred
+        if let Ok(decl) = lib.get::<*mut AbiFreightDeclaration>(
+            b"abi_freight_declaration\0") {
+
+            let declaration: AbiFreightDeclaration = **decl;
+            if declaration.abi_version == ABI_VERSION {
+                let description: FreightDescription =
+                    FreightDescription::from_abi(&declaration)?;
+                return Ok(PluginCandidate::from_description(
+                        lib_path,
+                        &description));
+            }
+        }
+
+        // The declaration may only be read if its layout matches
+        check_library_layout(&lib)?;
+
+        let declaration: FreightDeclaration;
+        match lib.get::<*mut FreightDeclaration>(
+            b"freight_declaration\0") {
+
+            Ok(decl) => declaration = (**decl).clone(),
+            Err(lib_err) => return Err(LoadingError(lib_err)),
+        }
+
+        return Ok(PluginCandidate::from_declaration(lib_path, &declaration));
+    }
+
+    /// Find out what plugin the library at the path provided holds,
+    /// reading the manifest file next to it if there is one, and
+    /// reading the library itself otherwise (unless `manifest_only`
+    /// is set)
+    ///
+    /// # Safety
+    ///
+    /// See [`PluginCandidate::from_library`]
+    pub unsafe fn inspect (
+        lib_path: &std::path::Path,
+        manifest_only: bool,
+    ) -> Result<PluginCandidate, Error> {
+
+        let manifest_path: std::path::PathBuf = manifest_path(lib_path);
+        if manifest_path.is_file() {
+            return PluginCandidate::from_manifest(lib_path, &manifest_path);
+        }
+        if manifest_only {
+            return Err(ImportError(
+                    format!(
+                        "No manifest found for {}",
+                        lib_path.display(),
+                    )));
+        }
+        return PluginCandidate::from_library(lib_path);
+    }
+
+    /// Find out what plugin the library at the path provided holds,
+    /// like [`PluginCandidate::inspect`] does, checking the library
+    /// with the verifier provided first
+    ///
+    /// If there is no manifest, the declaration is read from a
+    /// private copy of the checked contents (see
+    /// [`VerifiedLibrary`]), so no code of a library, that did not
+    /// pass the check, is run, even if it is replaced right after
+    /// the check
+    ///
+    /// # Safety
+    ///
+    /// See [`PluginCandidate::from_library`]
+    pub unsafe fn inspect_verified (
+        lib_path: &std::path::Path,
+        manifest_only: bool,
+        verifier: &LibraryVerifier,
+    ) -> Result<PluginCandidate, Error> {
+
+        let manifest_path: std::path::PathBuf = manifest_path(lib_path);
+        if manifest_path.is_file() {
+            // Nothing is loaded here, and the library is checked
+            // once again when it is (see load_verified)
+            verifier.verify(lib_path)?;
+            return PluginCandidate::from_manifest(lib_path, &manifest_path);
+        }
+        if manifest_only {
+            return Err(ImportError(
+                    format!(
+                        "No manifest found for {}",
+                        lib_path.display(),
+                    )));
+        }
+
+        let copy: VerifiedLibrary = verifier.copy_verified(lib_path)?;
+        let mut result: PluginCandidate =
+            PluginCandidate::from_library(copy.path())?;
+        result.path = lib_path.to_path_buf();
+        return Ok(result);
+    }
+
+    /// Check if the plugin can be loaded with [`FreightProxy::load`],
+    /// which requires it to either export the C ABI declaration of the
+    /// same version as the program that is using it, or be built with
+    /// the same compiler version and layout of the core structures
+    pub fn is_compatible (self: &Self) -> bool {
+        self.abi_version == Some(ABI_VERSION) || self.is_native()
+    }
+
+    /// Check if the plugin was built with the same compiler version
+    /// and layout of the core structures (see [`LAYOUT_FINGERPRINT`])
+    /// as the program that is using it, which is required by
+    /// [`FreightProxy::load_strict`]
+    ///
+    /// If the layout fingerprint of the plugin is not known, the
+    /// Dusk API versions have to match exactly instead
+    pub fn is_native (self: &Self) -> bool {
+        self.rustc_version == RUSTC_VERSION &&
+            match self.layout_fingerprint {
+                Some(fingerprint) => fingerprint == LAYOUT_FINGERPRINT,
+                None => self.api_version == API_VERSION,
+            }
+    }
+
+    /// Check if the plugin could be used to fulfill the request
+    /// provided, before it is loaded, returning every reason it could
+    /// not (see [`InterplugRequest::check_provider`])
+    pub fn check_request (
+        self: &Self,
+        request: &InterplugRequest,
+    ) -> Result<(), Vec<Rejection>> {
+
+        request.check_provider(
+            &self.name,
+            &self.freight_version,
+            &self.backwards_compat_version)
+    }
+
+    /// Build the text of the manifest, describing this candidate, in
+    /// the format read by [`PluginCandidate::from_manifest`]
+    pub fn to_manifest (self: &Self) -> String {
+        let mut result: String = format!(
+            "name = {}\nfreight_version = {}\n\
+            backwards_compat_version = {}\napi_version = {}\n\
+            rustc_version = {}\n",
+            self.name,
+            self.freight_version,
+            self.backwards_compat_version,
+            self.api_version,
+            self.rustc_version,
+        );
+        if let Some(fingerprint) = self.layout_fingerprint {
+            result.push_str(&format!("layout_fingerprint = {}\n", fingerprint));
+        }
+        if let Some(abi_version) = self.abi_version {
+            result.push_str(&format!("abi_version = {}\n", abi_version));
+        }
+        return result;
+    }
+
+    /// Write the manifest, describing this candidate, next to the
+    /// library it describes
+    pub fn write_manifest (self: &Self) -> Result<(), Error> {
+        let manifest_path: std::path::PathBuf = manifest_path(&self.path);
+        match std::fs::write(&manifest_path, self.to_manifest()) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(OsError(
+                    format!(
+                        "Could not write manifest {}: {}",
+                        manifest_path.display(),
+                        err,
+                    ))),
+        }
+    }
+
+    /// Load the plugin this candidate describes, checking the library
+    /// with the verifier provided first (see
+    /// [`FreightProxy::load_verified`]). Statically linked plugins
+    /// are not checked, as they are a part of the program itself
+    ///
+    /// The library is loaded from a private copy of the checked
+    /// contents, so it can not be replaced after the check
+    ///
+    /// # Safety
+    ///
+    /// See [`FreightProxy::load_verified`]
+    pub unsafe fn load_verified (
+        self: &Self,
+        verifier: &LibraryVerifier,
+    ) -> Result<FreightProxy, Error> {
+
+        if self.linked {
+            return self.load();
+        }
+        match self.path.to_str() {
+            Some(path) => FreightProxy::load_verified(path, verifier),
+            None => Err(ImportError(
+                    format!(
+                        "Library path {} is not valid unicode",
+                        self.path.display(),
+                    ))),
+        }
+    }
+
+    /// Load the plugin this candidate describes
+    ///
+    /// # Safety
+    ///
+    /// See [`FreightProxy::load`]
+    pub unsafe fn load (self: &Self) -> Result<FreightProxy, Error> {
+        if self.linked {
+            return match find_static_declaration(
+                &self.name,
+                Some(&self.freight_version)) {
+
+                Some(declaration) =>
+                    FreightProxy::load_from_declaration(&declaration),
+                None => Err(ImportError(
+                        format!(
+                            "No plugin named {} is linked statically",
+                            self.name,
+                        ))),
+            };
+        }
+        match self.path.to_str() {
+            Some(path) => FreightProxy::load(path),
+            None => Err(ImportError(
+                    format!(
+                        "Library path {} is not valid unicode",
+                        self.path.display(),
+                    ))),
+        }
+    }
+}
+
+/// A structure, that is used to find all the plugins in a list of
+/// directories
+///
+/// Every file in each of the search directories, that has the
+/// extension of a dynamic library on the current platform (e.g.
+/// `so` on linux) is considered to be a plugin library. Libraries
+/// that turn out not to be plugins are skipped. The plugins linked
+/// into the program statically may be listed as well.
+///
+/// # Example
+///
+/// ``` rust, ignore
+/// let discovery: PluginDiscovery = PluginDiscovery {
+///     search_dirs: vec!["/usr/lib/dusk".into(), "/home/user/.dusk".into()],
+///     manifest_only: false,
+///     verifier: None,
+///     include_static: true,
+/// };
+/// for candidate in unsafe { discovery.discover() }.unwrap() {
+///     println!("{} {:?}", candidate.name, candidate.freight_version);
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PluginDiscovery {
+
+    /// Directories to look for plugin libraries in, in the order
+    /// they should be searched
+    pub search_dirs: Vec<std::path::PathBuf>,
+
+    /// Only use manifest files, never opening the libraries
+    /// themselves, so no plugin code is run before a plugin is
+    /// actually chosen and loaded
+    pub manifest_only: bool,
+
+    /// Verifier, every library is checked with before it is opened
+    /// to read its declaration, if set. Libraries, that do not pass
+    /// the check, are skipped
+    pub verifier: Option<LibraryVerifier>,
+
+    /// Also list the plugins, linked into the program statically
+    /// (see [`STATIC_FREIGHTS`]), before the ones found in the search
+    /// directories
+    pub include_static: bool,
+}
+
+impl PluginDiscovery {
+
+    /// Create a discovery, searching the directories provided
+    pub fn new (
+        search_dirs: Vec<std::path::PathBuf>,
+    ) -> PluginDiscovery {
+
+        PluginDiscovery {
+            search_dirs: search_dirs,
+            manifest_only: false,
+            verifier: None,
+            include_static: false,
+        }
+    }
+
+    /// List the paths of all libraries in the search directories,
+    /// sorted by name inside of each directory
+    ///
+    /// The directories, that do not exist or can not be read, and the
+    /// entries, that fail to be read, are skipped, so that one bad
+    /// path does not hide the plugins in the other ones
+    pub fn find_libraries (
+        self: &Self,
+    ) -> Result<Vec<std::path::PathBuf>, Error> {
+
+        let mut result: Vec<std::path::PathBuf> = Vec::new();
+        for dir in &self.search_dirs {
+            let entries: std::fs::ReadDir = match std::fs::read_dir(dir) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+            let mut dir_result: Vec<std::path::PathBuf> = Vec::new();
+            for entry in entries {
+                let path: std::path::PathBuf = match entry {
+                    Ok(entry) => entry.path(),
+                    Err(_) => continue,
+                };
+                if !path.is_file() {
+                    continue;
+                }
+                match path.extension() {
+                    Some(ext) if ext == std::env::consts::DLL_EXTENSION =>
+                        dir_result.push(path),
+                    _ => (),
+                }
+            }
+            dir_result.sort();
+            result.append(&mut dir_result);
+        }
+        return Ok(result);
+    }
+
+    /// Find out what plugins are in the search directories
+    ///
+    /// # Safety
+    ///
+    /// Unless `manifest_only` is set, libraries without manifests
+    /// are opened (see [`PluginCandidate::from_library`])
+    pub unsafe fn discover (
+        self: &Self,
+    ) -> Result<Vec<PluginCandidate>, Error> {
+
+        let mut result: Vec<PluginCandidate> = Vec::new();
+        if self.include_static {
+            for declaration in static_declarations() {
+                result.push(PluginCandidate::from_static(&declaration));
+            }
+        }
+        for path in self.find_libraries()? {
+            let candidate: Result<PluginCandidate, Error> =
+                match &self.verifier {
+                    Some(verifier) => PluginCandidate::inspect_verified(
+                        &path,
+                        self.manifest_only,
+                        verifier),
+                    None => PluginCandidate::inspect(&path, self.manifest_only),
+                };
+            match candidate {
+                Ok(candidate) => result.push(candidate),
+                Err(_) => continue,
+            }
+        }
+        return Ok(result);
+    }
+
+    /// Find all the plugins with the name provided in the search
+    /// directories
+    ///
+    /// # Safety
+    ///
+    /// See [`PluginDiscovery::discover`]
+    pub unsafe fn discover_by_name (
+        self: &Self,
+        name: &String,
+    ) -> Result<Vec<PluginCandidate>, Error> {
+
+        let mut result: Vec<PluginCandidate> = self.discover()?;
+        result.retain(|candidate| candidate.name.eq(name));
+        return Ok(result);
+    }
+}
+
+/// Get the path of the manifest file for the library provided
+fn manifest_path (
+    lib_path: &std::path::Path,
+) -> std::path::PathBuf {
+
+    let mut path: std::ffi::OsString = lib_path.as_os_str().to_os_string();
+    path.push(".");
+    path.push(MANIFEST_EXTENSION);
+    return std::path::PathBuf::from(path);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Get a path in a directory, private to the test with the name
+    /// provided, removing whatever the previous run left there
+    fn scratch_path (
+        test: &str,
+        file: &str,
+    ) -> std::path::PathBuf {
+
+        let dir: std::path::PathBuf = std::env::temp_dir().join(format!(
+                "dusk-discovery-{}",
+                test,
+            ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        return dir.join(file);
+    }
+
+    /// Build a candidate with every field filled in
+    fn candidate (path: &std::path::Path) -> PluginCandidate {
+        PluginCandidate {
+            path: path.to_path_buf(),
+            from_manifest: false,
+            name: "test".to_string(),
+            freight_version: "1.23-rc.1+nightly.5".parse().unwrap(),
+            backwards_compat_version: "0.6".parse().unwrap(),
+            api_version: "0.2.0".to_string(),
+            layout_fingerprint: Some(6473893489302843213),
+            rustc_version: "1.52.1".to_string(),
+            abi_version: Some(ABI_VERSION),
+            linked: false,
+        }
+    }
+
+    /// Check that the candidate, read from a manifest, describes the
+    /// same plugin as the one provided
+    fn assert_same (
+        read: &PluginCandidate,
+        written: &PluginCandidate,
+    ) {
+
+        assert!(read.from_manifest);
+        assert_eq!(read.path, written.path);
+        assert_eq!(read.name, written.name);
+        assert_eq!(read.freight_version, written.freight_version);
+        assert_eq!(read.freight_version.pre_release, written.freight_version.pre_release);
+        assert_eq!(read.freight_version.metadata, written.freight_version.metadata);
+        assert_eq!(read.backwards_compat_version, written.backwards_compat_version);
+        assert_eq!(read.api_version, written.api_version);
+        assert_eq!(read.layout_fingerprint, written.layout_fingerprint);
+        assert_eq!(read.rustc_version, written.rustc_version);
+        assert_eq!(read.abi_version, written.abi_version);
+        assert_eq!(read.linked, written.linked);
+    }
+
+    #[test]
+    fn find_libraries () {
+        let first: std::path::PathBuf = scratch_path("libraries", "first");
+        let second: std::path::PathBuf = first.with_file_name("second");
+        std::fs::create_dir_all(&first).unwrap();
+        std::fs::create_dir_all(&second).unwrap();
+        let library = |dir: &std::path::Path, name: &str| -> std::path::PathBuf {
+            dir.join(format!("{}.{}", name, std::env::consts::DLL_EXTENSION))
+        };
+        for path in &[
+            library(&first, "libb"),
+            library(&first, "liba"),
+            library(&second, "libc"),
+            first.join("libd.manifest"),
+        ] {
+            std::fs::write(path, "").unwrap();
+        }
+        std::fs::create_dir_all(library(&second, "libdir")).unwrap();
+
+        // Missing directories do not hide the plugins in the others
+        let discovery: PluginDiscovery = PluginDiscovery::new(vec![
+            first.with_file_name("missing"),
+            first.clone(),
+            library(&second, "libc"),
+            second.clone(),
+        ]);
+        assert_eq!(
+            discovery.find_libraries().unwrap(),
+            vec![
+                library(&first, "liba"),
+                library(&first, "libb"),
+                library(&second, "libc"),
+            ]);
+    }
+
+    #[test]
+    fn manifest_round_trip () {
+        let lib_path: std::path::PathBuf =
+            scratch_path("round_trip", "libtest.so");
+        let written: PluginCandidate = candidate(&lib_path);
+        written.write_manifest().unwrap();
+
+        let manifest: std::path::PathBuf = manifest_path(&lib_path);
+        assert_eq!(
+            manifest.file_name().unwrap(),
+            format!("libtest.so.{}", MANIFEST_EXTENSION).as_str());
+        let read: PluginCandidate =
+            PluginCandidate::from_manifest(&lib_path, &manifest).unwrap();
+        assert_same(&read, &written);
+        assert_eq!(read.to_manifest(), written.to_manifest());
+    }
+
+    #[test]
+    fn manifest_round_trip_without_optional_keys () {
+        let lib_path: std::path::PathBuf =
+            scratch_path("optional_keys", "libtest.so");
+        let mut written: PluginCandidate = candidate(&lib_path);
+        written.layout_fingerprint = None;
+        written.abi_version = None;
+        written.write_manifest().unwrap();
+
+        let text: String = written.to_manifest();
+        assert!(!text.contains("layout_fingerprint"));
+        assert!(!text.contains("abi_version"));
+        let read: PluginCandidate = PluginCandidate::from_manifest(
+            &lib_path, &manifest_path(&lib_path)).unwrap();
+        assert_same(&read, &written);
+    }
+
+    #[test]
+    fn manifest_defaults_and_comments () {
+        let manifest: std::path::PathBuf =
+            scratch_path("defaults", "test.manifest");
+        std::fs::write(&manifest, "# A plugin\n\n\
+            name = test\n  freight_version=1.2.3\n\
+            api_version = 0.2.0\nrustc_version = 1.52.1\n").unwrap();
+
+        let read: PluginCandidate = PluginCandidate::from_manifest(
+            std::path::Path::new("libtest.so"), &manifest).unwrap();
+        assert_eq!(read.name, "test");
+        assert_eq!(read.freight_version, "1.2.3".parse().unwrap());
+        assert_eq!(read.backwards_compat_version, read.freight_version);
+        assert_eq!(read.layout_fingerprint, None);
+        assert_eq!(read.abi_version, None);
+    }
+
+    #[test]
+    fn invalid_manifests () {
+        let manifest: std::path::PathBuf =
+            scratch_path("invalid", "test.manifest");
+        let lib_path: &std::path::Path = std::path::Path::new("libtest.so");
+        let valid: String = candidate(lib_path).to_manifest();
+
+        for text in &[
+            valid.replace("name = test\n", ""),
+            valid.replace("rustc_version", "# rustc_version"),
+            valid.replace("freight_version = ", "freight_version = one."),
+            valid.replace("layout_fingerprint = ", "layout_fingerprint = -"),
+            valid.replace("abi_version = ", "abi_version = v"),
+            format!("{}no value\n", valid),
+        ] {
+            std::fs::write(&manifest, text).unwrap();
+            assert!(
+                PluginCandidate::from_manifest(lib_path, &manifest).is_err(),
+                "{}", text);
+        }
+        assert!(PluginCandidate::from_manifest(
+                lib_path, &manifest.with_file_name("missing")).is_err());
+    }
+}
@@ -80,6 +80,10 @@ pub enum Error {
     /// Plugin import failed
     ImportError (String),
 
+    /// Plugin library did not pass the integrity check (see
+    /// [`LibraryVerifier`]), so it was not loaded
+    IntegrityError (String),
+
     /// An argument of wrong type received
     TypeError (String),
 
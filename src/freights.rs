@@ -30,6 +30,11 @@ use crate::*;
 /// under Any trait as well as the function name to refer to it and
 /// its identification number, which is needed to call this function
 ///
+/// Plugins, built with the same compiler, use this trait directly,
+/// so every change of its methods changes the vtable, shared with
+/// them, and has to be followed by an increase of its layout version
+/// (see [`LAYOUT_FINGERPRINT`])
+///
 /// # Example
 /// TODO
 pub trait Freight {
@@ -58,6 +63,38 @@ pub trait Freight {
         ()
     }
 
+    /// Function that provides every setting, the plugin can be
+    /// limited in, if the plugin declares them
+    ///
+    /// If it does, the limitations, that do not fit the schema, are
+    /// not passed to [`Freight::init`] and
+    /// [`Freight::update_limitations`] of a [`FreightProxy`], but
+    /// reported through [`FreightProxy::get_rejected_limitations`]
+    /// instead (see [`LimitationSchema::filter`])
+    fn get_limitation_schema (
+        self: &mut Self,
+    ) -> Option<LimitationSchema> {
+
+        None
+    }
+
+    /// Function that provides the value the plugin actually uses for
+    /// every setting it knows, along with what it did with the
+    /// limitations of each of them (see [`AppliedLimitations`]), if
+    /// the plugin keeps track of them
+    ///
+    /// If it does not, [`None`] is reported, as the values the plugin
+    /// uses are not known. Otherwise a [`FreightProxy`] adds the
+    /// limitations, that were rejected by the schema of the plugin
+    /// (see [`Freight::get_limitation_schema`]) and therefore never
+    /// reached it, to the settings they are for
+    fn get_effective_limitations (
+        self: &mut Self,
+    ) -> Option<Vec<EffectiveSetting>> {
+
+        None
+    }
+
     /// Function that replies to the interplugin request by
     /// providing the requested plugin
     fn interplug_provide (
@@ -66,6 +103,49 @@ pub trait Freight {
         _freight_proxy: std::rc::Rc<FreightProxy>,
     ) {}
 
+    /// Function that replies to the interplugin request by
+    /// providing the requested plugin as a [`SyncFreightProxy`],
+    /// which, unlike a [`FreightProxy`], may be kept by a plugin,
+    /// that is itself used from several threads
+    ///
+    /// By default the proxy is wrapped into a [`FreightProxy`] and
+    /// provided with [`Freight::interplug_provide`]
+    fn interplug_provide_sync (
+        self: &mut Self,
+        request: InterplugRequest,
+        freight_proxy: std::sync::Arc<SyncFreightProxy>,
+    ) {
+
+        let proxy: FreightProxy = FreightProxy::new(
+            freight_proxy.name.clone(),
+            freight_proxy.version.clone(),
+            freight_proxy.backwards_compat_version.clone(),
+            Box::new(freight_proxy));
+        self.interplug_provide(request, std::rc::Rc::new(proxy));
+    }
+
+    /// Function that replies to the interplugin request by
+    /// providing a [`WeakFreightProxy`] handle to the requested
+    /// plugin, which is done instead of [`Freight::interplug_provide`]
+    /// when the requested plugin depends on this plugin itself, either
+    /// directly or through other plugins
+    ///
+    /// The handle should be kept instead of the proxy it is upgraded
+    /// to, otherwise the plugins keep each other from being dropped.
+    /// By default the handle is upgraded and the proxy is provided
+    /// with [`Freight::interplug_provide`]
+    fn interplug_provide_weak (
+        self: &mut Self,
+        request: InterplugRequest,
+        freight_proxy: WeakFreightProxy,
+    ) {
+
+        match freight_proxy.upgrade() {
+            Ok(proxy) => self.interplug_provide(request, proxy),
+            Err(_) => self.interplug_deny(request),
+        }
+    }
+
     /// Function that replies to the interplugin request by
     /// by informing it that the request was denied
     fn interplug_deny (
@@ -73,6 +153,67 @@ pub trait Freight {
         _request: InterplugRequest,
     ) {}
 
+    /// Function that informs the plugin, that the plugin it was
+    /// provided earlier with [`Freight::interplug_provide`] was
+    /// replaced by a new build of the same library (see
+    /// [`PluginHost::reload`]). The old proxy is invalidated, so
+    /// the new one has to be used from now on
+    ///
+    /// By default the new proxy is simply provided once again
+    fn interplug_replace (
+        self: &mut Self,
+        request: InterplugRequest,
+        freight_proxy: std::rc::Rc<FreightProxy>,
+    ) {
+
+        self.interplug_provide(request, freight_proxy);
+    }
+
+    /// Function that informs the plugin, that the plugin it was
+    /// provided earlier to fulfill the request provided is about to
+    /// be shut down and unloaded (see [`PluginHost::shutdown`]), so
+    /// every copy of the proxy it was given has to be dropped, as it
+    /// can not be used anymore
+    fn interplug_withdraw (
+        self: &mut Self,
+        _request: InterplugRequest,
+    ) {}
+
+    /// Function that is ran before the plugin is dropped and its
+    /// library is unloaded, which may be reimplemented in a plugin if
+    /// it needs to release anything it holds, such as threads, files
+    /// or sockets, the counterpart of [`Freight::init`]
+    ///
+    /// The plugins, that were provided to this plugin, are still
+    /// usable at this point
+    fn shutdown (self: &mut Self) {}
+
+    /// Function that is called on the plugin when it is about to be
+    /// replaced by a new build of the same library, and returns the
+    /// state that should be handed over to the new instance through
+    /// [`Freight::import_state`]
+    ///
+    /// The state is passed as bytes, as nothing defined in the old
+    /// library may be used once it is unloaded
+    fn export_state (
+        self: &mut Self,
+    ) -> Result<Option<Vec<u8>>, Error> {
+
+        Ok(None)
+    }
+
+    /// Function that receives the state, exported by
+    /// [`Freight::export_state`] of the instance this plugin
+    /// replaces. It is called after the plugin was initialized and
+    /// got its requests answered
+    fn import_state (
+        self: &mut Self,
+        _state: Vec<u8>,
+    ) -> Result<(), Error> {
+
+        Ok(())
+    }
+
     /// The function that is used to provide the main module /
     /// modules of the plugin. Any function, constant or type
     /// are defined inside those modules
@@ -160,10 +301,7 @@ pub trait Freight {
         for def_fun in self.get_operator_list() {
             if (def_fun.name.eq(&"".to_string())) {
                 return Err(ImportError(
-                        format!(
-                            "{}",
-                            "Operators can not have empty names",
-                        )));
+                        "Operators can not have empty names".to_string()));
             }
             result_unsorted.push(def_fun.clone());
         }
@@ -171,10 +309,7 @@ pub trait Freight {
             for def_fun in module.functions {
                 if (def_fun.name.eq(&"".to_string())) {
                     return Err(ImportError(
-                            format!(
-                                "{}",
-                                "Functions can not have empty names",
-                            )));
+                            "Functions can not have empty names".to_string()));
                 }
                 result_unsorted.push(def_fun.clone());
                 result_unsorted.last_mut().unwrap().name = format!(
@@ -185,10 +320,7 @@ pub trait Freight {
             for def_con in module.constants {
                 if (def_con.name.eq(&"".to_string())) {
                     return Err(ImportError(
-                            format!(
-                                "{}",
-                                "Functions can not have empty names",
-                            )));
+                            "Functions can not have empty names".to_string()));
                 }
                 result_unsorted.push(def_con.clone());
                 result_unsorted.last_mut().unwrap().name = format!(
@@ -199,18 +331,12 @@ pub trait Freight {
             for def_type in module.types {
                 if (def_type.name.eq(&"".to_string())) {
                     return Err(ImportError(
-                            format!(
-                                "{}",
-                                "Types can not have empty names",
-                            )));
+                            "Types can not have empty names".to_string()));
                 }
                 for def_met in def_type.methods {
                     if (def_met.name.eq(&"".to_string())) {
                         return Err(ImportError(
-                                format!(
-                                    "{}",
-                                    "Type methods can not have empty names",
-                                )));
+                                "Type methods can not have empty names".to_string()));
                     }
                     result_unsorted.push(def_met.clone());
                     result_unsorted.last_mut().unwrap().name = format!(
@@ -222,10 +348,7 @@ pub trait Freight {
                 for def_fil in def_type.fields {
                     if (def_fil.name.eq(&"".to_string())) {
                         return Err(ImportError(
-                                format!(
-                                    "{}",
-                                    "Type fields can not have empty names",
-                                )));
+                                "Type fields can not have empty names".to_string()));
                     }
                     result_unsorted.push(def_fil.clone());
                     result_unsorted.last_mut().unwrap().name = format!(
@@ -238,10 +361,7 @@ pub trait Freight {
                     for def_met in def_trt.methods {
                         if (def_met.function.name.eq(&"".to_string())) {
                             return Err(ImportError(
-                                    format!(
-                                        "{}",
-                                        "Type methods can not have empty names",
-                                    )));
+                                    "Type methods can not have empty names".to_string()));
                         }
                         result_unsorted.push(def_met.function.clone());
                         result_unsorted.last_mut().unwrap().name = format!(
@@ -351,10 +471,7 @@ pub trait Freight {
             for mut def_type in module.types {
                 if (def_type.name.eq(&"".to_string())) {
                     return Err(ImportError(
-                            format!(
-                                "{}",
-                                "Types can not have empty names",
-                            )));
+                            "Types can not have empty names".to_string()));
                 }
                 def_type.name = format!(
                     "{}::{}",
@@ -471,10 +588,7 @@ pub trait Freight {
             for mut def_trt in module.trait_definitions {
                 if (def_trt.name.eq(&"".to_string())) {
                     return Err(ImportError(
-                            format!(
-                                "{}",
-                                "Traits can not have empty names",
-                            )));
+                            "Traits can not have empty names".to_string()));
                 }
                 def_trt.name = format!(
                     "{}::{}",
@@ -565,10 +679,7 @@ pub trait Freight {
         for module in root_modules {
             if (module.name.eq(&"".to_string())){
                 return Err(ImportError(
-                        format!(
-                            "{}",
-                            "Modules can not have empty names",
-                        )));
+                        "Modules can not have empty names".to_string()));
             }
             parents = Vec::new();
             par_progress = Vec::new();
@@ -591,10 +702,7 @@ pub trait Freight {
                     )) {
 
                         return Err(ImportError(
-                                format!(
-                                    "{}",
-                                    "Modules can not have empty names",
-                                )));
+                                "Modules can not have empty names".to_string()));
                     }
 
                     parents.last_mut().unwrap().name = format!(
@@ -208,6 +208,31 @@ pub struct Function {
     pub dependencies: Vec<InterplugRequest>,
 }
 
+impl PinLibrary for Parameter {
+    fn pin_library (
+        self: &mut Self,
+        lib: &std::sync::Arc<libloading::Library>,
+    ) {
+
+        self.default_value.pin_library(lib);
+    }
+}
+
+impl PinLibrary for Function {
+    fn pin_library (
+        self: &mut Self,
+        lib: &std::sync::Arc<libloading::Library>,
+    ) {
+
+        let callable: Box<dyn DuskCallable> = std::mem::replace(
+            &mut self.callable,
+            Box::new(EmptyCallable{}),
+        );
+        self.callable = Box::new(LibraryCallable::new(callable, lib));
+        self.parameters.pin_library(lib);
+    }
+}
+
 impl Default for Function {
     fn default () -> Function {
         Function {
@@ -221,3 +246,76 @@ impl Default for Function {
         }
     }
 }
+
+impl Function {
+
+    /// Find out whether the function can be used, given the
+    /// interplugin requests, that were provided and denied so far
+    /// (see [`InterplugRequest::status`])
+    ///
+    /// The function is available if every request in its
+    /// dependencies is provided, and unavailable if any of them is
+    /// denied
+    pub fn availability (
+        self: &Self,
+        provided: &[InterplugRequest],
+        denied: &[InterplugRequest],
+    ) -> Availability {
+
+        let mut missing: Vec<InterplugRequest> = Vec::new();
+        let mut denial: bool = false;
+        for dependency in &self.dependencies {
+            match dependency.status(provided, denied) {
+                RequestStatus::Provided => continue,
+                RequestStatus::Denied => denial = true,
+                RequestStatus::Pending => (),
+            }
+            missing.push(dependency.clone());
+        }
+
+        if missing.is_empty() {
+            return Availability::Available;
+        }
+        if denial {
+            return Availability::Unavailable { missing: missing };
+        }
+        return Availability::Pending { missing: missing };
+    }
+}
+
+/// Enum, that represents whether a function can be used in the
+/// current setup (see [`Function::availability`])
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Availability {
+
+    /// Every dependency of the function is provided
+    Available,
+
+    /// Some of the dependencies were not answered yet
+    Pending {
+
+        /// The dependencies, that are not provided yet
+        missing: Vec<InterplugRequest>,
+    },
+
+    /// Some of the dependencies were denied, so the function can not
+    /// be used
+    Unavailable {
+
+        /// The dependencies, that are not provided, including the
+        /// ones, that were not answered yet
+        missing: Vec<InterplugRequest>,
+    },
+}
+
+/// A function, along with whether it can be used in the current
+/// setup (see [`FreightProxy::get_function_availability`])
+#[derive(Clone, Debug)]
+pub struct FunctionAvailability {
+
+    /// The function
+    pub function: Function,
+
+    /// Whether the function can be used
+    pub availability: Availability,
+}
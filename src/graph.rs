@@ -0,0 +1,471 @@
+// Copyright (C) 2021 by Andy Gozas <andy@gozas.me>
+//
+// This file is part of Dusk API.
+//
+// Dusk API is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Dusk API is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Dusk API.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Module, containing everything needed to export the graph of the
+//! plugins and the interplugin requests they made, so it could be
+//! inspected with other tools
+
+use crate::*;
+
+/// Enum, that represents a compound request, some request is nested
+/// in
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RequestKind {
+
+    /// The request is nested in [`InterplugRequest::RequestCrucial`]
+    Crucial,
+
+    /// The request is nested in [`InterplugRequest::RequestOptional`]
+    Optional,
+
+    /// The request is nested in [`InterplugRequest::RequestEither`]
+    Either,
+
+    /// The request is nested in [`InterplugRequest::RequestEach`]
+    Each,
+}
+
+impl std::fmt::Display for RequestKind {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        match self {
+            RequestKind::Crucial => f.write_str("crucial"),
+            RequestKind::Optional => f.write_str("optional"),
+            RequestKind::Either => f.write_str("either"),
+            RequestKind::Each => f.write_str("each"),
+        }
+    }
+}
+
+/// Enum, that represents how a request was answered
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+
+    /// The plugin with the ID provided was provided to fulfill the
+    /// request
+    Provided (usize),
+
+    /// A weak handle to the plugin with the ID provided was provided
+    /// to fulfill the request, as the plugin depends on the requesting
+    /// one (see [`WeakFreightProxy`])
+    ProvidedWeak (usize),
+
+    /// The request was denied
+    Denied,
+
+    /// The request was not answered, for example because another
+    /// alternative of a [`InterplugRequest::RequestEither`] request
+    /// was chosen instead
+    Unanswered,
+}
+
+/// A plugin in the [`DependencyGraph`]
+#[derive(Clone, Debug)]
+pub struct GraphNode {
+
+    /// The plugin name
+    pub name: String,
+
+    /// The plugin version
+    pub version: Version,
+
+    /// Whether the plugin was initialized successfully
+    pub ready: bool,
+}
+
+/// A request for a specific plugin or trait in the
+/// [`DependencyGraph`]
+#[derive(Clone, Debug)]
+pub struct GraphEdge {
+
+    /// ID of the plugin, that made the request
+    pub requester: usize,
+
+    /// The compound requests, the request is nested in, starting
+    /// from the outermost one
+    pub kinds: Vec<RequestKind>,
+
+    /// The request, either [`InterplugRequest::PlugRequest`],
+    /// [`InterplugRequest::PlugRequestAll`],
+    /// [`InterplugRequest::TraitRequest`] or
+    /// [`InterplugRequest::TraitRequestAll`]
+    pub request: InterplugRequest,
+
+    /// How the request was answered
+    pub answer: Answer,
+}
+
+/// A graph of the plugins and the interplugin requests they made,
+/// that can be written in the Graphviz DOT language or as JSON
+///
+/// Every plugin is a node and every request for a specific plugin or
+/// trait is an edge, labelled with the compound requests it is
+/// nested in, the version required and the trait ID. The requests,
+/// that were not fulfilled, lead to a separate node, named after the
+/// plugin requested
+///
+/// # Example
+///
+/// ```
+/// use dusk_api::*;
+///
+/// let mut graph: DependencyGraph = DependencyGraph::new();
+/// let app: usize = graph.add_plugin(
+///     "app".to_string(), Version::default(), true);
+/// let storage: usize = graph.add_plugin(
+///     "storage".to_string(), "1.4".parse().unwrap(), true);
+///
+/// let requests: Vec<InterplugRequest> = vec![RequestCrucial {
+///     request: Box::new(PlugRequestAll {
+///         plugin: "storage".to_string(),
+///         version: "^1.2".parse().unwrap(),
+///     }),
+/// }];
+/// graph.add_requests(app, &requests, &|_| Answer::Provided(storage));
+///
+/// assert!(graph.to_dot().contains("p0 -> p1"));
+/// assert!(graph.to_json().contains("\"kinds\":[\"crucial\"]"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DependencyGraph {
+
+    /// The plugins, the position of each of which is its ID
+    pub nodes: Vec<GraphNode>,
+
+    /// The requests for specific plugins and traits
+    pub edges: Vec<GraphEdge>,
+}
+
+impl DependencyGraph {
+
+    /// Create an empty graph
+    pub fn new () -> DependencyGraph {
+        DependencyGraph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Add a plugin to the graph, returning its ID
+    pub fn add_plugin (
+        self: &mut Self,
+        name: String,
+        version: Version,
+        ready: bool,
+    ) -> usize {
+
+        self.nodes.push(GraphNode {
+            name: name,
+            version: version,
+            ready: ready,
+        });
+        return self.nodes.len() - 1;
+    }
+
+    /// Add every request for a specific plugin or trait, found in the
+    /// requests provided, made by the plugin with the ID provided,
+    /// asking the function provided how each of them was answered
+    pub fn add_requests (
+        self: &mut Self,
+        requester: usize,
+        requests: &[InterplugRequest],
+        answer: &dyn Fn(&InterplugRequest) -> Answer,
+    ) {
+
+        let mut kinds: Vec<RequestKind> = Vec::new();
+        for request in requests {
+            self.add_request(requester, request, &mut kinds, answer);
+        }
+    }
+
+    /// Add the request provided, nested in the compound requests of
+    /// the kinds provided
+    fn add_request (
+        self: &mut Self,
+        requester: usize,
+        request: &InterplugRequest,
+        kinds: &mut Vec<RequestKind>,
+        answer: &dyn Fn(&InterplugRequest) -> Answer,
+    ) {
+
+        let (kind, nested): (RequestKind, Vec<&InterplugRequest>) =
+            match request {
+                RequestCrucial { request } =>
+                    (RequestKind::Crucial, vec![request]),
+                RequestOptional { request } =>
+                    (RequestKind::Optional, vec![request]),
+                RequestEither { requests } =>
+                    (RequestKind::Either, requests.iter().collect()),
+                RequestEach { requests } =>
+                    (RequestKind::Each, requests.iter().collect()),
+                _ => {
+                    self.edges.push(GraphEdge {
+                        requester: requester,
+                        kinds: kinds.clone(),
+                        request: request.clone(),
+                        answer: answer(request),
+                    });
+                    return;
+                },
+            };
+
+        kinds.push(kind);
+        for request in nested {
+            self.add_request(requester, request, kinds, answer);
+        }
+        kinds.pop();
+    }
+
+    /// Write the graph in the Graphviz DOT language
+    ///
+    /// Plugins, that were not initialized successfully, and the
+    /// requests, fulfilled with a weak handle, are drawn dashed,
+    /// denied requests are drawn red and the requests, that were not
+    /// answered, are drawn dotted
+    pub fn to_dot (self: &Self) -> String {
+        let mut result: String = "digraph plugins {\n".to_string();
+        result.push_str("    node [shape=box];\n");
+
+        for (id, node) in self.nodes.iter().enumerate() {
+            result.push_str(&format!(
+                    "    p{} [label=\"{}\"{}];\n",
+                    id,
+                    escape_dot(&format!("{}\n{}", node.name, node.version)),
+                    match node.ready {
+                        true => "",
+                        false => ", style=dashed",
+                    }));
+        }
+
+        for (position, edge) in self.edges.iter().enumerate() {
+            let target: String = match edge.answer {
+                Answer::Provided(provider) | Answer::ProvidedWeak(provider) =>
+                    format!("p{}", provider),
+                _ => {
+                    let target: String = format!("r{}", position);
+                    result.push_str(&format!(
+                            "    {} [label=\"{}\", style=dotted];\n",
+                            target,
+                            escape_dot(requested_plugin(&edge.request))));
+                    target
+                },
+            };
+            let style: &str = match edge.answer {
+                Answer::Provided(_) => "",
+                Answer::ProvidedWeak(_) => ", style=dashed",
+                Answer::Denied => ", color=red",
+                Answer::Unanswered => ", style=dotted",
+            };
+            result.push_str(&format!(
+                    "    p{} -> {} [label=\"{}\"{}];\n",
+                    edge.requester,
+                    target,
+                    escape_dot(&edge_label(edge)),
+                    style));
+        }
+
+        result.push_str("}\n");
+        return result;
+    }
+
+    /// Write the graph as a JSON document with the `plugins` and the
+    /// `requests` arrays
+    ///
+    /// # Example
+    /// ``` text
+    /// {"plugins":[{"id":0,"name":"app","version":"0.0.0.0","ready":true},
+    /// {"id":1,"name":"storage","version":"1.4.0.0","ready":true}],
+    /// "requests":[{"requester":0,"kinds":["crucial"],"type":"plugin",
+    /// "plugin":"storage","version":">=1.2.0.0, <2.0.0.0","fn_ids":null,
+    /// "trait_id":null,"answer":"provided","provider":1}]}
+    /// ```
+    pub fn to_json (self: &Self) -> String {
+        let mut plugins: Vec<String> = Vec::new();
+        for (id, node) in self.nodes.iter().enumerate() {
+            plugins.push(format!(
+                    "{{\"id\":{},\"name\":{},\"version\":{},\"ready\":{}}}",
+                    id,
+                    json_string(&node.name),
+                    json_string(&node.version.to_string()),
+                    node.ready));
+        }
+
+        let mut requests: Vec<String> = Vec::new();
+        for edge in &self.edges {
+            let kinds: Vec<String> = edge.kinds.iter()
+                .map(|kind| json_string(&kind.to_string()))
+                .collect();
+            let (tp, plugin, version, fn_ids, trait_id):
+                (&str, &String, &VersionReq, Option<&Vec<usize>>, Option<usize>) =
+                match &edge.request {
+                    PlugRequest { plugin, fn_ids, version } =>
+                        ("plugin", plugin, version, Some(fn_ids), None),
+                    PlugRequestAll { plugin, version } =>
+                        ("plugin", plugin, version, None, None),
+                    TraitRequest { plugin, trait_id, fn_ids, version } =>
+                        ("trait", plugin, version, Some(fn_ids), Some(*trait_id)),
+                    TraitRequestAll { plugin, trait_id, version } =>
+                        ("trait", plugin, version, None, Some(*trait_id)),
+                    // Compound requests are never stored as edges
+                    _ => continue,
+                };
+            let (answer, provider): (&str, String) = match edge.answer {
+                Answer::Provided(provider) => ("provided", provider.to_string()),
+                Answer::ProvidedWeak(provider) =>
+                    ("provided_weak", provider.to_string()),
+                Answer::Denied => ("denied", "null".to_string()),
+                Answer::Unanswered => ("unanswered", "null".to_string()),
+            };
+            requests.push(format!(
+                    "{{\"requester\":{},\"kinds\":[{}],\"type\":\"{}\",\
+                    \"plugin\":{},\"version\":{},\"fn_ids\":{},\
+                    \"trait_id\":{},\"answer\":\"{}\",\"provider\":{}}}",
+                    edge.requester,
+                    kinds.join(","),
+                    tp,
+                    json_string(plugin),
+                    json_string(&version.to_string()),
+                    match fn_ids {
+                        Some(fn_ids) => format!("{:?}", fn_ids)
+                            .replace(' ', ""),
+                        None => "null".to_string(),
+                    },
+                    match trait_id {
+                        Some(trait_id) => trait_id.to_string(),
+                        None => "null".to_string(),
+                    },
+                    answer,
+                    provider));
+        }
+
+        return format!(
+            "{{\"plugins\":[{}],\"requests\":[{}]}}",
+            plugins.join(","),
+            requests.join(","));
+    }
+}
+
+/// Get the name of the plugin, the request is made for
+fn requested_plugin (request: &InterplugRequest) -> &str {
+    match request {
+        PlugRequest { plugin, .. } | PlugRequestAll { plugin, .. } |
+            TraitRequest { plugin, .. } |
+            TraitRequestAll { plugin, .. } => plugin,
+        _ => "",
+    }
+}
+
+/// Get the label of the edge: the kinds of the compound requests it
+/// is nested in, followed by the request itself
+fn edge_label (edge: &GraphEdge) -> String {
+    let mut label: String = edge.kinds.iter()
+        .map(|kind| kind.to_string())
+        .collect::<Vec<String>>()
+        .join(", ");
+    if !label.is_empty() {
+        label.push('\n');
+    }
+    label.push_str(&edge.request.to_string());
+    return label;
+}
+
+/// Escape the text, so it could be put into a quoted string in the
+/// Graphviz DOT language
+fn escape_dot (text: &str) -> String {
+    let mut result: String = String::new();
+    for character in text.chars() {
+        match character {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            _ => result.push(character),
+        }
+    }
+    return result;
+}
+
+/// Write the text as a JSON string
+fn json_string (text: &str) -> String {
+    let mut result: String = "\"".to_string();
+    for character in text.chars() {
+        match character {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            character if (character as u32) < 0x20 =>
+                result.push_str(&format!("\\u{:04x}", character as u32)),
+            _ => result.push(character),
+        }
+    }
+    result.push('"');
+    return result;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Function, that makes a graph of a plugin with the name
+    /// provided, that requests a plugin with the same name and gets
+    /// the answer provided
+    fn test_graph (name: &str, answer: Answer) -> DependencyGraph {
+        let mut graph: DependencyGraph = DependencyGraph::new();
+        graph.add_plugin(name.to_string(), Version::default(), false);
+        let requests: Vec<InterplugRequest> = vec![RequestOptional {
+            request: Box::new(RequestEither {
+                requests: vec![PlugRequestAll {
+                    plugin: name.to_string(),
+                    version: VersionReq::any(),
+                }],
+            }),
+        }];
+        graph.add_requests(0, &requests, &|_| answer);
+        return graph;
+    }
+
+    #[test]
+    fn dot_escaping () {
+        assert_eq!(escape_dot("a \"b\" \\ c\nd"), "a \\\"b\\\" \\\\ c\\nd");
+
+        let dot: String = test_graph("say \"hi\"\\", Answer::Denied).to_dot();
+        assert!(dot.contains(
+            "p0 [label=\"say \\\"hi\\\"\\\\\\n0.0.0.0\", style=dashed];"));
+        assert!(dot.contains("r0 [label=\"say \\\"hi\\\"\\\\\", style=dotted];"));
+        assert!(dot.contains("p0 -> r0 [label=\"optional, either\\n"));
+        assert!(dot.contains(", color=red];"));
+    }
+
+    #[test]
+    fn json_escaping () {
+        assert_eq!(json_string("a \"b\" \\"), "\"a \\\"b\\\" \\\\\"");
+        assert_eq!(json_string("\n\r\t\u{1}"), "\"\\n\\r\\t\\u0001\"");
+        assert_eq!(json_string("ünïcode"), "\"ünïcode\"");
+
+        let json: String = test_graph("a\"\n", Answer::ProvidedWeak(0)).to_json();
+        assert_eq!(json, "{\"plugins\":[{\"id\":0,\"name\":\"a\\\"\\n\",\
+            \"version\":\"0.0.0.0\",\"ready\":false}],\"requests\":[\
+            {\"requester\":0,\"kinds\":[\"optional\",\"either\"],\
+            \"type\":\"plugin\",\"plugin\":\"a\\\"\\n\",\"version\":\"*\",\
+            \"fn_ids\":null,\"trait_id\":null,\"answer\":\"provided_weak\",\
+            \"provider\":0}]}");
+    }
+}
@@ -0,0 +1,582 @@
+// Copyright (C) 2021 by Andy Gozas <andy@gozas.me>
+//
+// This file is part of Dusk API.
+//
+// Dusk API is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Dusk API is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Dusk API.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Module, containing everything needed to make sure a plugin
+//! library is the one the program expects, before any of its code
+//! is run
+
+use crate::*;
+
+/// Extension, appended to the full library file name to get the
+/// name of the detached signature file of that library
+///
+/// For example, the signature of `libtest_plug.so` has to be named
+/// `libtest_plug.so.sig` and be located in the same directory
+pub static SIGNATURE_EXTENSION: &str = "sig";
+
+/// A structure, that checks plugin libraries before they are loaded
+///
+/// A library passes the check if its SHA-256 digest is in the allow
+/// list, or if it has a detached ed25519 signature file next to it
+/// (see [`SIGNATURE_EXTENSION`]), made with the private key of any of
+/// the trusted public keys. The signature file contains the 64 bytes
+/// of the signature of the whole library file, either as they are or
+/// written in hex. A verifier with neither digests nor keys rejects
+/// every library.
+///
+/// Every library, that is loaded or opened after the check (see
+/// [`FreightProxy::load_verified`], [`SyncFreightProxy::load_verified`],
+/// [`PluginCandidate::load_verified`] and
+/// [`PluginCandidate::inspect_verified`]), is read only once: the
+/// bytes, that were checked, are copied into a file inside of a
+/// private temporary directory, and that file is what gets loaded
+/// (see [`VerifiedLibrary`]), so replacing the library after the
+/// check has no effect and the plugins may be loaded from shared
+/// directories.
+///
+/// # Example
+///
+/// ``` rust, ignore
+/// let mut verifier: LibraryVerifier = LibraryVerifier::new();
+/// verifier.load_allow_list("/etc/dusk/plugins.sha256").unwrap();
+/// let mut my_f_proxy: FreightProxy = unsafe {
+///     FreightProxy::load_verified("/bin/libtest_plug.so", &verifier)
+/// }.expect("fail");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LibraryVerifier {
+
+    /// SHA-256 digests of the libraries, that are allowed to be
+    /// loaded
+    allowed_digests: std::collections::HashSet<[u8; 32]>,
+
+    /// Public keys, the signatures made with which are trusted
+    trusted_keys: Vec<ed25519_dalek::VerifyingKey>,
+}
+
+impl LibraryVerifier {
+
+    /// Create a verifier, that rejects every library until digests or
+    /// keys are added to it
+    pub fn new () -> LibraryVerifier {
+        LibraryVerifier {
+            allowed_digests: std::collections::HashSet::new(),
+            trusted_keys: Vec::new(),
+        }
+    }
+
+    /// Allow the library with the SHA-256 digest provided to be
+    /// loaded
+    pub fn allow_digest (
+        self: &mut Self,
+        digest: [u8; 32],
+    ) {
+
+        self.allowed_digests.insert(digest);
+    }
+
+    /// Read the allow list of SHA-256 digests from a file, in the
+    /// format of the `sha256sum` output: every line is either empty,
+    /// a comment starting with `#`, or a digest written in hex,
+    /// optionally followed by the file name, which is ignored
+    ///
+    /// # Example
+    /// ``` text
+    /// # Plugins allowed on this machine
+    /// 9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08  libtest_plug.so
+    /// ```
+    pub fn load_allow_list (
+        self: &mut Self,
+        path: &str,
+    ) -> Result<(), Error> {
+
+        let text: String = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) => return Err(OsError(
+                    format!(
+                        "Could not read allow list {}: {}",
+                        path,
+                        err,
+                    ))),
+        };
+
+        for line in text.lines() {
+            let line: &str = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let hex: &str = match line.split_whitespace().next() {
+                Some(hex) => hex,
+                None => continue,
+            };
+            let mut digest: [u8; 32] = [0; 32];
+            match parse_hex(hex) {
+                Some(bytes) if bytes.len() == digest.len() =>
+                    digest.copy_from_slice(&bytes),
+                _ => return Err(ValueError(
+                        format!(
+                            "Invalid digest in allow list {}: {}",
+                            path,
+                            hex,
+                        ))),
+            }
+            self.allow_digest(digest);
+        }
+        return Ok(());
+    }
+
+    /// Trust the signatures, made with the private key, matching the
+    /// ed25519 public key provided
+    pub fn trust_key (
+        self: &mut Self,
+        public_key: [u8; 32],
+    ) -> Result<(), Error> {
+
+        match ed25519_dalek::VerifyingKey::from_bytes(&public_key) {
+            Ok(key) => {
+                self.trusted_keys.push(key);
+                return Ok(());
+            },
+            Err(_) => return Err(ValueError(
+                    "Invalid ed25519 public key".to_string()
+            )),
+        }
+    }
+
+    /// Check the contents of a library and its detached signature,
+    /// if it has one
+    pub fn verify_bytes (
+        self: &Self,
+        data: &[u8],
+        signature: Option<&[u8]>,
+    ) -> Result<(), Error> {
+
+        use sha2::Digest;
+
+        if !self.allowed_digests.is_empty() {
+            let digest: [u8; 32] = sha2::Sha256::digest(data).into();
+            if self.allowed_digests.contains(&digest) {
+                return Ok(());
+            }
+        }
+
+        if let Some(signature) = signature {
+            let mut bytes: [u8; 64] = [0; 64];
+            match signature.len() {
+                64 => bytes.copy_from_slice(signature),
+                _ => match std::str::from_utf8(signature).ok()
+                    .and_then(|text| parse_hex(text.trim())) {
+
+                    Some(parsed) if parsed.len() == bytes.len() =>
+                        bytes.copy_from_slice(&parsed),
+                    _ => return Err(IntegrityError(
+                            "Invalid signature".to_string()
+                    )),
+                },
+            }
+            let signature: ed25519_dalek::Signature =
+                ed25519_dalek::Signature::from_bytes(&bytes);
+            for key in &self.trusted_keys {
+                if key.verify_strict(data, &signature).is_ok() {
+                    return Ok(());
+                }
+            }
+            return Err(IntegrityError(
+                    "Signature is not made by any trusted key".to_string()
+            ));
+        }
+
+        return Err(IntegrityError(
+                "Digest is not in the allow list and there is no signature"
+                .to_string()
+        ));
+    }
+
+    /// Check the library at the path provided, reading its detached
+    /// signature file, if there is one
+    ///
+    /// This gives no protection against the library being replaced
+    /// after the check, so the library must never be loaded from the
+    /// same path afterwards. Use [`LibraryVerifier::copy_verified`] to
+    /// get the contents, that were checked, instead
+    pub fn verify (
+        self: &Self,
+        lib_path: &std::path::Path,
+    ) -> Result<(), Error> {
+
+        self.read_verified(lib_path)?;
+        return Ok(());
+    }
+
+    /// Check the library at the path provided, like
+    /// [`LibraryVerifier::verify`] does, and copy the contents, that
+    /// were checked, into a private file, that can be loaded without
+    /// the library being replaced between the check and the load
+    pub fn copy_verified (
+        self: &Self,
+        lib_path: &std::path::Path,
+    ) -> Result<VerifiedLibrary, Error> {
+
+        let data: Vec<u8> = self.read_verified(lib_path)?;
+        VerifiedLibrary::create(lib_path, &data)
+    }
+
+    /// Read the library at the path provided and check it, returning
+    /// the contents, that were checked
+    fn read_verified (
+        self: &Self,
+        lib_path: &std::path::Path,
+    ) -> Result<Vec<u8>, Error> {
+
+        let data: Vec<u8> = match std::fs::read(lib_path) {
+            Ok(data) => data,
+            Err(err) => return Err(OsError(
+                    format!(
+                        "Could not read library {}: {}",
+                        lib_path.display(),
+                        err,
+                    ))),
+        };
+
+        let signature_path: std::path::PathBuf = signature_path(lib_path);
+        let signature: Option<Vec<u8>> =
+            if !self.trusted_keys.is_empty() && signature_path.is_file() {
+                match std::fs::read(&signature_path) {
+                    Ok(signature) => Some(signature),
+                    Err(err) => return Err(OsError(
+                            format!(
+                                "Could not read signature {}: {}",
+                                signature_path.display(),
+                                err,
+                            ))),
+                }
+            } else {
+                None
+            };
+
+        match self.verify_bytes(&data, signature.as_deref()) {
+            Ok(()) => Ok(data),
+            Err(IntegrityError(message)) => Err(IntegrityError(
+                    format!(
+                        "{}: {}",
+                        lib_path.display(),
+                        message,
+                    ))),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// A private copy of a library, that passed the check of a
+/// [`LibraryVerifier`], located in a temporary directory, only the
+/// current user can access
+///
+/// The copy is removed when this structure is dropped. A library,
+/// that was already loaded from it, stays loaded on the systems,
+/// that allow removing such files, and the copy is left in place on
+/// the ones, that do not
+#[derive(Debug)]
+pub struct VerifiedLibrary {
+
+    /// The private temporary directory
+    dir: std::path::PathBuf,
+
+    /// The copy of the library inside of it
+    path: std::path::PathBuf,
+}
+
+impl VerifiedLibrary {
+
+    /// Write the contents provided into a file, named the same way as
+    /// the library at the path provided, inside of a new private
+    /// temporary directory
+    fn create (
+        lib_path: &std::path::Path,
+        data: &[u8],
+    ) -> Result<VerifiedLibrary, Error> {
+
+        static COUNTER: std::sync::atomic::AtomicUsize =
+            std::sync::atomic::AtomicUsize::new(0);
+
+        let copy_error = |err: std::io::Error| OsError(
+            format!(
+                "Could not copy library {}: {}",
+                lib_path.display(),
+                err,
+            ));
+
+        let nanos: u128 = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|time| time.as_nanos())
+            .unwrap_or_default();
+        let dir: std::path::PathBuf = std::env::temp_dir().join(
+            format!(
+                "dusk-{}-{}-{}",
+                std::process::id(),
+                COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+                nanos,
+            ));
+
+        // The directory must not exist yet, so nobody else could
+        // have placed anything inside of it
+        let mut builder: std::fs::DirBuilder = std::fs::DirBuilder::new();
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+        builder.create(&dir).map_err(copy_error)?;
+
+        let result: VerifiedLibrary = VerifiedLibrary {
+            path: dir.join(lib_path.file_name().unwrap_or_default()),
+            dir: dir,
+        };
+        let mut file: std::fs::File = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&result.path)
+            .map_err(copy_error)?;
+        std::io::Write::write_all(&mut file, data).map_err(copy_error)?;
+        return Ok(result);
+    }
+
+    /// Get the path of the copy
+    pub fn path (self: &Self) -> &std::path::Path {
+        &self.path
+    }
+
+    /// Get the path of the copy as a string, the way the libraries
+    /// are loaded by
+    pub(crate) fn path_str (self: &Self) -> Result<&str, Error> {
+        match self.path.to_str() {
+            Some(path) => Ok(path),
+            None => Err(OsError(
+                    format!(
+                        "Invalid temporary path {}",
+                        self.path.display(),
+                    ))),
+        }
+    }
+}
+
+impl Drop for VerifiedLibrary {
+    fn drop (self: &mut Self) {
+        let _ = std::fs::remove_file(&self.path);
+        let _ = std::fs::remove_dir(&self.dir);
+    }
+}
+
+/// Get the path of the detached signature file for the library
+/// provided
+fn signature_path (
+    lib_path: &std::path::Path,
+) -> std::path::PathBuf {
+
+    let mut path: std::ffi::OsString = lib_path.as_os_str().to_os_string();
+    path.push(".");
+    path.push(SIGNATURE_EXTENSION);
+    return std::path::PathBuf::from(path);
+}
+
+/// Parse bytes, written in hex
+fn parse_hex (
+    text: &str,
+) -> Option<Vec<u8>> {
+
+    // Checked up front, as u8::from_str_radix would accept a sign
+    if !text.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let mut result: Vec<u8> = Vec::new();
+    for pair in text.as_bytes().chunks(2) {
+        if pair.len() != 2 {
+            return None;
+        }
+        match std::str::from_utf8(pair).ok()
+            .and_then(|pair| u8::from_str_radix(pair, 16).ok()) {
+
+            Some(byte) => result.push(byte),
+            None => return None,
+        }
+    }
+    return Some(result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::Signer;
+
+    /// Contents of the library, checked in the tests
+    const LIBRARY: &[u8] = b"not really a library";
+
+    /// Get the SHA-256 digest of the contents provided
+    fn digest (data: &[u8]) -> [u8; 32] {
+        use sha2::Digest;
+        sha2::Sha256::digest(data).into()
+    }
+
+    /// Get the key, the signatures are made with in the tests
+    fn signing_key (seed: u8) -> ed25519_dalek::SigningKey {
+        ed25519_dalek::SigningKey::from_bytes(&[seed; 32])
+    }
+
+    /// Write the bytes provided in hex
+    fn to_hex (bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    /// Get a path in a directory, private to the test with the name
+    /// provided, removing whatever the previous run left there
+    fn scratch_path (
+        test: &str,
+        file: &str,
+    ) -> std::path::PathBuf {
+
+        let dir: std::path::PathBuf = std::env::temp_dir().join(format!(
+                "dusk-integrity-{}",
+                test,
+            ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        return dir.join(file);
+    }
+
+    #[test]
+    fn allowed_digest () {
+        let mut verifier: LibraryVerifier = LibraryVerifier::new();
+        verifier.allow_digest(digest(LIBRARY));
+
+        assert!(verifier.verify_bytes(LIBRARY, None).is_ok());
+        assert!(matches!(
+                verifier.verify_bytes(b"another library", None),
+                Err(IntegrityError(_))));
+    }
+
+    #[test]
+    fn signatures () {
+        let key: ed25519_dalek::SigningKey = signing_key(1);
+        let mut verifier: LibraryVerifier = LibraryVerifier::new();
+        verifier.trust_key(key.verifying_key().to_bytes()).unwrap();
+
+        let raw: [u8; 64] = key.sign(LIBRARY).to_bytes();
+        let hex: String = format!("{}\n", to_hex(&raw));
+        assert!(verifier.verify_bytes(LIBRARY, Some(&raw)).is_ok());
+        assert!(verifier.verify_bytes(LIBRARY, Some(hex.as_bytes())).is_ok());
+
+        // The signature of other contents
+        assert!(matches!(
+                verifier.verify_bytes(b"another library", Some(&raw)),
+                Err(IntegrityError(_))));
+        // Not a signature at all
+        assert!(matches!(
+                verifier.verify_bytes(LIBRARY, Some(b"signed")),
+                Err(IntegrityError(_))));
+        assert!(matches!(
+                verifier.verify_bytes(LIBRARY, None),
+                Err(IntegrityError(_))));
+    }
+
+    #[test]
+    fn untrusted_key () {
+        let mut verifier: LibraryVerifier = LibraryVerifier::new();
+        verifier.trust_key(signing_key(1).verifying_key().to_bytes()).unwrap();
+
+        let signature: [u8; 64] = signing_key(2).sign(LIBRARY).to_bytes();
+        assert!(matches!(
+                verifier.verify_bytes(LIBRARY, Some(&signature)),
+                Err(IntegrityError(_))));
+    }
+
+    #[test]
+    fn empty_verifier_rejects_everything () {
+        let verifier: LibraryVerifier = LibraryVerifier::new();
+        let signature: [u8; 64] = signing_key(1).sign(LIBRARY).to_bytes();
+
+        assert!(verifier.verify_bytes(LIBRARY, None).is_err());
+        assert!(verifier.verify_bytes(LIBRARY, Some(&signature)).is_err());
+        assert!(verifier.verify_bytes(&[], None).is_err());
+    }
+
+    #[test]
+    fn allow_list () {
+        let path: std::path::PathBuf =
+            scratch_path("allow_list", "plugins.sha256");
+        std::fs::write(&path, format!(
+                "# Plugins allowed on this machine\n\n{}  libtest_plug.so\n",
+                to_hex(&digest(LIBRARY)))).unwrap();
+
+        let mut verifier: LibraryVerifier = LibraryVerifier::new();
+        verifier.load_allow_list(path.to_str().unwrap()).unwrap();
+        assert!(verifier.verify_bytes(LIBRARY, None).is_ok());
+        assert!(verifier.verify_bytes(b"another library", None).is_err());
+    }
+
+    #[test]
+    fn invalid_allow_lists () {
+        let path: std::path::PathBuf =
+            scratch_path("invalid_allow_list", "plugins.sha256");
+        let valid: String = to_hex(&digest(LIBRARY));
+
+        for line in &[
+            valid[1..].to_string(),
+            valid.replacen("a", "g", 1).replacen("0", "z", 1),
+            format!("+{}", &valid[1..]),
+            valid[2..].to_string(),
+            format!("{}00", valid),
+        ] {
+            std::fs::write(&path, format!("{}  libtest_plug.so\n", line)).unwrap();
+            let mut verifier: LibraryVerifier = LibraryVerifier::new();
+            assert!(
+                matches!(
+                    verifier.load_allow_list(path.to_str().unwrap()),
+                    Err(ValueError(_))),
+                "{}", line);
+        }
+    }
+
+    #[test]
+    fn hex () {
+        assert_eq!(parse_hex("00ff1A"), Some(vec![0x00, 0xff, 0x1a]));
+        assert_eq!(parse_hex(""), Some(Vec::new()));
+        assert_eq!(parse_hex("abc"), None);
+        assert_eq!(parse_hex("+a"), None);
+        assert_eq!(parse_hex("zz"), None);
+        assert_eq!(parse_hex("\u{e9}a"), None);
+    }
+
+    #[test]
+    fn verified_copy () {
+        let path: std::path::PathBuf =
+            scratch_path("verified_copy", "libtest.so");
+        std::fs::write(&path, LIBRARY).unwrap();
+        let mut verifier: LibraryVerifier = LibraryVerifier::new();
+        verifier.allow_digest(digest(LIBRARY));
+
+        let copy: VerifiedLibrary = verifier.copy_verified(&path).unwrap();
+        let copy_path: std::path::PathBuf = copy.path().to_path_buf();
+        assert_ne!(copy_path, path);
+        assert_eq!(copy_path.file_name(), path.file_name());
+        assert_eq!(std::fs::read(&copy_path).unwrap(), LIBRARY);
+
+        // Replacing the library does not affect the copy
+        std::fs::write(&path, b"another library").unwrap();
+        assert_eq!(std::fs::read(&copy_path).unwrap(), LIBRARY);
+        assert!(verifier.copy_verified(&path).is_err());
+        assert!(verifier.verify(&path).is_err());
+
+        drop(copy);
+        assert!(!copy_path.exists());
+    }
+}
@@ -22,13 +22,6 @@
 use crate::*;
 pub use InterplugRequest::*;
 
-/// TODO: define rules of plugin identification:
-///
-/// Plugin name string is uniquely identifying it in it's database,
-/// when requesting a plugin, first search in it's own database, then
-/// in the other default user configured databases, and then in all
-/// the known ones, going from the oldest to the newest
-
 /// Enum, that represents an interplugin request and either contains
 /// a [`InterplugRequest::RequestCrucial`] plugin request (must be provided
 /// in order for the plugin to work or an
@@ -50,7 +43,14 @@ pub use InterplugRequest::*;
 /// the dependencies, when seeing this request finds out that
 /// the plugin that was requested was already loaded earlier,
 /// so it might as well provide it to the requesting plugin.
-#[derive(Clone, Debug)]
+///
+/// Plugin name string is uniquely identifying it in its
+/// [`PluginDatabase`]. When a plugin is requested, it is first
+/// searched for in the database of the requesting plugin, then in
+/// the other default user configured databases, and then in all the
+/// known ones, going from the oldest to the newest (see
+/// [`PluginLocator`])
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum InterplugRequest {
 
     /// Request for a specific plugin with a specific version
@@ -65,9 +65,11 @@ pub enum InterplugRequest {
         /// fulfilled
         fn_ids: Vec<usize>,
 
-        /// The plugin version, with which the actuall version
-        /// has to at least be compatible
-        version: Version,
+        /// The requirement, the versions the plugin is compatible
+        /// with (see [`InterplugRequest::check_provider`]) have to
+        /// match. A single [`Version`] may be turned into a
+        /// requirement with `.into()`
+        version: VersionReq,
     },
 
     /// Request for any implementor of a specific trait from
@@ -88,9 +90,9 @@ pub enum InterplugRequest {
         /// their dependencies fulfilled
         fn_ids: Vec<usize>,
 
-        /// The version of the plugin containing the trait
-        /// definition
-        version: Version,
+        /// The requirement, the versions the plugin containing the
+        /// trait definition is compatible with have to match
+        version: VersionReq,
     },
 
     /// Request for a specific plugin with a specific version
@@ -101,9 +103,11 @@ pub enum InterplugRequest {
         /// The string, that identifies the plugin
         plugin: String,
 
-        /// The plugin version, with which the actuall version
-        /// has to at least be compatible
-        version: Version,
+        /// The requirement, the versions the plugin is compatible
+        /// with (see [`InterplugRequest::check_provider`]) have to
+        /// match. A single [`Version`] may be turned into a
+        /// requirement with `.into()`
+        version: VersionReq,
     },
 
     /// Request for any implementor of a specific trait from
@@ -119,9 +123,9 @@ pub enum InterplugRequest {
         /// Trait identifier
         trait_id: usize,
 
-        /// The version of the plugin containing the trait
-        /// definition
-        version: Version,
+        /// The requirement, the versions the plugin containing the
+        /// trait definition is compatible with have to match
+        version: VersionReq,
     },
 
     /// An interlplug request that contains several interlplug
@@ -162,13 +166,368 @@ pub enum InterplugRequest {
     },
 }
 
+impl InterplugRequest {
+
+    /// Check if the plugin with the name and versions provided could
+    /// be used to fulfill this request, returning every reason it
+    /// could not
+    ///
+    /// A plugin of some version can be used by the code, written for
+    /// any version from its backwards compatibility version up to the
+    /// version itself, so it fits the request if any version in that
+    /// range matches the requested [`VersionReq`]. Only the requests
+    /// for a specific plugin or for a trait from a specific plugin
+    /// can be checked, every other request is rejected with
+    /// [`Rejection::UnsupportedRequest`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_api::{InterplugRequest, Rejection, Version};
+    ///
+    /// let request: InterplugRequest = InterplugRequest::PlugRequestAll {
+    ///     plugin: "test".to_string(),
+    ///     version: Version { major: 1, minor: 2, ..Default::default() }.into(),
+    /// };
+    /// let old: Version = Version { major: 1, minor: 0, ..Default::default() };
+    /// let new: Version = Version { major: 1, minor: 6, ..Default::default() };
+    ///
+    /// assert!(request.check_provider("test", &new, &old).is_ok());
+    /// assert!(matches!(
+    ///     request.check_provider("test", &new, &new).unwrap_err()[..],
+    ///     [Rejection::CompatibilityDropped { .. }]));
+    /// ```
+    pub fn check_provider (
+        self: &Self,
+        name: &str,
+        version: &Version,
+        backwards_compat_version: &Version,
+    ) -> Result<(), Vec<Rejection>> {
+
+        let (plugin, requirement): (&String, &VersionReq) = match self {
+            PlugRequest { plugin, version, .. } => (plugin, version),
+            PlugRequestAll { plugin, version } => (plugin, version),
+            TraitRequest { plugin, version, .. } => (plugin, version),
+            TraitRequestAll { plugin, version, .. } => (plugin, version),
+            _ => return Err(vec![Rejection::UnsupportedRequest]),
+        };
+
+        let mut reasons: Vec<Rejection> = Vec::new();
+        if plugin != name {
+            reasons.push(Rejection::NameMismatch {
+                requested: plugin.clone(),
+                found: name.to_string(),
+            });
+        }
+
+        let lowest: Version = Version::default();
+        let highest: Version = Version {
+            major: usize::MAX,
+            minor: usize::MAX,
+            release: usize::MAX,
+            build: usize::MAX,
+            ..Default::default()
+        };
+        if backwards_compat_version > version {
+            reasons.push(Rejection::InvalidCompatibility {
+                version: version.clone(),
+                backwards_compat_version: backwards_compat_version.clone(),
+            });
+        } else if !requirement.matches_range(backwards_compat_version, version) {
+            let reason: Rejection =
+                if requirement.matches_range(version, &highest) {
+                    Rejection::TooOld {
+                        requirement: requirement.clone(),
+                        version: version.clone(),
+                    }
+                } else if requirement.matches_range(&lowest, backwards_compat_version) {
+                    Rejection::CompatibilityDropped {
+                        requirement: requirement.clone(),
+                        backwards_compat_version:
+                            backwards_compat_version.clone(),
+                    }
+                } else {
+                    Rejection::Unsatisfiable {
+                        requirement: requirement.clone(),
+                    }
+                };
+            reasons.push(reason);
+        }
+        if !requirement.allows_pre_release(version) {
+            reasons.push(Rejection::PreRelease {
+                version: version.clone(),
+            });
+        }
+
+        if reasons.is_empty() {
+            return Ok(());
+        }
+        return Err(reasons);
+    }
+}
+
+impl InterplugRequest {
+
+    /// Find out whether the request is fulfilled, given the requests,
+    /// that were provided and denied so far
+    ///
+    /// A request is provided or denied if it was answered exactly as
+    /// it is. Otherwise a [`InterplugRequest::RequestEither`] request
+    /// is provided if any of its requests is, and a
+    /// [`InterplugRequest::RequestEach`] request if all of them are.
+    /// The requests, that were neither provided nor denied, are
+    /// [`RequestStatus::Pending`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_api::*;
+    ///
+    /// let first: InterplugRequest = PlugRequestAll {
+    ///     plugin: "first".to_string(),
+    ///     version: VersionReq::any(),
+    /// };
+    /// let second: InterplugRequest = PlugRequestAll {
+    ///     plugin: "second".to_string(),
+    ///     version: VersionReq::any(),
+    /// };
+    /// let either: InterplugRequest = RequestEither {
+    ///     requests: vec![first.clone(), second.clone()],
+    /// };
+    ///
+    /// assert_eq!(either.status(&[], &[first.clone()]), RequestStatus::Pending);
+    /// assert_eq!(either.status(&[second], &[first]), RequestStatus::Provided);
+    /// ```
+    pub fn status (
+        self: &Self,
+        provided: &[InterplugRequest],
+        denied: &[InterplugRequest],
+    ) -> RequestStatus {
+
+        if provided.contains(self) {
+            return RequestStatus::Provided;
+        }
+        if denied.contains(self) {
+            return RequestStatus::Denied;
+        }
+
+        match self {
+            RequestCrucial { request } | RequestOptional { request } =>
+                request.status(provided, denied),
+            RequestEither { requests } => {
+                let mut result: RequestStatus = RequestStatus::Denied;
+                for request in requests {
+                    match request.status(provided, denied) {
+                        RequestStatus::Provided => return RequestStatus::Provided,
+                        RequestStatus::Pending => result = RequestStatus::Pending,
+                        RequestStatus::Denied => (),
+                    }
+                }
+                result
+            },
+            RequestEach { requests } => {
+                let mut result: RequestStatus = RequestStatus::Provided;
+                for request in requests {
+                    match request.status(provided, denied) {
+                        RequestStatus::Denied => return RequestStatus::Denied,
+                        RequestStatus::Pending => result = RequestStatus::Pending,
+                        RequestStatus::Provided => (),
+                    }
+                }
+                result
+            },
+            _ => RequestStatus::Pending,
+        }
+    }
+}
+
+/// Enum, that represents whether an interplugin request is fulfilled
+/// (see [`InterplugRequest::status`])
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RequestStatus {
+
+    /// The request is fulfilled
+    Provided,
+
+    /// The request can not be fulfilled anymore
+    Denied,
+
+    /// The request was not answered yet
+    Pending,
+}
+
+impl std::fmt::Display for InterplugRequest {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        match self {
+            PlugRequest { plugin, fn_ids, version } =>
+                write!(f, "plugin {} {} (functions {:?})",
+                    plugin, version, fn_ids),
+            PlugRequestAll { plugin, version } =>
+                write!(f, "plugin {} {}", plugin, version),
+            TraitRequest { plugin, trait_id, fn_ids, version } =>
+                write!(f, "trait {} of plugin {} {} (functions {:?})",
+                    trait_id, plugin, version, fn_ids),
+            TraitRequestAll { plugin, trait_id, version } =>
+                write!(f, "trait {} of plugin {} {}",
+                    trait_id, plugin, version),
+            RequestEither { .. } => write!(f, "either of"),
+            RequestEach { .. } => write!(f, "each of"),
+            RequestCrucial { .. } => write!(f, "crucial"),
+            RequestOptional { .. } => write!(f, "optional"),
+        }
+    }
+}
+
+/// Enum, that represents a reason, a plugin could not be used to
+/// fulfill an [`InterplugRequest`] (see
+/// [`InterplugRequest::check_provider`])
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rejection {
+
+    /// Only the requests for a specific plugin can be checked
+    UnsupportedRequest,
+
+    /// Another plugin was requested
+    NameMismatch {
+
+        /// The name of the plugin requested
+        requested: String,
+
+        /// The name of the plugin checked
+        found: String,
+    },
+
+    /// The plugin is older than any version the requirement matches
+    TooOld {
+
+        /// The requirement, that was not matched
+        requirement: VersionReq,
+
+        /// The version of the plugin
+        version: Version,
+    },
+
+    /// The plugin is new enough, but it is not compatible with the
+    /// older versions, that the requirement matches, anymore
+    CompatibilityDropped {
+
+        /// The requirement, that was not matched
+        requirement: VersionReq,
+
+        /// The earliest version, the plugin is compatible with
+        backwards_compat_version: Version,
+    },
+
+    /// No version at all matches the requirement
+    Unsatisfiable {
+
+        /// The requirement, that can not be matched
+        requirement: VersionReq,
+    },
+
+    /// The backwards compatibility version of the plugin is greater
+    /// than the version of the plugin itself
+    InvalidCompatibility {
+
+        /// The version of the plugin
+        version: Version,
+
+        /// The backwards compatibility version of the plugin
+        backwards_compat_version: Version,
+    },
+
+    /// The plugin is a pre-release, but the requirement or the
+    /// policy of the host does not allow one (see
+    /// [`VersionReq::pre_releases`] and [`PreReleasePolicy`])
+    PreRelease {
+
+        /// The version of the plugin
+        version: Version,
+    },
+
+    /// The plugin does not implement the trait requested
+    NotImplemented,
+
+    /// The plugin does not provide some of the functions requested
+    MissingFunctions {
+
+        /// IDs of the functions, that are not provided
+        fn_ids: Vec<usize>,
+    },
+
+    /// There is no such plugin loaded
+    NotLoaded,
+
+    /// The plugin failed to initialize
+    Failed,
+
+    /// The plugin is waiting for its own requests to be answered
+    Initializing,
+
+    /// The plugin was shut down
+    Unloaded,
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        match self {
+            Rejection::UnsupportedRequest =>
+                write!(f, "request is not for a specific plugin"),
+            Rejection::NameMismatch { requested, found } =>
+                write!(f, "plugin {} was requested, not {}", requested, found),
+            Rejection::TooOld { requirement, version } =>
+                write!(f, "version {} is older than required ({})",
+                    version, requirement),
+            Rejection::CompatibilityDropped {
+                requirement, backwards_compat_version } =>
+                write!(f, "compatibility with versions before {} was \
+                    dropped, but {} is required",
+                    backwards_compat_version, requirement),
+            Rejection::Unsatisfiable { requirement } =>
+                write!(f, "no version matches {}", requirement),
+            Rejection::InvalidCompatibility {
+                version, backwards_compat_version } =>
+                write!(f, "backwards compatibility version {} is greater \
+                    than version {}",
+                    backwards_compat_version, version),
+            Rejection::PreRelease { version } =>
+                write!(f, "version {} is a pre-release", version),
+            Rejection::NotImplemented =>
+                write!(f, "trait is not implemented"),
+            Rejection::MissingFunctions { fn_ids } =>
+                write!(f, "functions {:?} are not provided", fn_ids),
+            Rejection::NotLoaded =>
+                write!(f, "plugin is not loaded"),
+            Rejection::Failed =>
+                write!(f, "plugin failed to initialize"),
+            Rejection::Initializing =>
+                write!(f, "plugin is waiting for its own requests"),
+            Rejection::Unloaded =>
+                write!(f, "plugin was shut down"),
+        }
+    }
+}
+
 /// Enum that represents a system limitation, that a plugin either
 /// needs to know to work correctly, or should be notified of in
 /// case main program wants to limit some settings
 ///
 /// When initiating the plugin, using [`Freight::init`], a vector
 /// of limitations can be passed to the plugin, to set such limits
-/// as number of cpu threads, memory working directories, etc.
+/// as number of cpu threads, memory, working directories, etc.
+/// Numeric settings are limited with [`Limitation::Top`] and
+/// [`Limitation::Bottom`], while the ones, that take any other kind
+/// of value, such as a list of directories the plugin may access or
+/// a timeout, are set with [`Limitation::Set`] (see
+/// [`LimitationValue`]).
 /// If for example the main program started to do some multithreading
 /// itself, it may notify the plugin using [`Freight::update_limitations`]
 /// that the maximum amount of threads it can use was lowered from
@@ -176,7 +535,7 @@ pub enum InterplugRequest {
 /// about the amount of threads anymore, and lets the plugin decide
 /// by itself which amount it wants to use, it can send a
 /// [`Limitation::Reset`] to it.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Limitation {
 
     /// Set the maximum allowed number, represetting some setting
@@ -206,4 +565,349 @@ pub enum Limitation {
         /// The name of the setting
         setting: String,
     },
+
+    /// Set a setting, that is not a number, to some value
+    Set {
+
+        /// The name of the setting
+        setting: String,
+
+        /// The value to which we want to set it
+        value: LimitationValue,
+    },
+}
+
+impl Limitation {
+
+    /// Create a [`Limitation::Set`] of the setting provided
+    pub fn set (
+        setting: &str,
+        value: impl Into<LimitationValue>,
+    ) -> Limitation {
+
+        Limitation::Set {
+            setting: setting.to_string(),
+            value: value.into(),
+        }
+    }
+
+    /// Get the name of the setting, the limitation is for
+    pub fn setting (self: &Self) -> &str {
+        match self {
+            Limitation::Top { setting, .. } => setting,
+            Limitation::Bottom { setting, .. } => setting,
+            Limitation::Reset { setting } => setting,
+            Limitation::Set { setting, .. } => setting,
+        }
+    }
+
+    /// Find the value, the setting provided was last set to by the
+    /// limitations provided, unless it was reset after that
+    ///
+    /// Both the main program, looking through the limitations it
+    /// passes to the plugins, and the plugins, looking through the
+    /// ones passed to [`Freight::init`] or
+    /// [`Freight::update_limitations`], may use it
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_api::*;
+    ///
+    /// let limitations: Vec<Limitation> = vec![
+    ///     Limitation::set("temp_dir", std::path::PathBuf::from("/tmp/a")),
+    ///     Limitation::set("timeout", std::time::Duration::from_secs(5)),
+    ///     Limitation::Reset { setting: "timeout".to_string() },
+    /// ];
+    /// let temp_dir: Option<&std::path::Path> =
+    ///     Limitation::find_value(&limitations, "temp_dir")
+    ///         .and_then(LimitationValue::as_path);
+    /// assert_eq!(temp_dir, Some(std::path::Path::new("/tmp/a")));
+    /// assert!(Limitation::find_value(&limitations, "timeout").is_none());
+    /// ```
+    pub fn find_value<'a> (
+        limitations: &'a [Limitation],
+        setting: &str,
+    ) -> Option<&'a LimitationValue> {
+
+        for limitation in limitations.iter().rev() {
+            match limitation {
+                Limitation::Set { setting: name, value } if name == setting =>
+                    return Some(value),
+                Limitation::Reset { setting: name } if name == setting =>
+                    return None,
+                _ => (),
+            }
+        }
+        return None;
+    }
+}
+
+impl std::fmt::Display for Limitation {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        match self {
+            Limitation::Top { setting, limit } =>
+                write!(f, "{} <= {}", setting, limit),
+            Limitation::Bottom { setting, limit } =>
+                write!(f, "{} >= {}", setting, limit),
+            Limitation::Reset { setting } =>
+                write!(f, "{} reset", setting),
+            Limitation::Set { setting, value } =>
+                write!(f, "{} = {}", setting, value),
+        }
+    }
+}
+
+/// Enum, that represents the value of a setting, that is not a
+/// number, set with [`Limitation::Set`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LimitationValue {
+
+    /// A path, for example of the temporary directory
+    Path(std::path::PathBuf),
+
+    /// A string
+    String(String),
+
+    /// A list of strings, for example of the allowed directories
+    StringList(Vec<String>),
+
+    /// A flag, that is either on or off
+    Boolean(bool),
+
+    /// A duration, for example a timeout
+    Duration(std::time::Duration),
+}
+
+impl LimitationValue {
+
+    /// Check if the value provided is of the same kind as this one
+    pub fn same_kind (
+        self: &Self,
+        other: &LimitationValue,
+    ) -> bool {
+
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    /// Get the value as a path, if it is one
+    pub fn as_path (self: &Self) -> Option<&std::path::Path> {
+        match self {
+            LimitationValue::Path(path) => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a string, if it is one
+    pub fn as_str (self: &Self) -> Option<&str> {
+        match self {
+            LimitationValue::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a list of strings, if it is one
+    pub fn as_string_list (self: &Self) -> Option<&[String]> {
+        match self {
+            LimitationValue::StringList(list) => Some(list),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a flag, if it is one
+    pub fn as_bool (self: &Self) -> Option<bool> {
+        match self {
+            LimitationValue::Boolean(flag) => Some(*flag),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a duration, if it is one
+    pub fn as_duration (self: &Self) -> Option<std::time::Duration> {
+        match self {
+            LimitationValue::Duration(duration) => Some(*duration),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for LimitationValue {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        match self {
+            LimitationValue::Path(path) =>
+                write!(f, "{}", path.display()),
+            LimitationValue::String(string) =>
+                write!(f, "{:?}", string),
+            LimitationValue::StringList(list) =>
+                write!(f, "{:?}", list),
+            LimitationValue::Boolean(flag) =>
+                write!(f, "{}", flag),
+            LimitationValue::Duration(duration) =>
+                write!(f, "{:?}", duration),
+        }
+    }
+}
+
+impl From<std::path::PathBuf> for LimitationValue {
+    fn from (path: std::path::PathBuf) -> LimitationValue {
+        LimitationValue::Path(path)
+    }
+}
+
+impl From<String> for LimitationValue {
+    fn from (string: String) -> LimitationValue {
+        LimitationValue::String(string)
+    }
+}
+
+impl From<Vec<String>> for LimitationValue {
+    fn from (list: Vec<String>) -> LimitationValue {
+        LimitationValue::StringList(list)
+    }
+}
+
+impl From<bool> for LimitationValue {
+    fn from (flag: bool) -> LimitationValue {
+        LimitationValue::Boolean(flag)
+    }
+}
+
+impl From<std::time::Duration> for LimitationValue {
+    fn from (duration: std::time::Duration) -> LimitationValue {
+        LimitationValue::Duration(duration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Function, that makes a request for all the functions of the
+    /// "test" plugin, matching the requirement provided
+    fn request (requirement: &str) -> InterplugRequest {
+        PlugRequestAll {
+            plugin: "test".to_string(),
+            version: requirement.parse().unwrap(),
+        }
+    }
+
+    /// Function, that checks the "test" plugin of the version and
+    /// backwards compatibility version provided against the request
+    fn check (
+        requirement: &str,
+        version: &str,
+        backwards_compat_version: &str,
+    ) -> Result<(), Vec<Rejection>> {
+
+        request(requirement).check_provider(
+            "test",
+            &version.parse().unwrap(),
+            &backwards_compat_version.parse().unwrap())
+    }
+
+    #[test]
+    fn compatible_providers () {
+        assert_eq!(check("=1.4", "1.6", "1.2"), Ok(()));
+        assert_eq!(check("=1.4", "1.4", "1.4"), Ok(()));
+        assert_eq!(check("^1.2", "1.9", "1.0"), Ok(()));
+        assert_eq!(check(">=1.2, <1.5", "2.0", "1.4"), Ok(()));
+    }
+
+    #[test]
+    fn version_rejections () {
+        assert!(matches!(check("=1.4", "1.3", "1.0").unwrap_err()[..],
+            [Rejection::TooOld { .. }]));
+        assert!(matches!(check("=1.4", "1.6", "1.5").unwrap_err()[..],
+            [Rejection::CompatibilityDropped { .. }]));
+        assert!(matches!(check(">1.5, <1.5", "1.6", "1.0").unwrap_err()[..],
+            [Rejection::Unsatisfiable { .. }]));
+        assert!(matches!(check("=1.4", "1.4", "1.6").unwrap_err()[..],
+            [Rejection::InvalidCompatibility { .. }]));
+        assert!(matches!(check("^1.2", "1.4-rc.1", "1.0").unwrap_err()[..],
+            [Rejection::PreRelease { .. }]));
+        assert_eq!(check(">=1.2-rc.1", "1.2-rc.2", "1.0"), Ok(()));
+    }
+
+    #[test]
+    fn other_rejections () {
+        let version: Version = "1.4".parse().unwrap();
+        assert_eq!(
+            request("=1.4").check_provider("other", &version, &version),
+            Err(vec![Rejection::NameMismatch {
+                requested: "test".to_string(),
+                found: "other".to_string(),
+            }]));
+        assert_eq!(
+            request("=1.4").check_provider("other", &"1.3".parse().unwrap(), &version)
+                .unwrap_err().len(),
+            2);
+
+        let either: InterplugRequest = RequestEither {
+            requests: vec![request("=1.4")],
+        };
+        assert_eq!(
+            either.check_provider("test", &version, &version),
+            Err(vec![Rejection::UnsupportedRequest]));
+    }
+
+    #[test]
+    fn limitation_values () {
+        let limitations: Vec<Limitation> = vec![
+            Limitation::set("temp", std::path::PathBuf::from("/tmp/a")),
+            Limitation::set("name", "first".to_string()),
+            Limitation::set("name", "second".to_string()),
+            Limitation::set("paths", vec!["a".to_string(), "b".to_string()]),
+            Limitation::set("verbose", true),
+            Limitation::set("timeout", std::time::Duration::from_millis(1500)),
+            Limitation::Reset { setting: "temp".to_string() },
+            Limitation::Top { setting: "threads".to_string(), limit: 4 },
+        ];
+
+        // The latest value is found, unless the setting was reset
+        // after it
+        let find = |setting: &str| Limitation::find_value(&limitations, setting);
+        assert!(find("temp").is_none());
+        assert!(find("threads").is_none());
+        assert_eq!(find("name").and_then(LimitationValue::as_str), Some("second"));
+        assert_eq!(
+            find("paths").and_then(LimitationValue::as_string_list),
+            Some(&["a".to_string(), "b".to_string()][..]));
+        assert_eq!(find("verbose").and_then(LimitationValue::as_bool), Some(true));
+        assert_eq!(
+            find("timeout").and_then(LimitationValue::as_duration),
+            Some(std::time::Duration::from_millis(1500)));
+
+        // Values of another kind are never read
+        let path: &LimitationValue =
+            &std::path::PathBuf::from("/tmp/a").into();
+        assert_eq!(path.as_path(), Some(std::path::Path::new("/tmp/a")));
+        assert!(path.as_str().is_none());
+        assert!(path.as_string_list().is_none());
+        assert!(path.as_bool().is_none());
+        assert!(path.as_duration().is_none());
+        assert!(!path.same_kind(find("name").unwrap()));
+        assert!(find("name").unwrap().same_kind(&"".to_string().into()));
+
+        let printed: Vec<String> = limitations.iter()
+            .map(|limitation| limitation.to_string())
+            .collect();
+        assert_eq!(printed, vec![
+            "temp = /tmp/a",
+            "name = \"first\"",
+            "name = \"second\"",
+            "paths = [\"a\", \"b\"]",
+            "verbose = true",
+            "timeout = 1.5s",
+            "temp reset",
+            "threads <= 4",
+        ]);
+    }
 }
@@ -28,6 +28,9 @@
 //!
 //! To quickly learn how to import and use plugins see [`FreightProxy`]
 //! documentation
+//!
+//! To load several plugins at once and let them use each other, see
+//! [`PluginHost`] documentation
 
 #![deny(warnings)]
 
@@ -64,6 +67,9 @@
 extern crate dusk_api_macros;
 pub use dusk_api_macros::*;
 
+#[doc(hidden)]
+pub use ::linkme;
+
 use std::any::{Any, TypeId};
 
 pub mod changelog;
@@ -73,7 +79,18 @@ pub mod error;
 
 pub mod declaration;
 pub mod registration;
+pub mod threadsafe;
+pub mod discovery;
+pub mod database;
+pub mod integrity;
+pub mod remote;
+pub mod abi;
+pub mod process;
+pub mod registry;
+pub mod resolver;
+pub mod graph;
 pub mod interplugin;
+pub mod limitations;
 
 pub mod objects;
 pub mod callables;
@@ -85,12 +102,25 @@ pub mod freights;
 
 pub mod stdtypes;
 
+mod wire;
+
 pub use version::*;
 pub use error::*;
 
 pub use declaration::*;
 pub use registration::*;
+pub use threadsafe::*;
+pub use discovery::*;
+pub use database::*;
+pub use integrity::*;
+pub use remote::*;
+pub use abi::*;
+pub use process::*;
+pub use registry::*;
+pub use resolver::*;
+pub use graph::*;
 pub use interplugin::*;
+pub use limitations::*;
 
 pub use objects::*;
 pub use callables::*;
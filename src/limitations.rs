@@ -0,0 +1,765 @@
+// Copyright (C) 2021 by Andy Gozas <andy@gozas.me>
+//
+// This file is part of Dusk API.
+//
+// Dusk API is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Dusk API is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Dusk API.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Module, containing everything needed for a plugin to declare the
+//! settings, it can be limited in, and to check the limitations,
+//! sent to it, against them
+
+use crate::*;
+
+/// A setting, a plugin can be limited in with a [`Limitation`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LimitationSetting {
+
+    /// The name of the setting
+    pub name: String,
+
+    /// The description of the setting
+    pub description: String,
+
+    /// The value the plugin uses, if it is not limited, for a numeric
+    /// setting
+    pub default: isize,
+
+    /// The value the plugin uses, if it is not set, for a setting,
+    /// that takes a value other than a number (see
+    /// [`Limitation::Set`])
+    pub default_value: Option<LimitationValue>,
+
+    /// The lowest value the setting can be limited to, if there is
+    /// one
+    pub min: Option<isize>,
+
+    /// The greatest value the setting can be limited to, if there is
+    /// one
+    pub max: Option<isize>,
+
+    /// The units the setting is measured in, if there are any
+    pub units: Option<String>,
+}
+
+impl LimitationSetting {
+
+    /// Create a setting with the name, description and default value
+    /// provided, that can be limited to any value
+    pub fn new (
+        name: String,
+        description: String,
+        default: isize,
+    ) -> LimitationSetting {
+
+        LimitationSetting {
+            name: name,
+            description: description,
+            default: default,
+            default_value: None,
+            min: None,
+            max: None,
+            units: None,
+        }
+    }
+
+    /// Create a setting with the name, description and default value
+    /// provided, that takes a value other than a number, so it can
+    /// only be changed with [`Limitation::Set`] to a value of the same
+    /// kind as the default one
+    pub fn with_value (
+        name: String,
+        description: String,
+        default: LimitationValue,
+    ) -> LimitationSetting {
+
+        LimitationSetting {
+            default_value: Some(default),
+            ..LimitationSetting::new(name, description, 0)
+        }
+    }
+
+    /// Only allow the setting to be limited to the values from the
+    /// lowest to the greatest value provided
+    pub fn with_range (
+        mut self: Self,
+        min: Option<isize>,
+        max: Option<isize>,
+    ) -> LimitationSetting {
+
+        self.min = min;
+        self.max = max;
+        return self;
+    }
+
+    /// Set the units the setting is measured in
+    pub fn with_units (
+        mut self: Self,
+        units: String,
+    ) -> LimitationSetting {
+
+        self.units = Some(units);
+        return self;
+    }
+
+    /// Check if the setting could be limited to the value provided
+    pub fn check_limit (
+        self: &Self,
+        limit: isize,
+    ) -> Result<(), LimitationRejection> {
+
+        if let Some(min) = self.min {
+            if limit < min {
+                return Err(LimitationRejection::BelowMinimum { min: min });
+            }
+        }
+        if let Some(max) = self.max {
+            if limit > max {
+                return Err(LimitationRejection::AboveMaximum { max: max });
+            }
+        }
+        return Ok(());
+    }
+}
+
+/// Enum, that represents the reason a [`Limitation`] was rejected
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitationRejection {
+
+    /// The plugin does not declare such a setting
+    UnknownSetting,
+
+    /// The setting does not take the kind of value, the limitation
+    /// sets it to, or can not be limited with a number
+    WrongKind,
+
+    /// The limit is lower than the lowest value, the setting can be
+    /// limited to
+    BelowMinimum {
+
+        /// The lowest value, the setting can be limited to
+        min: isize,
+    },
+
+    /// The limit is greater than the greatest value, the setting can
+    /// be limited to
+    AboveMaximum {
+
+        /// The greatest value, the setting can be limited to
+        max: isize,
+    },
+
+    /// The [`Limitation::Bottom`] limit is greater than the
+    /// [`Limitation::Top`] limit of the same setting
+    Conflicting {
+
+        /// The bottom limit
+        bottom: isize,
+
+        /// The top limit
+        top: isize,
+    },
+}
+
+impl std::fmt::Display for LimitationRejection {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        match self {
+            LimitationRejection::UnknownSetting =>
+                write!(f, "the plugin does not declare such a setting"),
+            LimitationRejection::WrongKind =>
+                write!(f, "the setting does not take such a value"),
+            LimitationRejection::BelowMinimum { min } =>
+                write!(f, "the setting can not be lower than {}", min),
+            LimitationRejection::AboveMaximum { max } =>
+                write!(f, "the setting can not be greater than {}", max),
+            LimitationRejection::Conflicting { bottom, top } =>
+                write!(f, "the bottom limit {} is greater than the top \
+                    limit {}", bottom, top),
+        }
+    }
+}
+
+/// A [`Limitation`], that was rejected, along with the reason it
+/// was rejected
+#[derive(Clone, Debug)]
+pub struct RejectedLimitation {
+
+    /// The limitation
+    pub limitation: Limitation,
+
+    /// The reason it was rejected
+    pub reason: LimitationRejection,
+}
+
+impl std::fmt::Display for RejectedLimitation {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        write!(f, "{}: {}", self.limitation, self.reason)
+    }
+}
+
+/// A list of every setting, a plugin can be limited in (see
+/// [`Freight::get_limitation_schema`])
+///
+/// # Example
+///
+/// ```
+/// use dusk_api::*;
+///
+/// let mut schema: LimitationSchema = LimitationSchema::new();
+/// schema.add(LimitationSetting::new(
+///         "threads".to_string(),
+///         "Amount of worker threads".to_string(),
+///         4)
+///     .with_range(Some(1), Some(64)));
+///
+/// let limitations: Vec<Limitation> = vec![
+///     Limitation::Top { setting: "threads".to_string(), limit: 2 },
+///     Limitation::Top { setting: "treads".to_string(), limit: 2 },
+///     Limitation::Bottom { setting: "threads".to_string(), limit: 0 },
+/// ];
+/// let rejected: Vec<RejectedLimitation> =
+///     schema.validate(&limitations).unwrap_err();
+/// assert_eq!(rejected[0].reason, LimitationRejection::UnknownSetting);
+/// assert_eq!(rejected[1].reason, LimitationRejection::BelowMinimum { min: 1 });
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LimitationSchema {
+
+    /// The settings
+    pub settings: Vec<LimitationSetting>,
+}
+
+impl LimitationSchema {
+
+    /// Create a schema without any settings
+    pub fn new () -> LimitationSchema {
+        LimitationSchema {
+            settings: Vec::new(),
+        }
+    }
+
+    /// Add the setting provided to the schema, replacing the setting
+    /// with the same name, if there is one
+    pub fn add (
+        self: &mut Self,
+        setting: LimitationSetting,
+    ) {
+
+        self.settings.retain(|other| other.name != setting.name);
+        self.settings.push(setting);
+    }
+
+    /// Get the setting with the name provided
+    pub fn get (
+        self: &Self,
+        name: &str,
+    ) -> Option<&LimitationSetting> {
+
+        self.settings.iter().find(|setting| setting.name == name)
+    }
+
+    /// Check the limitation provided on its own
+    pub fn check (
+        self: &Self,
+        limitation: &Limitation,
+    ) -> Result<(), LimitationRejection> {
+
+        let setting: &LimitationSetting = match self.get(limitation.setting()) {
+            Some(setting) => setting,
+            None => return Err(LimitationRejection::UnknownSetting),
+        };
+        match (limitation, &setting.default_value) {
+            (Limitation::Top { limit, .. }, None) |
+            (Limitation::Bottom { limit, .. }, None) =>
+                setting.check_limit(*limit),
+            (Limitation::Set { value, .. }, Some(default))
+                if value.same_kind(default) => Ok(()),
+            (Limitation::Reset { .. }, _) => Ok(()),
+            _ => Err(LimitationRejection::WrongKind),
+        }
+    }
+
+    /// Split the limitations provided into the ones, that fit the
+    /// schema, and the rejected ones
+    ///
+    /// Besides the limitations, that do not fit on their own (see
+    /// [`LimitationSchema::check`]), a [`Limitation::Bottom`] is
+    /// rejected, if it is greater than a [`Limitation::Top`] of the
+    /// same setting, provided along with it
+    pub fn filter (
+        self: &Self,
+        limitations: &[Limitation],
+    ) -> (Vec<Limitation>, Vec<RejectedLimitation>) {
+
+        let mut accepted: Vec<Limitation> = Vec::new();
+        let mut rejected: Vec<RejectedLimitation> = Vec::new();
+        for limitation in limitations {
+            let mut result: Result<(), LimitationRejection> =
+                self.check(limitation);
+            if let (Ok(()), Limitation::Bottom { setting, limit }) =
+                (&result, limitation) {
+
+                for other in limitations {
+                    match other {
+                        Limitation::Top { setting: other, limit: top }
+                            if other == setting && top < limit => {
+
+                            result = Err(LimitationRejection::Conflicting {
+                                bottom: *limit,
+                                top: *top,
+                            });
+                        },
+                        _ => (),
+                    }
+                }
+            }
+            match result {
+                Ok(()) => accepted.push(limitation.clone()),
+                Err(reason) => rejected.push(RejectedLimitation {
+                    limitation: limitation.clone(),
+                    reason: reason,
+                }),
+            }
+        }
+        return (accepted, rejected);
+    }
+
+    /// Check the limitations provided, returning every one of them,
+    /// that was rejected (see [`LimitationSchema::filter`])
+    pub fn validate (
+        self: &Self,
+        limitations: &[Limitation],
+    ) -> Result<(), Vec<RejectedLimitation>> {
+
+        let (_, rejected): (Vec<Limitation>, Vec<RejectedLimitation>) =
+            self.filter(limitations);
+        if rejected.is_empty() {
+            return Ok(());
+        }
+        return Err(rejected);
+    }
+}
+
+/// Enum, that represents what a plugin did with a [`Limitation`],
+/// passed to it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitationStatus {
+
+    /// The limit was applied as it is
+    Applied,
+
+    /// The plugin could not apply the numeric limit exactly, so it
+    /// applied the closest limit it could instead
+    Clamped {
+
+        /// The limit, that was applied instead
+        applied: isize,
+    },
+
+    /// The limit was not applied at all
+    Rejected {
+
+        /// The reason it was rejected
+        reason: LimitationRejection,
+    },
+}
+
+impl std::fmt::Display for LimitationStatus {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        match self {
+            LimitationStatus::Applied =>
+                write!(f, "applied"),
+            LimitationStatus::Clamped { applied } =>
+                write!(f, "clamped to {}", applied),
+            LimitationStatus::Rejected { reason } =>
+                write!(f, "rejected, as {}", reason),
+        }
+    }
+}
+
+/// A [`Limitation`], that is in effect for a plugin, along with what
+/// the plugin did with it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LimitationOutcome {
+
+    /// The limitation
+    pub limitation: Limitation,
+
+    /// What the plugin did with it
+    pub status: LimitationStatus,
+}
+
+impl std::fmt::Display for LimitationOutcome {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        write!(f, "{}: {}", self.limitation, self.status)
+    }
+}
+
+/// The value a plugin actually uses for one of its settings (see
+/// [`Freight::get_effective_limitations`]), along with the
+/// limitations of the setting, that are in effect
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EffectiveSetting {
+
+    /// The name of the setting
+    pub name: String,
+
+    /// The value the plugin uses, for a numeric setting
+    pub value: isize,
+
+    /// The value the plugin uses, for a setting, that takes a value
+    /// other than a number
+    pub set_value: Option<LimitationValue>,
+
+    /// The limitations of the setting, that are in effect, and the
+    /// ones rejected after them (see [`AppliedLimitations`]), along
+    /// with what the plugin did with them
+    pub limitations: Vec<LimitationOutcome>,
+}
+
+impl EffectiveSetting {
+
+    /// Check if any of the limitations of the setting was not applied
+    /// as it is
+    pub fn is_adjusted (self: &Self) -> bool {
+        self.limitations.iter()
+            .any(|outcome| outcome.status != LimitationStatus::Applied)
+    }
+}
+
+/// A structure, that keeps track of the limitations, that are in
+/// effect for a plugin, and of what the plugin did with them
+///
+/// Only the latest [`Limitation::Top`], [`Limitation::Bottom`] and
+/// [`Limitation::Set`] of every setting, that were not rejected,
+/// are kept, along with the ones rejected after them, and a
+/// [`Limitation::Reset`] forgets all of them. Plugins may record the
+/// limitations they get here to answer
+/// [`Freight::get_effective_limitations`]
+///
+/// # Example
+///
+/// ```
+/// use dusk_api::*;
+///
+/// let mut schema: LimitationSchema = LimitationSchema::new();
+/// schema.add(LimitationSetting::new(
+///         "threads".to_string(),
+///         "Amount of worker threads".to_string(),
+///         8)
+///     .with_range(Some(1), Some(64)));
+///
+/// let mut applied: AppliedLimitations = AppliedLimitations::new();
+/// applied.record(
+///     &Limitation::Top { setting: "threads".to_string(), limit: 3 },
+///     LimitationStatus::Clamped { applied: 2 });
+/// applied.record(
+///     &Limitation::Bottom { setting: "threads".to_string(), limit: 0 },
+///     LimitationStatus::Rejected {
+///         reason: LimitationRejection::BelowMinimum { min: 1 },
+///     });
+///
+/// let effective: Vec<EffectiveSetting> = applied.effective(&schema);
+/// assert_eq!(effective[0].value, 2);
+/// assert!(effective[0].is_adjusted());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AppliedLimitations {
+
+    /// The limitations in effect
+    pub outcomes: Vec<LimitationOutcome>,
+}
+
+impl AppliedLimitations {
+
+    /// Create a record without any limitations in effect
+    pub fn new () -> AppliedLimitations {
+        AppliedLimitations {
+            outcomes: Vec::new(),
+        }
+    }
+
+    /// Forget every limitation
+    pub fn clear (self: &mut Self) {
+        self.outcomes.clear();
+    }
+
+    /// Record the limitation provided, along with what was done with
+    /// it, replacing the limitation of the same kind for the same
+    /// setting, unless the new one was rejected and the old one was not
+    pub fn record (
+        self: &mut Self,
+        limitation: &Limitation,
+        status: LimitationStatus,
+    ) {
+
+        let rejected: bool =
+            matches!(status, LimitationStatus::Rejected { .. });
+        self.outcomes.retain(|outcome| {
+            if outcome.limitation.setting() != limitation.setting() {
+                return true;
+            }
+            let same_kind: bool = matches!(
+                (&outcome.limitation, limitation),
+                (Limitation::Top { .. }, Limitation::Top { .. }) |
+                (Limitation::Bottom { .. }, Limitation::Bottom { .. }) |
+                (Limitation::Set { .. }, Limitation::Set { .. }));
+            let replaced: bool = !rejected || matches!(outcome.status,
+                LimitationStatus::Rejected { .. });
+            if let Limitation::Reset { .. } = limitation {
+                return false;
+            }
+            return !(same_kind && replaced);
+        });
+        if let Limitation::Reset { .. } = limitation {
+            return;
+        }
+        self.outcomes.push(LimitationOutcome {
+            limitation: limitation.clone(),
+            status: status,
+        });
+    }
+
+    /// Get the limitations of the setting with the name provided
+    pub fn get (
+        self: &Self,
+        setting: &str,
+    ) -> Vec<LimitationOutcome> {
+
+        self.outcomes.iter()
+            .filter(|outcome| outcome.limitation.setting() == setting)
+            .cloned()
+            .collect()
+    }
+
+    /// Get the value, the setting with the name provided was set to
+    /// by the latest [`Limitation::Set`], that was not rejected
+    pub fn get_value (
+        self: &Self,
+        setting: &str,
+    ) -> Option<&LimitationValue> {
+
+        self.outcomes.iter()
+            .filter(|outcome| !matches!(outcome.status,
+                LimitationStatus::Rejected { .. }))
+            .find_map(|outcome| match &outcome.limitation {
+                Limitation::Set { setting: name, value } if name == setting =>
+                    Some(value),
+                _ => None,
+            })
+    }
+
+    /// Get the value of every setting of the schema provided, by
+    /// limiting its default value with the limitations, that were
+    /// applied or clamped, or by replacing it with the value, it was
+    /// set to
+    pub fn effective (
+        self: &Self,
+        schema: &LimitationSchema,
+    ) -> Vec<EffectiveSetting> {
+
+        let mut result: Vec<EffectiveSetting> = Vec::new();
+        for setting in &schema.settings {
+            let limitations: Vec<LimitationOutcome> = self.get(&setting.name);
+            let mut value: isize = setting.default;
+            let mut set_value: Option<LimitationValue> =
+                setting.default_value.clone();
+            for outcome in &limitations {
+                let limit: isize = match (&outcome.limitation, outcome.status) {
+                    (_, LimitationStatus::Rejected { .. }) => continue,
+                    (Limitation::Set { value, .. }, _) => {
+                        set_value = Some(value.clone());
+                        continue;
+                    },
+                    (_, LimitationStatus::Clamped { applied }) => applied,
+                    (Limitation::Top { limit, .. }, _) => *limit,
+                    (Limitation::Bottom { limit, .. }, _) => *limit,
+                    (Limitation::Reset { .. }, _) => continue,
+                };
+                value = match outcome.limitation {
+                    Limitation::Top { .. } => value.min(limit),
+                    _ => value.max(limit),
+                };
+            }
+            result.push(EffectiveSetting {
+                name: setting.name.clone(),
+                value: value,
+                set_value: set_value,
+                limitations: limitations,
+            });
+        }
+        return result;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Function, that makes a schema with a numeric "threads" setting
+    /// from 1 to 64 and a "temp" setting, that takes a path
+    fn test_schema () -> LimitationSchema {
+        let mut schema: LimitationSchema = LimitationSchema::new();
+        schema.add(LimitationSetting::new(
+                "threads".to_string(),
+                "Amount of worker threads".to_string(),
+                8)
+            .with_range(Some(1), Some(64)));
+        schema.add(LimitationSetting::with_value(
+                "temp".to_string(),
+                "Temporary directory".to_string(),
+                std::path::PathBuf::from("/tmp").into()));
+        return schema;
+    }
+
+    /// Function, that makes a [`Limitation::Top`] limitation
+    fn top (setting: &str, limit: isize) -> Limitation {
+        Limitation::Top { setting: setting.to_string(), limit: limit }
+    }
+
+    /// Function, that makes a [`Limitation::Bottom`] limitation
+    fn bottom (setting: &str, limit: isize) -> Limitation {
+        Limitation::Bottom { setting: setting.to_string(), limit: limit }
+    }
+
+    #[test]
+    fn schema_checks () {
+        let schema: LimitationSchema = test_schema();
+        let path: Limitation = Limitation::Set {
+            setting: "temp".to_string(),
+            value: std::path::PathBuf::from("/var/tmp").into(),
+        };
+        let flag: Limitation = Limitation::Set {
+            setting: "temp".to_string(),
+            value: true.into(),
+        };
+
+        assert_eq!(schema.check(&top("threads", 64)), Ok(()));
+        assert_eq!(schema.check(&bottom("threads", 1)), Ok(()));
+        assert_eq!(schema.check(&path), Ok(()));
+        assert_eq!(
+            schema.check(&Limitation::Reset { setting: "temp".to_string() }),
+            Ok(()));
+        assert_eq!(
+            schema.check(&top("threads", 65)),
+            Err(LimitationRejection::AboveMaximum { max: 64 }));
+        assert_eq!(
+            schema.check(&bottom("threads", 0)),
+            Err(LimitationRejection::BelowMinimum { min: 1 }));
+        assert_eq!(
+            schema.check(&top("treads", 2)),
+            Err(LimitationRejection::UnknownSetting));
+        assert_eq!(schema.check(&flag), Err(LimitationRejection::WrongKind));
+        assert_eq!(
+            schema.check(&top("temp", 2)),
+            Err(LimitationRejection::WrongKind));
+        assert_eq!(
+            schema.check(&Limitation::Set {
+                setting: "threads".to_string(),
+                value: true.into(),
+            }),
+            Err(LimitationRejection::WrongKind));
+    }
+
+    #[test]
+    fn schema_filtering () {
+        let schema: LimitationSchema = test_schema();
+        let limitations: Vec<Limitation> = vec![
+            bottom("threads", 16),
+            top("threads", 4),
+            top("treads", 4),
+            top("threads", 32),
+        ];
+
+        // The bottom limit conflicts with the lowest top one, and
+        // everything else keeps its order
+        let (accepted, rejected): (Vec<Limitation>, Vec<RejectedLimitation>) =
+            schema.filter(&limitations);
+        assert_eq!(accepted, vec![top("threads", 4), top("threads", 32)]);
+        assert_eq!(rejected.len(), 2);
+        assert_eq!(rejected[0].limitation, bottom("threads", 16));
+        assert_eq!(
+            rejected[0].reason,
+            LimitationRejection::Conflicting { bottom: 16, top: 4 });
+        assert_eq!(rejected[1].reason, LimitationRejection::UnknownSetting);
+        assert_eq!(
+            rejected[0].to_string(),
+            "threads >= 16: the bottom limit 16 is greater than the top limit 4");
+
+        assert!(schema.validate(&accepted).is_ok());
+        assert_eq!(schema.validate(&limitations).unwrap_err().len(), 2);
+        assert!(LimitationSchema::new().validate(&[top("threads", 4)]).is_err());
+    }
+
+    #[test]
+    fn schema_settings () {
+        let mut schema: LimitationSchema = test_schema();
+        schema.add(LimitationSetting::new(
+                "threads".to_string(),
+                "Amount of worker threads".to_string(),
+                2)
+            .with_units("threads".to_string()));
+
+        assert_eq!(schema.settings.len(), 2);
+        assert_eq!(schema.get("threads").unwrap().default, 2);
+        assert_eq!(schema.check(&top("threads", 1000)), Ok(()));
+        assert!(schema.get("temp").unwrap().default_value.is_some());
+        assert!(schema.get("treads").is_none());
+    }
+
+    #[test]
+    fn applied_limitations () {
+        let schema: LimitationSchema = test_schema();
+        let mut applied: AppliedLimitations = AppliedLimitations::new();
+        applied.record(&top("threads", 16), LimitationStatus::Applied);
+        applied.record(&bottom("threads", 4), LimitationStatus::Applied);
+
+        // A rejected limitation does not replace the applied one
+        applied.record(&top("threads", 100), LimitationStatus::Rejected {
+            reason: LimitationRejection::AboveMaximum { max: 64 },
+        });
+        applied.record(&top("threads", 6), LimitationStatus::Clamped {
+            applied: 5,
+        });
+        let effective: Vec<EffectiveSetting> = applied.effective(&schema);
+        assert_eq!(effective[0].value, 5);
+        assert_eq!(effective[0].limitations.len(), 2);
+        assert!(effective[0].is_adjusted());
+        assert_eq!(
+            effective[1].set_value,
+            Some(std::path::PathBuf::from("/tmp").into()));
+        assert!(!effective[1].is_adjusted());
+
+        applied.record(
+            &Limitation::Reset { setting: "threads".to_string() },
+            LimitationStatus::Applied);
+        assert!(applied.get("threads").is_empty());
+        assert_eq!(applied.effective(&schema)[0].value, 8);
+    }
+}
@@ -52,6 +52,20 @@ pub struct Module {
     pub constants: Vec<Function>,
 }
 
+impl PinLibrary for Module {
+    fn pin_library (
+        self: &mut Self,
+        lib: &std::sync::Arc<libloading::Library>,
+    ) {
+
+        self.types.pin_library(lib);
+        self.functions.pin_library(lib);
+        self.submodules.pin_library(lib);
+        self.trait_definitions.pin_library(lib);
+        self.constants.pin_library(lib);
+    }
+}
+
 impl Default for Module {
     fn default () -> Module {
         Module {
@@ -49,6 +49,11 @@ pub struct Object {
     phantom: std::marker::PhantomData<Box<dyn DkAny>>,
     data_type: &'static Type,
     flags: u32,
+
+    /// Library, the code and the type of the underlying data are
+    /// located in, if it was returned from a plugin (see
+    /// [`PinLibrary`])
+    library: Option<std::sync::Arc<libloading::Library>>,
 }
 
 #[derive(Debug)]
@@ -196,9 +201,18 @@ impl Object {
             data_type: data_type,
             flags: flags,
             phantom: std::marker::PhantomData,
+            library: None,
         }
     }
 
+    /// Get the type of the underlying data
+    pub fn get_type (
+        self: &Object,
+    ) -> &'static Type {
+
+        self.data_type
+    }
+
     pub fn get_flags (
         self: &Object,
     ) -> Result<u32, Error> {
@@ -611,6 +625,7 @@ impl Clone for Object {
             data_type: self.data_type,
             phantom: std::marker::PhantomData,
             flags: self.flags,
+            library: self.library.clone(),
         }
     }
 }
@@ -631,6 +646,18 @@ impl Drop for Object {
     }
 }
 
+// The library is dropped only after the data is released in drop,
+// so the data is always released by the code it was created with
+impl PinLibrary for Object {
+    fn pin_library (
+        self: &mut Self,
+        lib: &std::sync::Arc<libloading::Library>,
+    ) {
+
+        self.library = Some(lib.clone());
+    }
+}
+
 unsafe impl Send for Object {}
 unsafe impl Sync for Object {}
 
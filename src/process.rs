@@ -0,0 +1,479 @@
+// Copyright (C) 2021 by Andy Gozas <andy@gozas.me>
+//
+// This file is part of Dusk API.
+//
+// Dusk API is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Dusk API is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Dusk API.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Module, containing everything needed to run a plugin in a child
+//! process, so that a plugin, that crashes or misbehaves, can not
+//! take the program using it down
+//!
+//! The child process serves the freight with [`serve_pipe`] (or
+//! [`serve_library`]), reading the encoded calls from its standard
+//! input and writing the replies to its standard output. The program
+//! starts it with [`FreightProxy::spawn`], getting a proxy, that
+//! passes every call through a [`PipeChannel`]. A child process,
+//! that does not read the call or does not reply in time, is killed,
+//! so it can not make the program wait forever.
+//!
+//! Every message is written as its length (8 little endian bytes)
+//! followed by the message itself. The first message the child
+//! writes is the [`FreightDescription`] of its freight.
+//!
+//! Plugins, running in another process, can not be provided with
+//! the plugins of the program, so every request of theirs, answered
+//! with [`Freight::interplug_provide`], is denied instead.
+
+use crate::*;
+use crate::wire::*;
+
+/// The largest message, that is accepted through a pipe. Anything
+/// longer is considered to be a protocol error, so a misbehaving
+/// child process can not make the program allocate more than this
+/// for a single reply
+pub static MAX_MESSAGE_SIZE: usize = 16 << 20;
+
+/// Time a child process is given to reply to each call, including
+/// the description it sends when it starts, unless another one is
+/// set (see [`PluginHost::process_timeout`])
+pub const DEFAULT_PROCESS_TIMEOUT: std::time::Duration =
+    std::time::Duration::from_secs(60);
+
+/// Time, a child process is given to exit on its own after its input
+/// is closed, before it is killed
+const EXIT_WAIT: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Time between the checks whether the child process has exited
+const EXIT_POLL: std::time::Duration = std::time::Duration::from_millis(10);
+
+/// Write a message to the pipe provided
+fn write_message (
+    pipe: &mut dyn std::io::Write,
+    message: &[u8],
+) -> std::io::Result<()> {
+
+    pipe.write_all(&(message.len() as u64).to_le_bytes())?;
+    pipe.write_all(message)?;
+    pipe.flush()
+}
+
+/// Read a message from the pipe provided, returning [`None`] if the
+/// pipe was closed before the next message started
+fn read_message (
+    pipe: &mut dyn std::io::Read,
+) -> std::io::Result<Option<Vec<u8>>> {
+
+    let mut len: [u8; 8] = [0; 8];
+    let mut read: usize = 0;
+    while read < len.len() {
+        match pipe.read(&mut len[read..])? {
+            0 if read == 0 => return Ok(None),
+            0 => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+            amount => read += amount,
+        }
+    }
+
+    let len: u64 = u64::from_le_bytes(len);
+    if len > MAX_MESSAGE_SIZE as u64 {
+        return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Message is too long"));
+    }
+
+    // The buffer only grows as the data arrives, so a length, that is
+    // never followed by the data, does not allocate anything
+    use std::io::Read;
+    let mut message: Vec<u8> = Vec::new();
+    pipe.take(len).read_to_end(&mut message)?;
+    if message.len() as u64 != len {
+        return Err(std::io::ErrorKind::UnexpectedEof.into());
+    }
+    return Ok(Some(message));
+}
+
+/// Serve the freight provided through the standard input and output
+/// of this process, until the input is closed
+///
+/// This function is meant to be the only thing done by the child
+/// process, started with [`FreightProxy::spawn`]. The freight
+/// **MUST NOT** write anything to the standard output, as it is
+/// used to pass the replies. The standard error may be used freely
+///
+/// # Example
+///
+/// ``` rust, ignore
+/// fn main () {
+///     let description: FreightDescription = FreightDescription {
+///         name: "test".to_string(),
+///         freight_version: Version { major: 1, ..Default::default() },
+///         backwards_compat_version: Version { major: 1, ..Default::default() },
+///         api_version: dusk_api::API_VERSION.to_string(),
+///         layout_fingerprint: dusk_api::LAYOUT_FINGERPRINT,
+///         rustc_version: dusk_api::RUSTC_VERSION.to_string(),
+///     };
+///     dusk_api::serve_pipe(&description, Box::new(MyFreight)).unwrap();
+/// }
+/// ```
+pub fn serve_pipe (
+    description: &FreightDescription,
+    freight: Box<dyn Freight>,
+) -> Result<(), Error> {
+
+    let stdin: std::io::Stdin = std::io::stdin();
+    let stdout: std::io::Stdout = std::io::stdout();
+    let mut input: std::io::StdinLock<'_> = stdin.lock();
+    let mut output: std::io::StdoutLock<'_> = stdout.lock();
+    let mut server: FreightServer = FreightServer::new(freight);
+
+    if let Err(err) = write_message(&mut output, &encode(description)?) {
+        return Err(pipe_error(err));
+    }
+    loop {
+        let request: Vec<u8> = match read_message(&mut input) {
+            Ok(Some(request)) => request,
+            Ok(None) => return Ok(()),
+            Err(err) => return Err(pipe_error(err)),
+        };
+        if let Err(err) = write_message(&mut output, &server.handle(&request)) {
+            return Err(pipe_error(err));
+        }
+    }
+}
+
+/// Load the plugin library at the path provided and serve its
+/// freight with [`serve_pipe`]
+///
+/// This lets any program, that only calls this function, be used as
+/// a runner for plugin libraries
+///
+/// # Safety
+///
+/// See [`FreightProxy::load`]
+///
+/// # Example
+///
+/// ``` rust, ignore
+/// fn main () {
+///     let path: String = std::env::args().nth(1).unwrap();
+///     unsafe { dusk_api::serve_library(&path) }.unwrap();
+/// }
+/// ```
+pub unsafe fn serve_library (
+    lib_path: &str,
+) -> Result<(), Error> {
+
+    let proxy: FreightProxy = FreightProxy::load(lib_path)?;
+    let description: FreightDescription = FreightDescription {
+        name: proxy.name.clone(),
+        freight_version: proxy.version.clone(),
+        backwards_compat_version: proxy.backwards_compat_version.clone(),
+        api_version: API_VERSION.to_string(),
+        layout_fingerprint: LAYOUT_FINGERPRINT,
+        rustc_version: RUSTC_VERSION.to_string(),
+    };
+    serve_pipe(&description, Box::new(proxy))
+}
+
+/// Turn an error, that happened while using a pipe into an
+/// [`Error::OsError`]
+fn pipe_error (err: std::io::Error) -> Error {
+    OsError(
+        format!(
+            "Plugin pipe failed: {}",
+            err,
+        ))
+}
+
+/// A [`FreightChannel`], that passes the calls to a child process
+/// through its standard input and output
+///
+/// Once the child process exits, does anything, that does not
+/// follow the protocol, or does not read a call or reply to it in
+/// time (see
+/// [`PipeChannel::set_timeout`]), it is killed and every call made
+/// afterwards fails with an [`Error::RuntimeError`]. The child
+/// process is killed when the channel is dropped as well
+#[derive(Debug)]
+pub struct PipeChannel {
+
+    /// The child process
+    child: std::process::Child,
+
+    /// Messages, written to the input of the child process by a
+    /// separate thread, so that waiting for the child process to read
+    /// them can time out. Dropped to close the input
+    requests: Option<std::sync::mpsc::Sender<Vec<u8>>>,
+
+    /// Results of writing each of the messages to the input of the
+    /// child process
+    written: std::sync::mpsc::Receiver<std::io::Result<()>>,
+
+    /// Messages, read from the output of the child process by a
+    /// separate thread, so that waiting for them can time out
+    replies: std::sync::mpsc::Receiver<std::io::Result<Option<Vec<u8>>>>,
+
+    /// Time the child process is given to reply to a call
+    timeout: Option<std::time::Duration>,
+
+    /// The description of the freight, sent by the child process
+    description: FreightDescription,
+
+    /// Whether the child process has stopped following the protocol
+    broken: bool,
+}
+
+impl PipeChannel {
+
+    /// Start the command provided with piped standard input and
+    /// output, and read the description of the freight it serves
+    ///
+    /// The child process is given the timeout provided to send the
+    /// description and to read and reply to every call afterwards (see
+    /// [`PipeChannel::set_timeout`])
+    pub fn spawn (
+        command: &mut std::process::Command,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<PipeChannel, Error> {
+
+        let mut child: std::process::Child = match command
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .spawn() {
+
+            Ok(child) => child,
+            Err(err) => return Err(OsError(
+                    format!(
+                        "Could not start the plugin process: {}",
+                        err,
+                    ))),
+        };
+
+        let mut stdout: std::process::ChildStdout = match child.stdout.take() {
+            Some(stdout) => stdout,
+            None => return Err(OsError(
+                    "Could not read the plugin process output".to_string()
+            )),
+        };
+        let mut stdin: std::process::ChildStdin = match child.stdin.take() {
+            Some(stdin) => stdin,
+            None => return Err(OsError(
+                    "Could not write the plugin process input".to_string()
+            )),
+        };
+
+        let (requests, pending) = std::sync::mpsc::channel::<Vec<u8>>();
+        let (written_sender, written) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for request in pending {
+                let result: std::io::Result<()> =
+                    write_message(&mut stdin, &request);
+                let last: bool = result.is_err();
+                if written_sender.send(result).is_err() || last {
+                    return;
+                }
+            }
+        });
+        let (sender, replies) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            loop {
+                let message: std::io::Result<Option<Vec<u8>>> =
+                    read_message(&mut stdout);
+                let last: bool = !matches!(message, Ok(Some(_)));
+                if sender.send(message).is_err() || last {
+                    return;
+                }
+            }
+        });
+
+        let mut result: PipeChannel = PipeChannel {
+            child: child,
+            requests: Some(requests),
+            written: written,
+            replies: replies,
+            timeout: timeout,
+            description: FreightDescription {
+                name: String::new(),
+                freight_version: Version::default(),
+                backwards_compat_version: Version::default(),
+                api_version: String::new(),
+                layout_fingerprint: 0,
+                rustc_version: String::new(),
+            },
+            broken: false,
+        };
+        let description: Vec<u8> = result.receive(result.deadline())?;
+        result.description = decode(&description)?;
+        return Ok(result);
+    }
+
+    /// Get the description of the freight, sent by the child process
+    pub fn description (self: &Self) -> &FreightDescription {
+        &self.description
+    }
+
+    /// Set the time the child process is given to read and reply to
+    /// each call, [`None`] meaning it is waited for as long as it takes
+    pub fn set_timeout (
+        self: &mut Self,
+        timeout: Option<std::time::Duration>,
+    ) {
+
+        self.timeout = timeout;
+    }
+
+    /// Check if the child process is still running and following
+    /// the protocol
+    pub fn is_alive (self: &mut Self) -> bool {
+        if self.broken {
+            return false;
+        }
+        matches!(self.child.try_wait(), Ok(None))
+    }
+
+    /// Stop using the child process, killing it
+    fn fail (
+        self: &mut Self,
+        err: Error,
+    ) -> Error {
+
+        self.broken = true;
+        self.requests = None;
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        return err;
+    }
+
+    /// Get the time, the child process has to reply to a call, made
+    /// right now, by, if there is a timeout
+    fn deadline (self: &Self) -> Option<std::time::Instant> {
+        self.timeout.map(|timeout| std::time::Instant::now() + timeout)
+    }
+
+    /// Write the message to the input of the child process, waiting
+    /// for it to be read until the deadline provided
+    fn send (
+        self: &mut Self,
+        request: Vec<u8>,
+        deadline: Option<std::time::Instant>,
+    ) -> Result<(), Error> {
+
+        let sent: bool = match &self.requests {
+            Some(requests) => requests.send(request).is_ok(),
+            None => false,
+        };
+        if !sent {
+            return Err(self.fail(pipe_error(
+                        std::io::ErrorKind::BrokenPipe.into())));
+        }
+
+        let result: std::io::Result<()> = match deadline {
+            Some(deadline) => match self.written.recv_timeout(
+                deadline.saturating_duration_since(std::time::Instant::now())) {
+
+                Ok(result) => result,
+                Err(std::sync::mpsc::RecvTimeoutError::Timeout) =>
+                    return Err(self.fail(RuntimeError(
+                                "Plugin process did not read the call in time"
+                                .to_string()
+                    ))),
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) =>
+                    Err(std::io::ErrorKind::BrokenPipe.into()),
+            },
+            None => match self.written.recv() {
+                Ok(result) => result,
+                Err(_) => Err(std::io::ErrorKind::BrokenPipe.into()),
+            },
+        };
+        match result {
+            Ok(()) => Ok(()),
+            Err(err) => Err(self.fail(pipe_error(err))),
+        }
+    }
+
+    /// Read the next message, sent by the child process, waiting for
+    /// it until the deadline provided
+    fn receive (
+        self: &mut Self,
+        deadline: Option<std::time::Instant>,
+    ) -> Result<Vec<u8>, Error> {
+
+        let result: std::io::Result<Option<Vec<u8>>> = match deadline {
+            Some(deadline) => match self.replies.recv_timeout(
+                deadline.saturating_duration_since(std::time::Instant::now())) {
+
+                Ok(result) => result,
+                Err(std::sync::mpsc::RecvTimeoutError::Timeout) =>
+                    return Err(self.fail(RuntimeError(
+                                "Plugin process did not reply in time"
+                                .to_string()
+                    ))),
+                Err(std::sync::mpsc::RecvTimeoutError::Disconnected) =>
+                    Ok(None),
+            },
+            None => match self.replies.recv() {
+                Ok(result) => result,
+                Err(_) => Ok(None),
+            },
+        };
+        match result {
+            Ok(Some(message)) => Ok(message),
+            Ok(None) => Err(self.fail(RuntimeError(
+                        "Plugin process exited".to_string()
+            ))),
+            Err(err) => Err(self.fail(pipe_error(err))),
+        }
+    }
+}
+
+impl FreightChannel for PipeChannel {
+    fn exchange (
+        self: &mut Self,
+        request: Vec<u8>,
+    ) -> Result<Vec<u8>, Error> {
+
+        if self.broken {
+            return Err(RuntimeError(
+                    "Plugin process is not running".to_string()
+            ));
+        }
+
+        // Both writing the call and reading the reply have to be done
+        // within the same timeout
+        let deadline: Option<std::time::Instant> = self.deadline();
+        self.send(request, deadline)?;
+        self.receive(deadline)
+    }
+}
+
+impl Drop for PipeChannel {
+    fn drop (self: &mut Self) {
+        if self.broken {
+            return;
+        }
+
+        // Closing the input makes the child process stop serving, so
+        // it is given some time to exit on its own before it is killed.
+        // The input is closed once the thread, writing to it, stops
+        self.requests = None;
+        let start: std::time::Instant = std::time::Instant::now();
+        while start.elapsed() < EXIT_WAIT {
+            match self.child.try_wait() {
+                Ok(None) => std::thread::sleep(EXIT_POLL),
+                _ => return,
+            }
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -39,6 +39,26 @@ pub trait FreightRegistrar {
         self: &mut Self,
         freight: Box<dyn Freight>,
     );
+
+    /// Function that gets a [`Freight`] implementor, that may be
+    /// moved to another thread, passed as an argument. Plugins, that
+    /// are safe to be used from any thread, should register their
+    /// freight with this function, so that they could be loaded as
+    /// a [`SyncFreightProxy`] as well
+    ///
+    /// The objects, its functions return, are handed out to the
+    /// calling thread, so they must not share anything with the
+    /// freight, that is not thread safe
+    ///
+    /// By default the freight is simply passed to
+    /// [`FreightRegistrar::register_freight`]
+    fn register_sync_freight (
+        self: &mut Self,
+        freight: Box<dyn Freight + Send>,
+    ) {
+
+        self.register_freight(freight);
+    }
 }
 
 /// A structure, that contains a Freight object and is used to import
@@ -52,6 +72,11 @@ pub trait FreightRegistrar {
 /// To learn more about the functions you may call on the
 /// [`FreightProxy`], see [`Freight`] trait documentation
 ///
+/// [`Freight`] is also implemented for [`std::rc::Rc<FreightProxy>`],
+/// so a proxy, provided to a plugin through
+/// [`Freight::interplug_provide`] or gotten from a [`PluginHost`],
+/// can be used in exactly the same way
+///
 /// # Example
 ///
 /// ``` rust, ignore
@@ -67,12 +92,14 @@ pub trait FreightRegistrar {
 #[derive(Debug)]
 pub struct FreightProxy {
 
-    /// Imported freight, solely for internal purposes
-    freight: Box<dyn Freight>,
-
-    /// Lib this freight was imported from to make sure this
-    /// structure does not outlive the library it was imported from
-    lib: Option<std::rc::Rc<libloading::Library>>,
+    /// Imported freight along with everything already gotten from
+    /// it, solely for internal purposes
+    ///
+    /// It is kept inside of a [`std::cell::RefCell`], so that a
+    /// proxy, shared using [`std::rc::Rc`] (for example by a
+    /// [`PluginHost`] or through [`Freight::interplug_provide`])
+    /// could still be used
+    inner: std::cell::RefCell<FreightCache>,
 
     /// Imported freights name as a static string
     pub name: String,
@@ -83,6 +110,14 @@ pub struct FreightProxy {
     /// The earliest version, for which the code was designed, this
     /// code can safely be run with the new plugin version
     pub backwards_compat_version: Version,
+}
+
+/// Imported freight and all the lists, that were already gotten
+/// from it, so they do not need to be built again
+#[derive(Debug)]
+pub(crate) struct FreightCache {
+
+    freight: Box<dyn Freight>,
 
     callables: Option<Vec<Box<dyn DuskCallable>>>,
 
@@ -103,6 +138,88 @@ pub struct FreightProxy {
     trait_definitions_by_name: Option<std::collections::HashMap<String, Vec<usize>>>,
 
     modules_by_name: Option<std::collections::HashMap<String, Vec<usize>>>,
+
+    /// Requests, that were provided to the freight since it was
+    /// last initialized
+    provided: Vec<InterplugRequest>,
+
+    /// Requests, that were denied to the freight since it was last
+    /// initialized
+    denied: Vec<InterplugRequest>,
+
+    limitation_schema: Option<Option<LimitationSchema>>,
+
+    /// Limitations, that did not fit the schema of the freight, the
+    /// last time it was initialized or had its limitations updated
+    rejected_limitations: Vec<RejectedLimitation>,
+
+    /// Limitations, that are in effect for the freight since it was
+    /// last initialized, along with the ones rejected by its schema
+    applied_limitations: AppliedLimitations,
+
+    /// Lib this freight was imported from to make sure this
+    /// structure does not outlive the library it was imported from
+    ///
+    /// Everything gotten from the freight is pinned to this library
+    /// (see [`PinLibrary`]), so it is only unloaded once nothing,
+    /// imported from it, is used anymore. Must be dropped after
+    /// everything else, but the copy it was loaded from
+    pub(crate) lib: Option<std::sync::Arc<libloading::Library>>,
+
+    /// The private copy of the library, checked by a
+    /// [`LibraryVerifier`], if the library was loaded from one (see
+    /// [`FreightProxy::load_verified`]). Must stay the last field, so
+    /// the copy is only removed after the library is dropped
+    pub(crate) verified: Option<VerifiedLibrary>,
+}
+
+/// Trait, implemented for everything a plugin may provide, that
+/// holds code or data located inside of the plugin library
+///
+/// A [`FreightProxy`] pins every [`Function`], [`Type`], [`Module`]
+/// and [`TraitDefinition`] it hands out to the library it was loaded
+/// from, wrapping every callable into a [`LibraryCallable`], so that
+/// every [`Object`] such callable returns is pinned as well. This way
+/// the library is only unloaded once neither the proxy nor anything
+/// gotten from it is used anymore.
+pub trait PinLibrary {
+
+    /// Make sure the library provided is not unloaded while this
+    /// value (or anything cloned or returned from it) is still in use
+    fn pin_library (
+        self: &mut Self,
+        lib: &std::sync::Arc<libloading::Library>,
+    );
+}
+
+impl <T> PinLibrary for Vec<T>
+where
+    T: PinLibrary
+{
+    fn pin_library (
+        self: &mut Self,
+        lib: &std::sync::Arc<libloading::Library>,
+    ) {
+
+        for item in self.iter_mut() {
+            item.pin_library(lib);
+        }
+    }
+}
+
+impl <T> PinLibrary for Option<T>
+where
+    T: PinLibrary
+{
+    fn pin_library (
+        self: &mut Self,
+        lib: &std::sync::Arc<libloading::Library>,
+    ) {
+
+        if let Some(item) = self {
+            item.pin_library(lib);
+        }
+    }
 }
 
 /// Functions, needed to configure [`FreightProxy`] structure
@@ -111,17 +228,107 @@ impl FreightProxy {
 
     /// Function, used to build a [`FreightProxy`] object from a
     /// library path
+    ///
+    /// If the plugin was built with the same compiler version and
+    /// layout of the core structures (see [`LAYOUT_FINGERPRINT`]) as
+    /// the program using it, the freight is used directly. Otherwise
+    /// it is used through the C ABI (see [`AbiFreightDeclaration`]),
+    /// so the plugin does not have to be rebuilt every time the
+    /// compiler changes. To only allow the exact match, use
+    /// [`FreightProxy::load_strict`]
+    ///
+    /// # Safety
+    ///
+    /// The library is loaded, which runs its initialization code and
+    /// the register function of the plugin, so it has to be a Dusk
+    /// API plugin, that is safe to run
     pub unsafe fn load (
         lib_path: &str,
     ) -> Result<FreightProxy, Error> {
 
         // Import the library
-        let lib : std::rc::Rc<libloading::Library>;
+        let lib : std::sync::Arc<libloading::Library>;
         match libloading::Library::new(lib_path) {
-            Ok(library) => lib = std::rc::Rc::new(library),
+            Ok(library) => lib = std::sync::Arc::new(library),
             Err(lib_err) => return(Err(LoadingError (lib_err))),
         }
 
+        // Get the C ABI declaration structure from this lib, if it
+        // has one, and use it unless the plugin can be used directly
+        if let Ok(decl) = lib.get::<*mut AbiFreightDeclaration>(
+            b"abi_freight_declaration\0") {
+
+            let declaration: AbiFreightDeclaration = decl.read();
+            if declaration.abi_version == ABI_VERSION &&
+                !FreightDescription::from_abi(&declaration)?.is_native() {
+
+                return FreightProxy::load_from_abi_declaration(
+                    &declaration,
+                    Some(lib));
+            }
+        }
+
+        return FreightProxy::load_native(lib);
+    }
+
+    /// Function, used to build a [`FreightProxy`] object from a
+    /// library path, checking the library with the verifier provided
+    /// first, so no code of a library, that did not pass the check,
+    /// is ever run
+    ///
+    /// The library, that gets loaded, is a private copy of the
+    /// contents, that were checked (see [`VerifiedLibrary`]), kept
+    /// for as long as the proxy is, so it can not be replaced after
+    /// the check. If the check fails, [`Error::IntegrityError`] is
+    /// returned
+    ///
+    /// # Safety
+    ///
+    /// The library is loaded, which runs its initialization code and
+    /// the register function of the plugin, so it has to be a Dusk
+    /// API plugin, that is safe to run. The check only makes sure the
+    /// library is the one expected, not that its code is sound
+    pub unsafe fn load_verified (
+        lib_path: &str,
+        verifier: &LibraryVerifier,
+    ) -> Result<FreightProxy, Error> {
+
+        let copy: VerifiedLibrary =
+            verifier.copy_verified(std::path::Path::new(lib_path))?;
+        let mut result: FreightProxy = FreightProxy::load(copy.path_str()?)?;
+        result.inner.get_mut().verified = Some(copy);
+        return Ok(result);
+    }
+
+    /// Function, used to build a [`FreightProxy`] object from a
+    /// library path, only if the plugin was built with exactly the
+    /// same compiler version and layout of the core structures as
+    /// the program using it, and never through the C ABI
+    ///
+    /// # Safety
+    ///
+    /// See [`FreightProxy::load`]
+    pub unsafe fn load_strict (
+        lib_path: &str,
+    ) -> Result<FreightProxy, Error> {
+
+        // Import the library
+        match libloading::Library::new(lib_path) {
+            Ok(library) =>
+                FreightProxy::load_native(std::sync::Arc::new(library)),
+            Err(lib_err) => Err(LoadingError (lib_err)),
+        }
+    }
+
+    /// Build a [`FreightProxy`] from the [`FreightDeclaration`] of
+    /// the library provided, checking the versions
+    unsafe fn load_native (
+        lib: std::sync::Arc<libloading::Library>,
+    ) -> Result<FreightProxy, Error> {
+
+        // The declaration may only be read if its layout matches
+        check_library_layout(&lib)?;
+
         // Get the plugin declaration structure from this lib
         let declaration: FreightDeclaration;
         match lib.get::<*mut FreightDeclaration>(
@@ -133,21 +340,138 @@ impl FreightProxy {
 
         let mut result: FreightProxy =
             FreightProxy::load_from_declaration(&declaration)?;
-        result.lib = Some(lib);
+        result.inner.get_mut().lib = Some(lib);
 
         // Return the result
         Ok(result)
     }
 
+    /// Build a [`FreightProxy`] from the [`AbiFreightDeclaration`]
+    /// of a plugin, calling its freight through the C ABI, whatever
+    /// compiler version and Dusk API version it was built with
+    ///
+    /// # Safety
+    ///
+    /// The declaration has to be exported by [`export_freight!`]. If
+    /// it is located in a library, that library has to be provided,
+    /// so it is not unloaded while still in use
+    pub unsafe fn load_from_abi_declaration (
+        declaration: &AbiFreightDeclaration,
+        lib: Option<std::sync::Arc<libloading::Library>>,
+    ) -> Result<FreightProxy, Error> {
+
+        let description: FreightDescription =
+            FreightDescription::from_abi(declaration)?;
+        let channel: AbiChannel =
+            AbiChannel::new((declaration.create)(), lib.clone());
+
+        let mut result: FreightProxy = FreightProxy::new(
+            description.name,
+            description.freight_version,
+            description.backwards_compat_version,
+            Box::new(RemoteFreight::new(Box::new(channel))));
+        result.inner.get_mut().lib = lib;
+        return Ok(result);
+    }
+
+    /// Build a [`FreightProxy`] from the latest version of a plugin,
+    /// statically linked into the program (see [`STATIC_FREIGHTS`])
+    ///
+    /// No library is loaded, so unlike [`FreightProxy::load`] this
+    /// function is safe
+    pub fn load_static (
+        name: &str,
+    ) -> Result<FreightProxy, Error> {
+
+        match find_static_declaration(name, None) {
+            Some(declaration) =>
+                FreightProxy::load_from_declaration(&declaration),
+            None => Err(ImportError (
+                    format!(
+                        "No plugin named {} is linked statically",
+                        name,
+                    ))),
+        }
+    }
+
+    /// Start the command provided, which has to serve a freight with
+    /// [`serve_pipe`] or [`serve_library`], and build a
+    /// [`FreightProxy`], that calls that freight in the child process
+    /// (see [`PipeChannel`])
+    ///
+    /// Unlike [`FreightProxy::load`] this function is safe, as
+    /// nothing the plugin does can affect the memory of this process.
+    /// The child process is killed if it does not reply to a call,
+    /// including the first one, describing its freight, within the
+    /// timeout provided, [`None`] meaning it is waited for as long as
+    /// it takes
+    ///
+    /// # Example
+    ///
+    /// ``` rust, ignore
+    /// let mut my_f_proxy: FreightProxy = FreightProxy::spawn(
+    ///     std::process::Command::new("dusk-runner")
+    ///         .arg("/bin/libtest_plug.so"),
+    ///     Some(DEFAULT_PROCESS_TIMEOUT)).expect("fail");
+    /// println!("{}, {}", my_f_proxy.name, my_f_proxy.version);
+    /// ```
+    pub fn spawn (
+        command: &mut std::process::Command,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<FreightProxy, Error> {
+
+        let channel: PipeChannel = PipeChannel::spawn(command, timeout)?;
+        let description: FreightDescription = channel.description().clone();
+        return Ok(FreightProxy::new(
+                description.name,
+                description.freight_version,
+                description.backwards_compat_version,
+                Box::new(RemoteFreight::new(Box::new(channel)))));
+    }
+
+    /// Build a [`FreightProxy`] around a [`Freight`] implementor,
+    /// that was not imported from a library, such as a
+    /// [`RemoteFreight`]
+    pub fn new (
+        name: String,
+        version: Version,
+        backwards_compat_version: Version,
+        freight: Box<dyn Freight>,
+    ) -> FreightProxy {
+
+        FreightProxy {
+            inner: std::cell::RefCell::new(FreightCache::new(freight)),
+            name: name,
+            version: version,
+            backwards_compat_version: backwards_compat_version,
+        }
+    }
+
+    /// Borrow the cache of the freight, failing if it is borrowed
+    /// already, which only happens if the proxy is called from inside
+    /// of a call to the same proxy
+    fn borrow_cache (
+        self: &Self,
+    ) -> Result<std::cell::RefMut<'_, FreightCache>, Error> {
+
+        match self.inner.try_borrow_mut() {
+            Ok(cache) => Ok(cache),
+            Err(_) => Err(RuntimeError(
+                    format!(
+                        "Plugin {} {} called from inside of its own call",
+                        self.name,
+                        self.version,
+                    ))),
+        }
+    }
+
     /// # Warning
-    /// This is an internal function. This is reserved for
-    /// future use in dawn builder and must never be used
-    /// for any purpose other than creating the program that
-    /// would link together the final program in case of
-    /// building for systems that do not allow for runtime
-    /// dynamic library loading. In cases that do not
-    /// require static linking use the [`FreightProxy::load`]
-    /// function instead.
+    /// This is an internal function. It is used by
+    /// [`FreightProxy::load_static`] for the plugins linked
+    /// into the program statically, in case of building for
+    /// systems that do not allow for runtime dynamic library
+    /// loading. In cases that do not require static linking
+    /// use the [`FreightProxy::load`] function instead.
     ///
     /// # Unsafe, Use at your own risk
     /// You may
@@ -176,20 +500,7 @@ impl FreightProxy {
         declaration: &FreightDeclaration,
     ) -> Result<FreightProxy, Error> {
 
-        // Check if the compiler and api versions match
-        // If not -- immediately return an error
-        if declaration.rustc_version != RUSTC_VERSION {
-            return Err(ImportError (
-                    "Compiler version mismatch".to_string()
-            ));
-        }
-
-        if declaration.api_version != API_VERSION {
-            return Err(ImportError (
-                    "Dusk API version mismatch".to_string()
-            ));
-        }
-
+        check_declaration(declaration)?;
         return FreightProxy::load_from_declaration_no_vcheck(&declaration);
     }
 
@@ -207,11 +518,234 @@ impl FreightProxy {
         // Make a new FreightProxy with all values that are
         // already available
         let mut result: FreightProxy = FreightProxy {
-            freight: Box::new(EmptyFreight{}),
-            lib: None,
+            inner: std::cell::RefCell::new(
+                FreightCache::new(Box::new(EmptyFreight{}))),
             name: declaration.name,
-            version: declaration.freight_version,
-            backwards_compat_version: declaration.backwards_compat_version,
+            version: declaration.freight_version.clone(),
+            backwards_compat_version:
+                declaration.backwards_compat_version.clone(),
+        };
+
+        // Call the function, imported in the plugin declaration
+        // and pass the FreightProxy to it as an argument
+        // so it sets the internal freight variable to a
+        // correct value
+        (declaration.register)(&mut result);
+
+        return Ok(result);
+    }
+
+    /// Hand the state of this plugin over to another instance of it,
+    /// using [`Freight::export_state`] on this proxy and
+    /// [`Freight::import_state`] on the new one
+    pub fn hand_over_state (
+        self: &Self,
+        new_proxy: &mut FreightProxy,
+    ) -> Result<(), Error> {
+
+        let state: Option<Vec<u8>> =
+            self.inner.borrow_mut().export_state()?;
+        match state {
+            Some(state) => new_proxy.import_state(state),
+            None => Ok(()),
+        }
+    }
+
+    /// Get the requests, that were provided to the plugin through
+    /// [`Freight::interplug_provide`] since it was last initialized
+    pub fn provided_requests (self: &Self) -> Vec<InterplugRequest> {
+        self.inner.borrow().provided_requests()
+    }
+
+    /// Get the requests, that were denied to the plugin through
+    /// [`Freight::interplug_deny`] since it was last initialized
+    pub fn denied_requests (self: &Self) -> Vec<InterplugRequest> {
+        self.inner.borrow().denied_requests()
+    }
+
+    /// Get the limitations, that did not fit the schema of the plugin
+    /// (see [`Freight::get_limitation_schema`]), the last time it was
+    /// initialized or had its limitations updated, along with the
+    /// reason each of them was rejected
+    pub fn get_rejected_limitations (
+        self: &Self,
+    ) -> Vec<RejectedLimitation> {
+
+        self.inner.borrow().rejected_limitations()
+    }
+
+    /// Get every function of the plugin (see
+    /// [`Freight::get_function_list`]) along with whether it can be
+    /// used with the requests, provided and denied so far, and which
+    /// of its dependencies are missing (see [`Function::availability`])
+    pub fn get_function_availability (
+        self: &Self,
+    ) -> Result<Vec<FunctionAvailability>, Error> {
+
+        self.inner.borrow_mut().get_function_availability()
+    }
+
+    /// Get only the functions of the plugin, that can be used with
+    /// the requests, provided so far
+    pub fn get_available_functions (
+        self: &Self,
+    ) -> Result<Vec<Function>, Error> {
+
+        return Ok(self.get_function_availability()?.into_iter()
+            .filter(|status| status.availability == Availability::Available)
+            .map(|status| status.function)
+            .collect());
+    }
+
+    /// Drop the imported freight along with all the lists that were
+    /// already gotten from it, so the proxy can not be used anymore
+    ///
+    /// Every function called on the proxy after that acts as if the
+    /// plugin was empty. The library itself is only unloaded once
+    /// nothing, gotten from the proxy before, is used anymore
+    pub fn invalidate (self: &Self) {
+        *self.inner.borrow_mut() =
+            FreightCache::new(Box::new(EmptyFreight{}));
+    }
+}
+
+/// A weak handle to a [`FreightProxy`], shared through
+/// [`std::rc::Rc`], that does not keep the proxy from being dropped
+///
+/// It is provided through [`Freight::interplug_provide_weak`] instead
+/// of the proxy itself, when the requested plugin depends on the
+/// requesting one, so that the two plugins do not keep each other
+/// from being dropped. The handle is upgraded every time the plugin
+/// is used, which fails once the plugin is unloaded
+///
+/// # Example
+///
+/// ```
+/// use dusk_api::*;
+///
+/// let proxy: std::rc::Rc<FreightProxy> = std::rc::Rc::new(FreightProxy::new(
+///     "test".to_string(), Version::default(), Version::default(),
+///     Box::new(EmptyFreight{})));
+/// let handle: WeakFreightProxy = WeakFreightProxy::new(&proxy);
+/// assert!(handle.upgrade().is_ok());
+/// drop(proxy);
+/// assert!(handle.upgrade().is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct WeakFreightProxy {
+
+    /// The proxy itself
+    proxy: std::rc::Weak<FreightProxy>,
+
+    /// Name of the plugin
+    pub name: String,
+
+    /// Version of the plugin
+    pub version: Version,
+}
+
+impl WeakFreightProxy {
+
+    /// Create a weak handle to the proxy provided
+    pub fn new (
+        proxy: &std::rc::Rc<FreightProxy>,
+    ) -> WeakFreightProxy {
+
+        WeakFreightProxy {
+            proxy: std::rc::Rc::downgrade(proxy),
+            name: proxy.name.clone(),
+            version: proxy.version.clone(),
+        }
+    }
+
+    /// Get the proxy, if the plugin was not unloaded yet
+    pub fn upgrade (
+        self: &Self,
+    ) -> Result<std::rc::Rc<FreightProxy>, Error> {
+
+        match self.proxy.upgrade() {
+            Some(proxy) => Ok(proxy),
+            None => Err(RuntimeError(
+                    format!(
+                        "Plugin {} {} was unloaded",
+                        self.name,
+                        self.version,
+                    ))),
+        }
+    }
+
+    /// Check if the plugin was not unloaded yet
+    pub fn is_loaded (self: &Self) -> bool {
+        self.proxy.strong_count() > 0
+    }
+}
+
+/// Check if the layout of the core structures (see
+/// [`LAYOUT_FINGERPRINT`]), the plugin library provided was built
+/// with, matches the one of this program, so its
+/// [`FreightDeclaration`] can be read at all
+///
+/// The fingerprint is exported on its own by [`export_freight!`], as
+/// the one in the declaration can not be read before it is checked.
+/// Libraries, that do not export it, are rejected
+///
+/// # Safety
+///
+/// The library has to be a plugin, exported by [`export_freight!`]
+pub(crate) unsafe fn check_library_layout (
+    lib: &libloading::Library,
+) -> Result<(), Error> {
+
+    let fingerprint: u64 = match lib.get::<*const u64>(
+        b"freight_layout_fingerprint\0") {
+
+        Ok(fingerprint) => fingerprint.read(),
+        Err(_) => return Err(ImportError (
+                "Plugin does not export its Dusk API layout fingerprint"
+                .to_string()
+        )),
+    };
+    if fingerprint != LAYOUT_FINGERPRINT {
+        return Err(ImportError (
+                "Dusk API layout mismatch".to_string()
+        ));
+    }
+    return Ok(());
+}
+
+/// Check if the compiler version and the layout of the core
+/// structures (see [`LAYOUT_FINGERPRINT`]), a plugin was built with,
+/// match the ones of this program, so its freight can be used
+/// directly
+pub(crate) fn check_declaration (
+    declaration: &FreightDeclaration,
+) -> Result<(), Error> {
+
+    // If the versions do not match -- immediately return an error
+    if declaration.rustc_version != RUSTC_VERSION {
+        return Err(ImportError (
+                "Compiler version mismatch".to_string()
+        ));
+    }
+
+    if declaration.layout_fingerprint != LAYOUT_FINGERPRINT {
+        return Err(ImportError (
+                format!(
+                    "Dusk API layout mismatch: plugin was built with \
+                    Dusk API {}",
+                    declaration.api_version,
+                )));
+    }
+
+    return Ok(());
+}
+
+impl FreightCache {
+
+    /// Create a cache for the freight provided, with nothing in it yet
+    pub(crate) fn new (freight: Box<dyn Freight>) -> FreightCache {
+        FreightCache {
+            freight: freight,
             callables: None,
             functions: None,
             types: None,
@@ -222,15 +756,107 @@ impl FreightProxy {
             types_by_native_id: None,
             trait_definitions_by_name: None,
             modules_by_name: None,
-        };
+            provided: Vec::new(),
+            denied: Vec::new(),
+            limitation_schema: None,
+            rejected_limitations: Vec::new(),
+            applied_limitations: AppliedLimitations::new(),
+            lib: None,
+            verified: None,
+        }
+    }
 
-        // Call the function, imported in the plugin declaration
-        // and pass the FreightProxy to it as an argument
-        // so it sets the internal freight variable to a
-        // correct value
-        (declaration.register)(&mut result);
+    /// Remember that the request was answered, either by providing
+    /// it or by denying it
+    fn answer (
+        self: &mut Self,
+        request: &InterplugRequest,
+        provided: bool,
+    ) {
 
-        return Ok(result);
+        self.provided.retain(|answered| answered != request);
+        self.denied.retain(|answered| answered != request);
+        match provided {
+            true => self.provided.push(request.clone()),
+            false => self.denied.push(request.clone()),
+        }
+    }
+
+    /// Get the requests, that were provided to the freight
+    pub(crate) fn provided_requests (self: &Self) -> Vec<InterplugRequest> {
+        self.provided.clone()
+    }
+
+    /// Get the requests, that were denied to the freight
+    pub(crate) fn denied_requests (self: &Self) -> Vec<InterplugRequest> {
+        self.denied.clone()
+    }
+
+    /// Get the limitations, that did not fit the schema of the
+    /// freight, the last time they were passed to it
+    pub(crate) fn rejected_limitations (
+        self: &Self,
+    ) -> Vec<RejectedLimitation> {
+
+        self.rejected_limitations.clone()
+    }
+
+    /// Check the limitations provided against the schema of the
+    /// freight, if it declares one, remembering the rejected ones and
+    /// returning the rest
+    fn filter_limitations (
+        self: &mut Self,
+        limitations: &[Limitation],
+    ) -> Vec<Limitation> {
+
+        let (accepted, rejected): (Vec<Limitation>, Vec<RejectedLimitation>) =
+            match self.get_limitation_schema() {
+                Some(schema) => schema.filter(limitations),
+                None => (limitations.to_vec(), Vec::new()),
+            };
+        for limitation in limitations {
+            let status: LimitationStatus = match rejected.iter()
+                .find(|rejected| &rejected.limitation == limitation) {
+
+                Some(rejected) =>
+                    LimitationStatus::Rejected { reason: rejected.reason },
+                None => LimitationStatus::Applied,
+            };
+            self.applied_limitations.record(limitation, status);
+        }
+        self.rejected_limitations = rejected;
+        return accepted;
+    }
+
+    /// Get every function of the freight along with whether it can
+    /// be used with the requests, answered so far
+    pub(crate) fn get_function_availability (
+        self: &mut Self,
+    ) -> Result<Vec<FunctionAvailability>, Error> {
+
+        let functions: Vec<Function> = self.get_function_list()?;
+        return Ok(functions.into_iter()
+            .filter(|function| !function.name.is_empty())
+            .map(|function| FunctionAvailability {
+                availability: function.availability(
+                    &self.provided,
+                    &self.denied),
+                function: function,
+            })
+            .collect());
+    }
+
+    /// Pin the value provided to the library the freight was loaded
+    /// from, if there is one
+    fn pin<T> (
+        self: &Self,
+        mut value: T,
+    ) -> T where T: PinLibrary {
+
+        if let Some(lib) = &self.lib {
+            value.pin_library(lib);
+        }
+        return value;
     }
 }
 
@@ -240,7 +866,8 @@ macro_rules! remember_or_create {
         match &$self.$memory {
             Some(list) => return Ok(list.clone()),
             None => {
-                $self.$memory = Some($self.freight.$get_list()?);
+                let list = $self.freight.$get_list()?;
+                $self.$memory = Some($self.pin(list));
                 return Ok($self.$memory.as_ref().unwrap().clone());
             },
         }
@@ -265,7 +892,8 @@ macro_rules! find_by_id {
                         )));
             },
             None => {
-                $self.$memory = Some($self.freight.$get_list()?);
+                let list = $self.freight.$get_list()?;
+                $self.$memory = Some($self.pin(list));
                 $self.$self_fn($id)
             },
         }
@@ -301,64 +929,162 @@ macro_rules! find_by_name {
     }
 }
 
-// Implementation of trait Freight for the proxy structure, so we
-// can call exact same functions from it
-impl Freight for FreightProxy {
+// Implementation of trait Freight for the cache structure, so that
+// every list is only built once
+impl Freight for FreightCache {
 
-    // Proxy function, that calls the internal freights init function
-    // and returns its plugin dependencies
     fn init (
         self: &mut Self,
         limitations: &Option<Vec<Limitation>>,
     ) -> Vec<InterplugRequest> {
 
-        self.freight.init(limitations)
+        self.provided.clear();
+        self.denied.clear();
+        self.applied_limitations.clear();
+        let limitations: Option<Vec<Limitation>> = match limitations {
+            Some(limitations) => Some(self.filter_limitations(limitations)),
+            None => {
+                self.rejected_limitations.clear();
+                None
+            },
+        };
+        self.freight.init(&limitations)
     }
 
-    // Proxy function that takes the list of new system limitations
-    // and passes it to the plugin
     fn update_limitations (
         self: &mut Self,
         limitations: &Vec<Limitation>,
     ) {
 
-        self.freight.update_limitations(limitations)
+        let limitations: Vec<Limitation> =
+            self.filter_limitations(limitations);
+        self.freight.update_limitations(&limitations)
+    }
+
+    fn get_limitation_schema (
+        self: &mut Self,
+    ) -> Option<LimitationSchema> {
+
+        if self.limitation_schema.is_none() {
+            self.limitation_schema =
+                Some(self.freight.get_limitation_schema());
+        }
+        return self.limitation_schema.clone().unwrap_or_default();
+    }
+
+    fn get_effective_limitations (
+        self: &mut Self,
+    ) -> Option<Vec<EffectiveSetting>> {
+
+        // Only the plugin knows what it applied, so nothing is
+        // reported for the plugins, that do not keep track of it
+        let mut settings: Vec<EffectiveSetting> =
+            self.freight.get_effective_limitations()?;
+        for setting in &mut settings {
+            for outcome in self.applied_limitations.get(&setting.name) {
+                let rejected: bool = matches!(outcome.status,
+                    LimitationStatus::Rejected { .. });
+                if rejected && !setting.limitations.iter()
+                    .any(|other| other.limitation == outcome.limitation) {
+
+                    setting.limitations.push(outcome);
+                }
+            }
+        }
+        return Some(settings);
     }
 
-    // Proxy function for replying to an interplugin dependency
-    // request by providing the requested plugin
     fn interplug_provide (
         self: &mut Self,
         request: InterplugRequest,
         freight_proxy: std::rc::Rc<FreightProxy>,
     ) {
 
+        self.answer(&request, true);
         self.freight.interplug_provide(request, freight_proxy);
     }
 
-    // Proxy function for replying to an interplugin dependency
-    // request by informing it of request denial
+    fn interplug_provide_sync (
+        self: &mut Self,
+        request: InterplugRequest,
+        freight_proxy: std::sync::Arc<SyncFreightProxy>,
+    ) {
+
+        self.answer(&request, true);
+        self.freight.interplug_provide_sync(request, freight_proxy);
+    }
+
+    fn interplug_provide_weak (
+        self: &mut Self,
+        request: InterplugRequest,
+        freight_proxy: WeakFreightProxy,
+    ) {
+
+        self.answer(&request, true);
+        self.freight.interplug_provide_weak(request, freight_proxy);
+    }
+
     fn interplug_deny (
         self: &mut Self,
         request: InterplugRequest,
     ) {
 
+        self.answer(&request, false);
         self.freight.interplug_deny(request);
     }
 
-    fn get_root_modules (self: &mut Self) -> Vec<Module> {
-        self.freight.get_root_modules()
-    }
+    fn interplug_replace (
+        self: &mut Self,
+        request: InterplugRequest,
+        freight_proxy: std::rc::Rc<FreightProxy>,
+    ) {
 
-    fn get_operator_list (self: &mut Self) -> Vec<Function> {
-        self.freight.get_operator_list()
+        self.freight.interplug_replace(request, freight_proxy);
     }
 
-    fn get_callable_list (
+    fn interplug_withdraw (
         self: &mut Self,
-    ) -> Result<Vec<Box<dyn DuskCallable>>, Error> {
+        request: InterplugRequest,
+    ) {
 
-        match &self.callables {
+        self.provided.retain(|answered| answered != &request);
+        self.freight.interplug_withdraw(request);
+    }
+
+    fn shutdown (self: &mut Self) {
+        self.freight.shutdown()
+    }
+
+    fn export_state (
+        self: &mut Self,
+    ) -> Result<Option<Vec<u8>>, Error> {
+
+        self.freight.export_state()
+    }
+
+    fn import_state (
+        self: &mut Self,
+        state: Vec<u8>,
+    ) -> Result<(), Error> {
+
+        self.freight.import_state(state)
+    }
+
+    fn get_root_modules (self: &mut Self) -> Vec<Module> {
+        let modules: Vec<Module> = self.freight.get_root_modules();
+        self.pin(modules)
+    }
+
+    fn get_operator_list (self: &mut Self) -> Vec<Function> {
+        let operators: Vec<Function> = self.freight.get_operator_list();
+        self.pin(operators)
+    }
+
+    fn get_callable_list (
+        self: &mut Self,
+    ) -> Result<Vec<Box<dyn DuskCallable>>, Error> {
+
+        match &self.callables {
             Some(list) => return Ok(list.clone()),
             None => {
                 let tmp_functions: Vec<Function> = self.get_function_list()?;
@@ -429,8 +1155,8 @@ impl Freight for FreightProxy {
             Some(hash_map) => {
                 match hash_map.get(&native_id) {
                     Some(id) => {
-                        let id_clone = id.clone();
-                        self.get_type_by_id(id_clone)
+                        let id: usize = *id;
+                        self.get_type_by_id(id)
                     },
                     None => Err(IndexError(
                             format!(
@@ -443,10 +1169,8 @@ impl Freight for FreightProxy {
             None => {
                 let mut hash_map: std::collections::HashMap<TypeId, usize> =
                     std::collections::HashMap::new();
-                let mut idx: usize = 0;
-                for item in self.get_type_list()? {
-                    hash_map.insert(item.native_id.clone(), idx);
-                    idx += 1;
+                for (idx, item) in self.get_type_list()?.into_iter().enumerate() {
+                    hash_map.insert(item.native_id, idx);
                 }
                 self.types_by_native_id = Some(hash_map);
                 self.get_type_by_native_id(native_id)
@@ -515,6 +1239,300 @@ impl Freight for FreightProxy {
     }
 }
 
+macro_rules! proxy_freight {
+    ($proxy: ty) => {
+        impl Freight for $proxy {
+
+            // Proxy function, that calls the internal freights init
+            // function and returns its plugin dependencies
+            fn init (
+                self: &mut Self,
+                limitations: &Option<Vec<Limitation>>,
+            ) -> Vec<InterplugRequest> {
+
+                match self.borrow_cache() {
+                    Ok(mut cache) => cache.init(limitations),
+                    Err(_) => Vec::new(),
+                }
+            }
+
+            // Proxy function that takes the list of new system
+            // limitations and passes it to the plugin
+            fn update_limitations (
+                self: &mut Self,
+                limitations: &Vec<Limitation>,
+            ) {
+
+                if let Ok(mut cache) = self.borrow_cache() {
+                    cache.update_limitations(limitations);
+                }
+            }
+
+            fn get_limitation_schema (
+                self: &mut Self,
+            ) -> Option<LimitationSchema> {
+
+                match self.borrow_cache() {
+                    Ok(mut cache) => cache.get_limitation_schema(),
+                    Err(_) => None,
+                }
+            }
+
+            // Proxy function, that reports the values, the plugin
+            // actually uses for its settings
+            fn get_effective_limitations (
+                self: &mut Self,
+            ) -> Option<Vec<EffectiveSetting>> {
+
+                match self.borrow_cache() {
+                    Ok(mut cache) => cache.get_effective_limitations(),
+                    Err(_) => None,
+                }
+            }
+
+            // Proxy function for replying to an interplugin dependency
+            // request by providing the requested plugin
+            fn interplug_provide (
+                self: &mut Self,
+                request: InterplugRequest,
+                freight_proxy: std::rc::Rc<FreightProxy>,
+            ) {
+
+                if let Ok(mut cache) = self.borrow_cache() {
+                    cache.interplug_provide(request, freight_proxy);
+                }
+            }
+
+            // Proxy function for replying to an interplugin dependency
+            // request by providing a thread safe proxy
+            fn interplug_provide_sync (
+                self: &mut Self,
+                request: InterplugRequest,
+                freight_proxy: std::sync::Arc<SyncFreightProxy>,
+            ) {
+
+                if let Ok(mut cache) = self.borrow_cache() {
+                    cache.interplug_provide_sync(request, freight_proxy);
+                }
+            }
+
+            // Proxy function for replying to an interplugin dependency
+            // request by providing a weak handle
+            fn interplug_provide_weak (
+                self: &mut Self,
+                request: InterplugRequest,
+                freight_proxy: WeakFreightProxy,
+            ) {
+
+                if let Ok(mut cache) = self.borrow_cache() {
+                    cache.interplug_provide_weak(request, freight_proxy);
+                }
+            }
+
+            // Proxy function for replying to an interplugin dependency
+            // request by informing it of request denial
+            fn interplug_deny (
+                self: &mut Self,
+                request: InterplugRequest,
+            ) {
+
+                if let Ok(mut cache) = self.borrow_cache() {
+                    cache.interplug_deny(request);
+                }
+            }
+
+            // Proxy function for informing the plugin, that the
+            // plugin it was provided was replaced by a new build
+            fn interplug_replace (
+                self: &mut Self,
+                request: InterplugRequest,
+                freight_proxy: std::rc::Rc<FreightProxy>,
+            ) {
+
+                if let Ok(mut cache) = self.borrow_cache() {
+                    cache.interplug_replace(request, freight_proxy);
+                }
+            }
+
+            // Proxy function for informing the plugin, that the
+            // plugin it was provided is about to be unloaded
+            fn interplug_withdraw (
+                self: &mut Self,
+                request: InterplugRequest,
+            ) {
+
+                if let Ok(mut cache) = self.borrow_cache() {
+                    cache.interplug_withdraw(request);
+                }
+            }
+
+            fn shutdown (self: &mut Self) {
+                if let Ok(mut cache) = self.borrow_cache() {
+                    cache.shutdown();
+                }
+            }
+
+            fn export_state (
+                self: &mut Self,
+            ) -> Result<Option<Vec<u8>>, Error> {
+
+                self.borrow_cache()?.export_state()
+            }
+
+            fn import_state (
+                self: &mut Self,
+                state: Vec<u8>,
+            ) -> Result<(), Error> {
+
+                self.borrow_cache()?.import_state(state)
+            }
+
+            fn get_root_modules (self: &mut Self) -> Vec<Module> {
+                match self.borrow_cache() {
+                    Ok(mut cache) => cache.get_root_modules(),
+                    Err(_) => Vec::new(),
+                }
+            }
+
+            fn get_operator_list (self: &mut Self) -> Vec<Function> {
+                match self.borrow_cache() {
+                    Ok(mut cache) => cache.get_operator_list(),
+                    Err(_) => Vec::new(),
+                }
+            }
+
+            fn get_callable_list (
+                self: &mut Self,
+            ) -> Result<Vec<Box<dyn DuskCallable>>, Error> {
+
+                self.borrow_cache()?.get_callable_list()
+            }
+
+            fn get_callable_by_id (
+                self: &mut Self,
+                id: usize,
+            ) -> Result<Box<dyn DuskCallable>, Error> {
+
+                self.borrow_cache()?.get_callable_by_id(id)
+            }
+
+            fn get_function_list (
+                self: &mut Self,
+            ) -> Result<Vec<Function>, Error> {
+
+                self.borrow_cache()?.get_function_list()
+            }
+
+            fn get_function_by_id (
+                self: &mut Self,
+                id: usize,
+            ) -> Result<Function, Error> {
+
+                self.borrow_cache()?.get_function_by_id(id)
+            }
+
+            fn get_functions_by_name (
+                self: &mut Self,
+                name: &String,
+            ) -> Result<Vec<Function>, Error> {
+
+                self.borrow_cache()?.get_functions_by_name(name)
+            }
+
+            fn get_type_list (
+                self: &mut Self,
+            ) -> Result<Vec<Type>, Error> {
+
+                self.borrow_cache()?.get_type_list()
+            }
+
+            fn get_type_by_id (
+                self: &mut Self,
+                id: usize,
+            ) -> Result<Type, Error> {
+
+                self.borrow_cache()?.get_type_by_id(id)
+            }
+
+            fn get_type_by_native_id (
+                self: &mut Self,
+                native_id: TypeId,
+            ) -> Result<Type, Error> {
+
+                self.borrow_cache()?.get_type_by_native_id(native_id)
+            }
+
+            fn get_types_by_name (
+                self: &mut Self,
+                name: &String,
+            ) -> Result<Vec<Type>, Error> {
+
+                self.borrow_cache()?.get_types_by_name(name)
+            }
+
+            fn get_trait_definition_list (
+                self: &mut Self,
+            ) -> Result<Vec<TraitDefinition>, Error> {
+
+                self.borrow_cache()?.get_trait_definition_list()
+            }
+
+            fn get_trait_definition_by_id (
+                self: &mut Self,
+                id: usize,
+            ) -> Result<TraitDefinition, Error> {
+
+                self.borrow_cache()?.get_trait_definition_by_id(id)
+            }
+
+            fn get_trait_definitions_by_name (
+                self: &mut Self,
+                name: &String,
+            ) -> Result<Vec<TraitDefinition>, Error> {
+
+                self.borrow_cache()?.get_trait_definitions_by_name(name)
+            }
+
+            fn get_module_list (
+                self: &mut Self,
+            ) -> Result<Vec<Module>, Error> {
+
+                self.borrow_cache()?.get_module_list()
+            }
+
+            fn get_module_by_id (
+                self: &mut Self,
+                id: usize,
+            ) -> Result<Module, Error> {
+
+                self.borrow_cache()?.get_module_by_id(id)
+            }
+
+            fn get_modules_by_name (
+                self: &mut Self,
+                name: &String,
+            ) -> Result<Vec<Module>, Error> {
+
+                self.borrow_cache()?.get_modules_by_name(name)
+            }
+        }
+    }
+}
+
+// Implementation of trait Freight for the proxy structure, so we
+// can call exact same functions from it
+proxy_freight!(FreightProxy);
+
+// Implementation of trait Freight for a shared proxy, so that the
+// plugins provided through Freight::interplug_provide and the
+// plugins stored in a PluginHost could be used just as well
+//
+// Calling into a shared proxy from inside of a call to the same
+// proxy is not allowed. The functions, that return a result, fail
+// with an Error::RuntimeError, and as the rest of them can not
+// return an error, they act as if the plugin was empty
+proxy_freight!(std::rc::Rc<FreightProxy>);
+
 // Implementation of FreightRegistrar trait for the proxy
 // structure, so that we can call register function on it without
 // any third party structure
@@ -527,6 +1545,1904 @@ impl FreightRegistrar for FreightProxy {
         self: &mut Self,
         freight: Box<dyn Freight>,
     ) {
-        self.freight = freight;
+        self.inner.get_mut().freight = freight;
+    }
+}
+
+/// State of a plugin, stored inside of a [`PluginHost`]
+#[derive(Debug)]
+enum PluginState {
+
+    /// The plugin was loaded, but its init function was not called
+    /// yet
+    Loaded (FreightProxy),
+
+    /// The plugin init function was called, and the requests it
+    /// returned are being answered right now
+    Initializing,
+
+    /// The plugin is initialized, all of its requests are answered
+    /// and it can be provided to other plugins
+    Ready (std::rc::Rc<FreightProxy>),
+
+    /// One of the crucial requests of the plugin could not be
+    /// fulfilled, so the plugin can not be used
+    Failed (FreightProxy),
+
+    /// The plugin was shut down and dropped, so its library may
+    /// have been unloaded
+    Unloaded,
+}
+
+/// Enum, that represents the policy of a [`PluginHost`] on the
+/// pre-release plugins, such as nightly builds, being used to
+/// fulfill the requests of other plugins
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum PreReleasePolicy {
+
+    /// Pre-release plugins are never used, even if requested
+    Never,
+
+    /// Pre-release plugins are only used if the request allows them
+    /// (see [`VersionReq::pre_releases`])
+    #[default]
+    Requested,
+
+    /// Pre-release plugins are used as if they were stable, although
+    /// stable versions are still preferred to them
+    Always,
+}
+
+impl PreReleasePolicy {
+
+    /// Apply the policy to the reasons, a plugin of the version
+    /// provided was rejected with (see
+    /// [`InterplugRequest::check_provider`]), either adding or
+    /// removing the [`Rejection::PreRelease`] reason
+    pub fn apply (
+        self: &Self,
+        version: &Version,
+        reasons: &mut Vec<Rejection>,
+    ) {
+
+        let rejected: bool = reasons.iter()
+            .any(|reason| matches!(reason, Rejection::PreRelease { .. }));
+        match self {
+            PreReleasePolicy::Never if version.is_pre_release() && !rejected =>
+                reasons.push(Rejection::PreRelease {
+                    version: version.clone(),
+                }),
+            PreReleasePolicy::Always => reasons.retain(|reason|
+                !matches!(reason, Rejection::PreRelease { .. })),
+            _ => (),
+        }
+    }
+}
+
+/// A plugin, stored inside of a [`PluginHost`], along with the
+/// requests it made when it was initialized
+#[derive(Debug)]
+struct HostedPlugin {
+
+    /// The plugin name
+    name: String,
+
+    /// The plugin version
+    version: Version,
+
+    /// The earliest version, the plugin is compatible with
+    backwards_compat_version: Version,
+
+    /// The plugin itself
+    state: PluginState,
+
+    /// Requests, returned by the plugin init function
+    requests: Vec<InterplugRequest>,
+
+    /// Limitations of the host, that did not fit the schema of the
+    /// plugin when it was initialized
+    rejected_limitations: Vec<RejectedLimitation>,
+
+    /// IDs of the plugins this plugin was provided to, along with
+    /// the requests it was provided for
+    provided_to: Vec<(usize, InterplugRequest)>,
+
+    /// IDs of the plugins a weak handle to this plugin was provided
+    /// to, along with the requests it was provided for
+    weakly_provided_to: Vec<(usize, InterplugRequest)>,
+
+    /// IDs of the plugins, this plugin depends on, that requested it
+    /// while it was being initialized, along with their requests,
+    /// which are answered once it is initialized
+    weak_pending: Vec<(usize, InterplugRequest)>,
+}
+
+/// A structure, that loads and stores any amount of plugins,
+/// initializes them and answers the interplugin requests they make
+/// by providing other plugins, loaded into the same host
+///
+/// Plugins are stored in the order they were added to the host, so
+/// the position of the plugin in that order is used as the plugin ID
+/// and is returned by both [`PluginHost::load`] and
+/// [`PluginHost::add`].
+///
+/// When [`PluginHost::init_all`] is called, every plugin that was
+/// not initialized yet gets initialized, and for each request it
+/// returns, the requested plugins are initialized first, so that
+/// they are always provided with all of their own dependencies
+/// already in place. Once the plugin is initialized it is stored as
+/// an [`std::rc::Rc`] copy, which is then both provided to the
+/// plugins that request it and returned by [`PluginHost::get`] and
+/// similar functions. Trait requests are fulfilled by providing a
+/// plugin, a type of which implements the trait, initializing it
+/// first, if needed (see [`PluginHost::find_trait_implementors`]).
+///
+/// When the host is dropped, every plugin is shut down in reverse
+/// dependency order (see [`PluginHost::shutdown_all`]), each one
+/// only after the plugins it was provided to, which are told to drop
+/// it through [`Freight::interplug_withdraw`].
+///
+/// # Example
+///
+/// ``` rust, ignore
+/// let mut host: PluginHost = PluginHost::new();
+/// unsafe {
+///     host.load("/bin/libtest_plug.so").expect("fail");
+///     host.load("/bin/libother_plug.so").expect("fail");
+/// }
+/// host.init_all().expect("fail");
+///
+/// let mut my_f_proxy: std::rc::Rc<FreightProxy> =
+///     host.get_latest(&"test".to_string()).expect("not loaded");
+/// let fnlist: Vec<Function> = my_f_proxy.get_function_list().unwrap();
+/// for func in fnlist {
+///     println!("{}, {}", func.name, func.fn_id);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct PluginHost {
+
+    /// System limitations, passed to each plugin when it is
+    /// initialized. The ones, that do not fit the schema of a plugin,
+    /// are reported by [`PluginHost::all_rejected_limitations`]
+    pub limitations: Option<Vec<Limitation>>,
+
+    /// Verifier, every library is checked with before it is loaded
+    /// by [`PluginHost::load`] or [`PluginHost::reload`], if set
+    pub verifier: Option<LibraryVerifier>,
+
+    /// Whether pre-release plugins may be used to fulfill the
+    /// requests of other plugins
+    pub pre_releases: PreReleasePolicy,
+
+    /// Time the plugins, started in child processes by
+    /// [`PluginHost::spawn`], are given to reply to each call,
+    /// [`DEFAULT_PROCESS_TIMEOUT`] unless set otherwise
+    pub process_timeout: Option<std::time::Duration>,
+
+    plugins: Vec<HostedPlugin>,
+
+    plugins_by_name: std::collections::HashMap<String, Vec<usize>>,
+}
+
+impl Default for PluginHost {
+    fn default () -> PluginHost {
+        PluginHost::new()
+    }
+}
+
+impl Drop for PluginHost {
+    fn drop (self: &mut Self) {
+        self.shutdown_all();
+    }
+}
+
+impl PluginHost {
+
+    /// Create an empty plugin host, with no limitations set
+    pub fn new () -> PluginHost {
+        PluginHost {
+            limitations: None,
+            verifier: None,
+            pre_releases: PreReleasePolicy::default(),
+            process_timeout: Some(DEFAULT_PROCESS_TIMEOUT),
+            plugins: Vec::new(),
+            plugins_by_name: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Load the plugin from a library path (see
+    /// [`FreightProxy::load`]) and add it to the host, returning
+    /// the plugin ID
+    ///
+    /// # Safety
+    ///
+    /// See [`FreightProxy::load`]
+    pub unsafe fn load (
+        self: &mut Self,
+        lib_path: &str,
+    ) -> Result<usize, Error> {
+
+        let proxy: FreightProxy = self.load_proxy(lib_path)?;
+        return Ok(self.add(proxy));
+    }
+
+    /// Load the library, checking it with the verifier first, if
+    /// there is one
+    unsafe fn load_proxy (
+        self: &Self,
+        lib_path: &str,
+    ) -> Result<FreightProxy, Error> {
+
+        match &self.verifier {
+            Some(verifier) => FreightProxy::load_verified(lib_path, verifier),
+            None => FreightProxy::load(lib_path),
+        }
+    }
+
+    /// Load the latest version of a statically linked plugin (see
+    /// [`FreightProxy::load_static`]) and add it to the host,
+    /// returning the plugin ID
+    pub fn load_static (
+        self: &mut Self,
+        name: &str,
+    ) -> Result<usize, Error> {
+
+        let proxy: FreightProxy = FreightProxy::load_static(name)?;
+        return Ok(self.add(proxy));
+    }
+
+    /// Start the plugin in a child process (see
+    /// [`FreightProxy::spawn`]) with the
+    /// [`PluginHost::process_timeout`] and add it to the host,
+    /// returning the plugin ID
+    pub fn spawn (
+        self: &mut Self,
+        command: &mut std::process::Command,
+    ) -> Result<usize, Error> {
+
+        let proxy: FreightProxy =
+            FreightProxy::spawn(command, self.process_timeout)?;
+        return Ok(self.add(proxy));
+    }
+
+    /// Add an already loaded plugin to the host, returning the
+    /// plugin ID
+    pub fn add (
+        self: &mut Self,
+        proxy: FreightProxy,
+    ) -> usize {
+
+        let id: usize = self.plugins.len();
+        self.plugins_by_name.entry(proxy.name.clone())
+            .or_default()
+            .push(id);
+        self.plugins.push(HostedPlugin {
+            name: proxy.name.clone(),
+            version: proxy.version.clone(),
+            backwards_compat_version: proxy.backwards_compat_version.clone(),
+            state: PluginState::Loaded(proxy),
+            requests: Vec::new(),
+            rejected_limitations: Vec::new(),
+            provided_to: Vec::new(),
+            weakly_provided_to: Vec::new(),
+            weak_pending: Vec::new(),
+        });
+        return id;
+    }
+
+    /// Get the amount of plugins, added to the host
+    pub fn len (self: &Self) -> usize {
+        self.plugins.len()
+    }
+
+    /// Check if no plugins were added to the host
+    pub fn is_empty (self: &Self) -> bool {
+        self.plugins.is_empty()
+    }
+
+    /// Get IDs of all the plugins with the name provided, in the
+    /// order they were added to the host
+    pub fn get_ids_by_name (
+        self: &Self,
+        name: &String,
+    ) -> Vec<usize> {
+
+        match self.plugins_by_name.get(name) {
+            Some(ids) => ids.clone(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Get the ID of the plugin with the exact name and version
+    /// provided
+    pub fn get_id (
+        self: &Self,
+        name: &String,
+        version: &Version,
+    ) -> Option<usize> {
+
+        self.get_ids_by_name(name).into_iter()
+            .find(|id| self.plugins[*id].version.eq(version))
+    }
+
+    /// Get the initialized plugin by its ID
+    ///
+    /// Returns [`None`] if there is no such plugin, or if it was not
+    /// initialized successfully yet
+    pub fn get_by_id (
+        self: &Self,
+        id: usize,
+    ) -> Option<std::rc::Rc<FreightProxy>> {
+
+        if id >= self.plugins.len() {
+            return None;
+        }
+        match &self.plugins[id].state {
+            PluginState::Ready(proxy) => Some(proxy.clone()),
+            _ => None,
+        }
+    }
+
+    /// Get the initialized plugin with the exact name and version
+    /// provided
+    pub fn get (
+        self: &Self,
+        name: &String,
+        version: &Version,
+    ) -> Option<std::rc::Rc<FreightProxy>> {
+
+        match self.get_id(name, version) {
+            Some(id) => self.get_by_id(id),
+            None => None,
+        }
+    }
+
+    /// Get the initialized plugin with the name provided and the
+    /// greatest version of all loaded
+    pub fn get_latest (
+        self: &Self,
+        name: &String,
+    ) -> Option<std::rc::Rc<FreightProxy>> {
+
+        let mut best: Option<usize> = None;
+        for id in self.get_ids_by_name(name) {
+            if self.get_by_id(id).is_none() {
+                continue;
+            }
+            best = match best {
+                Some(best_id)
+                    if !self.plugins[id].version.is_preferred_to(
+                        &self.plugins[best_id].version) => Some(best_id),
+                _ => Some(id),
+            };
+        }
+        match best {
+            Some(id) => self.get_by_id(id),
+            None => None,
+        }
+    }
+
+    /// Get the requests, the plugin returned from its init function
+    pub fn get_requests (
+        self: &Self,
+        id: usize,
+    ) -> Result<Vec<InterplugRequest>, Error> {
+
+        if id >= self.plugins.len() {
+            return Err(IndexError(
+                    format!(
+                        "Plugin with index {} does not exist",
+                        id,
+                    )));
+        }
+        return Ok(self.plugins[id].requests.clone());
+    }
+
+    /// Get the limitations of the host, that did not fit the schema
+    /// of the plugin (see [`Freight::get_limitation_schema`]) when it
+    /// was last initialized, and so were not passed to it
+    pub fn get_rejected_limitations (
+        self: &Self,
+        id: usize,
+    ) -> Result<Vec<RejectedLimitation>, Error> {
+
+        if id >= self.plugins.len() {
+            return Err(IndexError(
+                    format!(
+                        "Plugin with index {} does not exist",
+                        id,
+                    )));
+        }
+        return Ok(self.plugins[id].rejected_limitations.clone());
+    }
+
+    /// Get the limitations of the host, that did not fit the schema
+    /// of any of the plugins, along with the IDs of those plugins
+    /// (see [`PluginHost::get_rejected_limitations`])
+    pub fn all_rejected_limitations (
+        self: &Self,
+    ) -> Vec<(usize, RejectedLimitation)> {
+
+        let mut result: Vec<(usize, RejectedLimitation)> = Vec::new();
+        for (id, plugin) in self.plugins.iter().enumerate() {
+            for rejected in &plugin.rejected_limitations {
+                result.push((id, rejected.clone()));
+            }
+        }
+        return result;
+    }
+
+    /// Call the function with the ID provided on the initialized
+    /// plugin with the ID provided
+    ///
+    /// If the function returns [`Error::DependencyError`], the
+    /// request it contains is answered by the plugins of the host,
+    /// just like the requests the plugin returned from its init
+    /// function, and the function is called once again. If the
+    /// request can not be fulfilled, it is denied, and
+    /// [`Error::RuntimeError`] is returned, explaining why (see
+    /// [`Resolver::explain_request`])
+    ///
+    /// # Example
+    ///
+    /// ``` rust, ignore
+    /// let id: usize = host.get_ids_by_name(&"test".to_string())[0];
+    /// host.init_plugin(id).expect("fail");
+    /// let result: Object = host.call(id, 3, vec![]).expect("fail");
+    /// ```
+    pub fn call (
+        self: &mut Self,
+        id: usize,
+        fn_id: usize,
+        args: Vec<Object>,
+    ) -> Result<Object, Error> {
+
+        let mut proxy: std::rc::Rc<FreightProxy> = match self.get_by_id(id) {
+            Some(proxy) => proxy,
+            None => return Err(RuntimeError(
+                    format!(
+                        "Plugin with index {} is not initialized",
+                        id,
+                    ))),
+        };
+
+        let request: InterplugRequest =
+            match proxy.get_callable_by_id(fn_id)?.call(args.clone()) {
+                Err(DependencyError(request)) => request,
+                result => return result,
+            };
+
+        match self.plan_requests(id, std::slice::from_ref(&request)) {
+            Ok(resolution) if resolution.denials.is_empty() =>
+                self.apply_resolution(id, &mut proxy, resolution),
+            _ => {
+                proxy.interplug_deny(request.clone());
+                let explanation: Explanation =
+                    self.resolver().explain_request(id, &request);
+                return Err(RuntimeError(
+                        format!(
+                            "Plugin {} needs a dependency, that can not be \
+                            provided:\n{}",
+                            proxy.name,
+                            explanation,
+                        )));
+            },
+        }
+        return proxy.get_callable_by_id(fn_id)?.call(args);
+    }
+
+    /// Initialize all the plugins, that were not initialized yet,
+    /// answering all of their requests
+    ///
+    /// If a crucial request of some plugin could not be fulfilled,
+    /// the rest of the plugins are still initialized, but the
+    /// first such request is returned as an
+    /// [`Error::DependencyError`]
+    pub fn init_all (self: &mut Self) -> Result<(), Error> {
+        let mut result: Result<(), Error> = Ok(());
+        for id in 0..self.plugins.len() {
+            let init_result: Result<(), Error> = self.init_plugin(id);
+            if result.is_ok() {
+                result = init_result;
+            }
+        }
+        return result;
+    }
+
+    /// Initialize the plugin with the ID provided, initializing
+    /// all the plugins it requests before providing them to it
+    ///
+    /// Does nothing if the plugin is already initialized. If the
+    /// plugin failed to initialize earlier, its requests are
+    /// answered again without calling its init function, so a plugin
+    /// could be initialized once its missing dependencies are added
+    /// to the host
+    pub fn init_plugin (
+        self: &mut Self,
+        id: usize,
+    ) -> Result<(), Error> {
+
+        if id >= self.plugins.len() {
+            return Err(IndexError(
+                    format!(
+                        "Plugin with index {} does not exist",
+                        id,
+                    )));
+        }
+
+        let mut proxy: FreightProxy;
+        let requests: Vec<InterplugRequest>;
+        match std::mem::replace(
+            &mut self.plugins[id].state,
+            PluginState::Initializing,
+        ) {
+            PluginState::Loaded(mut loaded) => {
+                requests = loaded.init(&self.limitations);
+                self.plugins[id].requests = requests.clone();
+                self.plugins[id].rejected_limitations =
+                    loaded.get_rejected_limitations();
+                proxy = loaded;
+            },
+            PluginState::Failed(failed) => {
+                requests = self.plugins[id].requests.clone();
+                proxy = failed;
+            },
+            PluginState::Unloaded => {
+                self.plugins[id].state = PluginState::Unloaded;
+                return Err(RuntimeError(
+                        format!(
+                            "Plugin {} was shut down",
+                            self.plugins[id].name,
+                        )));
+            },
+            other => {
+                self.plugins[id].state = other;
+                return Ok(());
+            },
+        }
+
+        let result: Result<(), Error> =
+            self.answer_requests(id, &mut proxy, requests);
+
+        self.plugins[id].state = match result {
+            Ok(()) => PluginState::Ready(std::rc::Rc::new(proxy)),
+            Err(_) => PluginState::Failed(proxy),
+        };
+        self.answer_weak_requests(id);
+        return result;
+    }
+
+    /// Replace the plugin with the ID provided by another build of
+    /// the same library, loaded from the path provided, handing the
+    /// plugin state over to the new instance (see
+    /// [`PluginHost::replace`])
+    ///
+    /// # Warning
+    ///
+    /// Most systems will not load a library again if a library with
+    /// the same path is already loaded, returning the already loaded
+    /// one instead, so the new build should be placed under another
+    /// path (for example, copied into a temporary file) before
+    /// reloading.
+    ///
+    /// # Safety
+    ///
+    /// See [`FreightProxy::load`]
+    pub unsafe fn reload (
+        self: &mut Self,
+        id: usize,
+        lib_path: &str,
+    ) -> Result<(), Error> {
+
+        if id >= self.plugins.len() {
+            return Err(IndexError(
+                    format!(
+                        "Plugin with index {} does not exist",
+                        id,
+                    )));
+        }
+
+        let new_proxy: FreightProxy = self.load_proxy(lib_path)?;
+        return self.replace(id, new_proxy);
+    }
+
+    /// Replace the plugin with the ID provided by another instance of
+    /// it, handing the plugin state over to the new instance
+    ///
+    /// The new instance is initialized and gets its requests
+    /// answered, then gets the state, exported by the old instance
+    /// (see [`Freight::export_state`] and [`Freight::import_state`]).
+    /// The old instance is then shut down (see [`Freight::shutdown`])
+    /// and invalidated (see [`FreightProxy::invalidate`]) and every
+    /// plugin it was provided to is given the new one through
+    /// [`Freight::interplug_replace`].
+    ///
+    /// If the plugin was never initialized, it is simply replaced.
+    /// If the state could not be handed over, the new instance is
+    /// shut down and dropped, the old one is kept just as it was, and
+    /// the error is returned. If a crucial request of the new
+    /// instance could not be fulfilled, the new instance is kept
+    /// anyway and the error is returned.
+    pub fn replace (
+        self: &mut Self,
+        id: usize,
+        mut new_proxy: FreightProxy,
+    ) -> Result<(), Error> {
+
+        if id >= self.plugins.len() {
+            return Err(IndexError(
+                    format!(
+                        "Plugin with index {} does not exist",
+                        id,
+                    )));
+        }
+
+        if !new_proxy.name.eq(&self.plugins[id].name) {
+            return Err(ImportError(
+                    format!(
+                        "Can not replace plugin {} with plugin {}",
+                        self.plugins[id].name,
+                        new_proxy.name,
+                    )));
+        }
+
+        let old_state: PluginState = std::mem::replace(
+            &mut self.plugins[id].state,
+            PluginState::Initializing,
+        );
+        match old_state {
+            PluginState::Initializing => return Err(RuntimeError(
+                    format!(
+                        "Plugin {} is being initialized",
+                        self.plugins[id].name,
+                    ))),
+            PluginState::Loaded(_) | PluginState::Unloaded => {
+                self.plugins[id].version = new_proxy.version.clone();
+                self.plugins[id].backwards_compat_version =
+                    new_proxy.backwards_compat_version.clone();
+                self.plugins[id].rejected_limitations.clear();
+                self.plugins[id].state = PluginState::Loaded(new_proxy);
+                return Ok(());
+            },
+            _ => (),
+        }
+
+        // Everything, that is changed for the new instance, is kept,
+        // so the old one could be put back, if the state can not be
+        // handed over
+        let old_version: Version = self.plugins[id].version.clone();
+        let old_backwards_compat_version: Version =
+            self.plugins[id].backwards_compat_version.clone();
+        let old_requests: Vec<InterplugRequest> =
+            self.plugins[id].requests.clone();
+        let old_rejected_limitations: Vec<RejectedLimitation> =
+            self.plugins[id].rejected_limitations.clone();
+        let old_provided_to: Vec<Vec<(usize, InterplugRequest)>> =
+            self.plugins.iter()
+            .map(|plugin| plugin.provided_to.clone())
+            .collect();
+        let old_weakly_provided_to: Vec<Vec<(usize, InterplugRequest)>> =
+            self.plugins.iter()
+            .map(|plugin| plugin.weakly_provided_to.clone())
+            .collect();
+
+        // The requests of the old instance are not needed anymore
+        for plugin in &mut self.plugins {
+            plugin.provided_to.retain(|(dependent, _)| *dependent != id);
+            plugin.weakly_provided_to.retain(|(dependent, _)| *dependent != id);
+        }
+
+        let requests: Vec<InterplugRequest> =
+            new_proxy.init(&self.limitations);
+        self.plugins[id].requests = requests.clone();
+        self.plugins[id].rejected_limitations =
+            new_proxy.get_rejected_limitations();
+        self.plugins[id].version = new_proxy.version.clone();
+        self.plugins[id].backwards_compat_version =
+            new_proxy.backwards_compat_version.clone();
+        let result: Result<(), Error> =
+            self.answer_requests(id, &mut new_proxy, requests);
+
+        let hand_over: Result<(), Error> = match &old_state {
+            PluginState::Ready(old_proxy) =>
+                old_proxy.hand_over_state(&mut new_proxy),
+            PluginState::Failed(old_proxy) =>
+                old_proxy.hand_over_state(&mut new_proxy),
+            _ => Ok(()),
+        };
+        if hand_over.is_err() {
+            new_proxy.shutdown();
+            drop(new_proxy);
+            // The plugins, initialized for the new instance, keep
+            // their own provisions, but the ones made for the new
+            // instance are replaced by the ones of the old one
+            for (plugin, provided_to) in
+                self.plugins.iter_mut().zip(old_provided_to) {
+
+                plugin.provided_to.retain(|(dependent, _)| *dependent != id);
+                plugin.provided_to.extend(provided_to.into_iter()
+                    .filter(|(dependent, _)| *dependent == id));
+            }
+            for (plugin, weakly_provided_to) in
+                self.plugins.iter_mut().zip(old_weakly_provided_to) {
+
+                plugin.weakly_provided_to.retain(|(dependent, _)| *dependent != id);
+                plugin.weakly_provided_to.extend(weakly_provided_to.into_iter()
+                    .filter(|(dependent, _)| *dependent == id));
+            }
+            self.plugins[id].version = old_version;
+            self.plugins[id].backwards_compat_version =
+                old_backwards_compat_version;
+            self.plugins[id].requests = old_requests;
+            self.plugins[id].rejected_limitations = old_rejected_limitations;
+            self.plugins[id].state = old_state;
+            self.answer_weak_requests(id);
+            return hand_over;
+        }
+
+        match &old_state {
+            PluginState::Ready(old_proxy) => {
+                old_proxy.clone().shutdown();
+                old_proxy.invalidate();
+            },
+            PluginState::Failed(old_proxy) => {
+                old_proxy.inner.borrow_mut().shutdown();
+                old_proxy.invalidate();
+            },
+            _ => (),
+        }
+        drop(old_state);
+
+        let new_proxy: std::rc::Rc<FreightProxy> = match result {
+            Err(DependencyError(_)) => {
+                self.plugins[id].state = PluginState::Failed(new_proxy);
+                self.answer_weak_requests(id);
+                return result;
+            },
+            _ => std::rc::Rc::new(new_proxy),
+        };
+        self.plugins[id].state = PluginState::Ready(new_proxy.clone());
+        self.answer_weak_requests(id);
+
+        for (dependent, request) in self.plugins[id].provided_to.clone() {
+            self.with_plugin(dependent, |freight: &mut dyn Freight| {
+                freight.interplug_replace(request, new_proxy.clone());
+            });
+        }
+        // The plugins, that got a weak handle, get a new one, so they
+        // still do not keep this plugin from being dropped
+        for (dependent, request) in self.plugins[id].weakly_provided_to.clone() {
+            self.with_plugin(dependent, |freight: &mut dyn Freight| {
+                freight.interplug_provide_weak(
+                    request,
+                    WeakFreightProxy::new(&new_proxy));
+            });
+        }
+        return result;
+    }
+
+    /// Shut the plugin with the ID provided down and drop it, so its
+    /// library could be unloaded
+    ///
+    /// Every plugin it was provided to, including the ones, that got
+    /// a weak handle to it, is told to drop it through
+    /// [`Freight::interplug_withdraw`] first, then, if the plugin was
+    /// ever initialized, its [`Freight::shutdown`] function is called
+    /// and the proxy is invalidated (see [`FreightProxy::invalidate`]).
+    /// The plugins, that were provided to it, are not shut down. Does
+    /// nothing if the plugin was already shut down
+    pub fn shutdown (
+        self: &mut Self,
+        id: usize,
+    ) -> Result<(), Error> {
+
+        if id >= self.plugins.len() {
+            return Err(IndexError(
+                    format!(
+                        "Plugin with index {} does not exist",
+                        id,
+                    )));
+        }
+
+        match self.plugins[id].state {
+            PluginState::Initializing => return Err(RuntimeError(
+                    format!(
+                        "Plugin {} is being initialized",
+                        self.plugins[id].name,
+                    ))),
+            PluginState::Unloaded => return Ok(()),
+            _ => (),
+        }
+
+        let mut dependents: Vec<(usize, InterplugRequest)> =
+            std::mem::take(&mut self.plugins[id].provided_to);
+        dependents.append(&mut self.plugins[id].weakly_provided_to);
+        for (dependent, request) in dependents {
+            self.with_plugin(dependent, |freight: &mut dyn Freight| {
+                freight.interplug_withdraw(request);
+            });
+        }
+
+        // The requests of the plugin are not needed anymore
+        for plugin in &mut self.plugins {
+            plugin.provided_to.retain(|(dependent, _)| *dependent != id);
+            plugin.weakly_provided_to.retain(|(dependent, _)| *dependent != id);
+        }
+
+        match std::mem::replace(
+            &mut self.plugins[id].state,
+            PluginState::Unloaded,
+        ) {
+            PluginState::Ready(proxy) => {
+                proxy.clone().shutdown();
+                proxy.invalidate();
+            },
+            PluginState::Failed(mut proxy) => proxy.shutdown(),
+            _ => (),
+        }
+        return Ok(());
+    }
+
+    /// Shut every plugin of the host down (see
+    /// [`PluginHost::shutdown`]) in reverse dependency order, so each
+    /// plugin is only shut down after all the plugins it was provided
+    /// to, except for the ones, that only got a weak handle to it
+    ///
+    /// This is done automatically, when the host is dropped
+    pub fn shutdown_all (self: &mut Self) {
+        loop {
+            let remaining: Vec<usize> = (0..self.plugins.len())
+                .filter(|id| !matches!(
+                        self.plugins[*id].state,
+                        PluginState::Unloaded | PluginState::Initializing))
+                .collect();
+
+            // Plugins added later are shut down first, as well as
+            // the ones on a dependency cycle, if there is one
+            let id: usize = match remaining.iter().rev()
+                .find(|id| self.plugins[**id].provided_to.is_empty()) {
+
+                Some(id) => *id,
+                None => match remaining.last() {
+                    Some(id) => *id,
+                    None => return,
+                },
+            };
+            let _ = self.shutdown(id);
+        }
+    }
+
+    /// Call the function provided on the plugin with the ID
+    /// provided, unless it is being initialized right now
+    fn with_plugin<F> (
+        self: &mut Self,
+        id: usize,
+        function: F,
+    ) where F: FnOnce(&mut dyn Freight) {
+
+        match &mut self.plugins[id].state {
+            PluginState::Loaded(proxy) | PluginState::Failed(proxy) =>
+                function(proxy),
+            PluginState::Ready(proxy) => function(&mut proxy.clone()),
+            PluginState::Initializing | PluginState::Unloaded => (),
+        }
+    }
+
+    /// Answer the requests, made for the plugin with the ID provided
+    /// by the plugins it depends on, while it was being initialized,
+    /// by providing a weak handle to it, if it was initialized
+    /// successfully, or by denying them otherwise
+    fn answer_weak_requests (
+        self: &mut Self,
+        id: usize,
+    ) {
+
+        let pending: Vec<(usize, InterplugRequest)> =
+            std::mem::take(&mut self.plugins[id].weak_pending);
+        for (dependent, request) in pending {
+            match self.get_by_id(id) {
+                Some(provider) => {
+                    self.plugins[id].weakly_provided_to.push(
+                        (dependent, request.clone()));
+                    self.with_plugin(dependent, |freight: &mut dyn Freight| {
+                        freight.interplug_provide_weak(
+                            request,
+                            WeakFreightProxy::new(&provider));
+                    });
+                },
+                None => self.with_plugin(dependent, |freight: &mut dyn Freight| {
+                    freight.interplug_deny(request);
+                }),
+            }
+        }
+    }
+
+    /// Answer all the requests a plugin made, as planned by the
+    /// [`Resolver`] (see [`PluginHost::plan_requests`]), returning
+    /// the crucial request, that could not be fulfilled, as an error
+    ///
+    /// Nothing is provided unless every crucial request can be
+    /// fulfilled, in which case all the requests are denied
+    fn answer_requests (
+        self: &mut Self,
+        id: usize,
+        proxy: &mut dyn Freight,
+        requests: Vec<InterplugRequest>,
+    ) -> Result<(), Error> {
+
+        match self.plan_requests(id, &requests) {
+            Ok(resolution) => {
+                self.apply_resolution(id, proxy, resolution);
+                return Ok(());
+            },
+            Err(error) => {
+                for request in requests {
+                    proxy.interplug_deny(request);
+                }
+                return Err(error);
+            },
+        }
+    }
+
+    /// Plan how to answer the requests provided, made by the plugin
+    /// with the ID provided, with the [`Resolver`] of the host, and
+    /// initialize every plugin, the plan provides to it
+    ///
+    /// Since the requests of a plugin are only known once it is
+    /// initialized, a plugin, that was planned to be provided, may
+    /// still fail to initialize, in which case the requests are
+    /// planned once again without it
+    fn plan_requests (
+        self: &mut Self,
+        id: usize,
+        requests: &[InterplugRequest],
+    ) -> Result<Resolution, Error> {
+
+        loop {
+            let states: Vec<HostedState> = self.plugins.iter()
+                .map(|plugin| match plugin.state {
+                    PluginState::Loaded(_) => HostedState::Loaded,
+                    PluginState::Initializing => HostedState::Initializing,
+                    PluginState::Ready(_) => HostedState::Ready,
+                    PluginState::Failed(_) | PluginState::Unloaded =>
+                        HostedState::Unusable,
+                })
+                .collect();
+            let resolution: Resolution =
+                self.resolver().resolve_hosted(id, requests, &states)?;
+
+            let mut initialized: bool = true;
+            for provision in &resolution.provisions {
+                if provision.requester != id || provision.weak {
+                    continue;
+                }
+                // The result is checked through the plugin state
+                // right below
+                let _ = self.init_plugin(provision.provider);
+                if self.get_by_id(provision.provider).is_none() {
+                    initialized = false;
+                    break;
+                }
+            }
+            if initialized {
+                return Ok(resolution);
+            }
+        }
+    }
+
+    /// Provide the plugin with the ID provided with every plugin,
+    /// the resolution decided to provide to it, and deny the rest
+    /// of its requests
+    ///
+    /// The plugins, that depend on it, are provided as weak handles,
+    /// once they are initialized (see
+    /// [`PluginHost::answer_weak_requests`])
+    fn apply_resolution (
+        self: &mut Self,
+        id: usize,
+        proxy: &mut dyn Freight,
+        resolution: Resolution,
+    ) {
+
+        for provision in resolution.provisions {
+            if provision.requester != id {
+                continue;
+            }
+            if provision.weak {
+                self.plugins[provision.provider].weak_pending.push(
+                    (id, provision.request));
+                continue;
+            }
+            match self.get_by_id(provision.provider) {
+                Some(provider) => {
+                    self.plugins[provision.provider].provided_to.push(
+                        (id, provision.request.clone()));
+                    proxy.interplug_provide(provision.request, provider);
+                },
+                None => proxy.interplug_deny(provision.request),
+            }
+        }
+        for denial in resolution.denials {
+            if denial.requester == id {
+                proxy.interplug_deny(denial.request);
+            }
+        }
+    }
+
+    /// Check if the plugin with the ID provided could be used to
+    /// fulfill the request, returning every reason it could not
+    ///
+    /// Besides the reasons, returned by
+    /// [`InterplugRequest::check_provider`], the plugin is rejected
+    /// if it failed or is waiting for its own requests to be answered.
+    /// Whether a pre-release plugin is rejected also depends on the
+    /// [`PluginHost::pre_releases`] policy
+    pub fn check_provider (
+        self: &Self,
+        id: usize,
+        request: &InterplugRequest,
+    ) -> Result<(), Vec<Rejection>> {
+
+        let plugin: &HostedPlugin = match self.plugins.get(id) {
+            Some(plugin) => plugin,
+            None => return Err(vec![Rejection::NotLoaded]),
+        };
+
+        let mut reasons: Vec<Rejection> = match request.check_provider(
+            &plugin.name,
+            &plugin.version,
+            &plugin.backwards_compat_version) {
+
+            Ok(()) => Vec::new(),
+            Err(reasons) => reasons,
+        };
+        self.pre_releases.apply(&plugin.version, &mut reasons);
+        match plugin.state {
+            PluginState::Loaded(_) | PluginState::Ready(_) => (),
+            PluginState::Initializing => reasons.push(Rejection::Initializing),
+            PluginState::Failed(_) => reasons.push(Rejection::Failed),
+            PluginState::Unloaded => reasons.push(Rejection::Unloaded),
+        }
+
+        if reasons.is_empty() {
+            return Ok(());
+        }
+        return Err(reasons);
+    }
+
+    /// Find the ID of a plugin, that could be used to fulfill a
+    /// [`InterplugRequest::PlugRequest`] or
+    /// [`InterplugRequest::PlugRequestAll`]: a plugin with the same
+    /// name, that is compatible with a version, matching the
+    /// requested [`VersionReq`], and is neither failed nor waiting
+    /// for its own requests to be answered (see
+    /// [`PluginHost::check_provider`])
+    ///
+    /// If several plugins fit, the one with the greatest version is
+    /// chosen
+    pub fn find_provider (
+        self: &Self,
+        request: &InterplugRequest,
+    ) -> Option<usize> {
+
+        let plugin: &String = match request {
+            PlugRequest { plugin, .. } => plugin,
+            PlugRequestAll { plugin, .. } => plugin,
+            _ => return None,
+        };
+
+        let mut best: Option<usize> = None;
+        for id in self.get_ids_by_name(plugin) {
+            if self.check_provider(id, request).is_err() {
+                continue;
+            }
+            best = match best {
+                Some(best_id)
+                    if !self.plugins[id].version.is_preferred_to(
+                        &self.plugins[best_id].version) => Some(best_id),
+                _ => Some(id),
+            };
+        }
+        return best;
+    }
+
+    /// Find the IDs of the plugins of the host, either initialized or
+    /// only loaded, a type of which implements the trait, so that it
+    /// could be used to fulfill a [`InterplugRequest::TraitRequest`]
+    /// or [`InterplugRequest::TraitRequestAll`]
+    ///
+    /// A plugin, that is only loaded, needs to be initialized before
+    /// it is used, after which the implementations themselves can be
+    /// found with [`TraitProxy::find`]. Whether an implementation of a
+    /// pre-release version of the trait is used depends on the
+    /// [`PluginHost::pre_releases`] policy
+    pub fn find_trait_implementors (
+        self: &Self,
+        request: &InterplugRequest,
+    ) -> Vec<usize> {
+
+        let mut result: Vec<usize> = Vec::new();
+        for id in 0..self.plugins.len() {
+            let types: Vec<Type> = match self.inspect_plugin(
+                id,
+                |cache: &mut FreightCache| cache.get_type_list()) {
+
+                Some(Ok(types)) => types,
+                _ => continue,
+            };
+            let implements = |implementation: &TraitImplementation| {
+                let mut reasons: Vec<Rejection> =
+                    match ImplementedTrait::from(implementation)
+                        .check_request(request) {
+
+                        Ok(()) => Vec::new(),
+                        Err(reasons) => reasons,
+                    };
+                self.pre_releases.apply(&implementation.version, &mut reasons);
+                reasons.is_empty()
+            };
+            if types.iter().any(|tp| tp.trait_implementations.iter().any(implements)) {
+                result.push(id);
+            }
+        }
+        return result;
+    }
+
+    /// Call the function provided on the cache of the plugin with the
+    /// ID provided, if it is either loaded or initialized, without
+    /// initializing it
+    fn inspect_plugin<F, R> (
+        self: &Self,
+        id: usize,
+        function: F,
+    ) -> Option<R> where F: FnOnce(&mut FreightCache) -> R {
+
+        match &self.plugins.get(id)?.state {
+            PluginState::Loaded(proxy) =>
+                Some(function(&mut proxy.inner.borrow_mut())),
+            PluginState::Ready(proxy) =>
+                Some(function(&mut proxy.inner.borrow_mut())),
+            _ => None,
+        }
+    }
+
+    /// Get a [`Resolver`] with every plugin of the host available in
+    /// it under the same ID, along with the requests it made, if it
+    /// was initialized already
+    ///
+    /// The functions and the implemented traits of the plugins are
+    /// only known for the ones, that are either loaded or initialized
+    /// successfully
+    pub fn resolver (self: &Self) -> Resolver {
+        let mut resolver: Resolver = Resolver::new();
+        resolver.pre_releases = self.pre_releases;
+        for (id, plugin) in self.plugins.iter().enumerate() {
+            let mut available: AvailablePlugin = AvailablePlugin::new(
+                plugin.name.clone(),
+                plugin.version.clone(),
+                plugin.backwards_compat_version.clone());
+            available.requests = plugin.requests.clone();
+            self.inspect_plugin(id, |cache: &mut FreightCache| {
+                available.functions = cache.get_function_list().ok()
+                    .map(|functions| functions.iter()
+                        .map(|function| function.fn_id)
+                        .collect());
+                for tp in cache.get_type_list().unwrap_or_default() {
+                    available.traits.extend(tp.trait_implementations.iter()
+                        .map(ImplementedTrait::from));
+                }
+            });
+            resolver.add(available);
+        }
+        return resolver;
+    }
+
+    /// Explain whether each of the requests of the plugin with the ID
+    /// provided can be fulfilled by the plugins of the host, and why
+    /// not, if it can not (see [`Resolver::explain`])
+    ///
+    /// # Example
+    ///
+    /// ``` rust, ignore
+    /// if let Err(DependencyError(_)) = host.init_plugin(id) {
+    ///     for explanation in host.explain(id).unwrap() {
+    ///         eprint!("{}", explanation);
+    ///     }
+    /// }
+    /// ```
+    pub fn explain (
+        self: &Self,
+        id: usize,
+    ) -> Result<Vec<Explanation>, Error> {
+
+        self.resolver().explain(id)
+    }
+
+    /// Get the [`DependencyGraph`] of the plugins of the host, under
+    /// the same IDs, along with the requests they made and how each
+    /// of them was answered
+    ///
+    /// # Example
+    ///
+    /// ``` rust, ignore
+    /// host.init_all()?;
+    /// std::fs::write("plugins.dot", host.dependency_graph().to_dot())?;
+    /// ```
+    pub fn dependency_graph (self: &Self) -> DependencyGraph {
+        let mut graph: DependencyGraph = DependencyGraph::new();
+        for plugin in &self.plugins {
+            graph.add_plugin(
+                plugin.name.clone(),
+                plugin.version.clone(),
+                matches!(plugin.state, PluginState::Ready(_)));
+        }
+
+        for (id, plugin) in self.plugins.iter().enumerate() {
+            let denied: Vec<InterplugRequest> = match &plugin.state {
+                PluginState::Loaded(proxy) | PluginState::Failed(proxy) =>
+                    proxy.denied_requests(),
+                PluginState::Ready(proxy) => proxy.denied_requests(),
+                PluginState::Initializing | PluginState::Unloaded =>
+                    Vec::new(),
+            };
+            graph.add_requests(id, &plugin.requests, &|request| {
+                let answered = |(requester, answered): &(usize, InterplugRequest)|
+                    *requester == id && answered == request;
+                for (provider, other) in self.plugins.iter().enumerate() {
+                    if other.provided_to.iter().any(answered) {
+                        return Answer::Provided(provider);
+                    }
+                    if other.weakly_provided_to.iter().any(answered) {
+                        return Answer::ProvidedWeak(provider);
+                    }
+                }
+                match denied.iter().any(|denied| contains_request(denied, request)) {
+                    true => Answer::Denied,
+                    false => Answer::Unanswered,
+                }
+            });
+        }
+        return graph;
+    }
+}
+
+/// Check if the request provided is the same as the outer one or is
+/// nested in it
+fn contains_request (
+    outer: &InterplugRequest,
+    request: &InterplugRequest,
+) -> bool {
+
+    if outer == request {
+        return true;
+    }
+    match outer {
+        RequestCrucial { request: nested } |
+            RequestOptional { request: nested } =>
+            contains_request(nested, request),
+        RequestEither { requests } | RequestEach { requests } =>
+            requests.iter().any(|nested| contains_request(nested, request)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A shared place for a proxy, that is only known after the
+    /// freight, using it, is built
+    type ProxySlot =
+        std::rc::Rc<std::cell::RefCell<Option<std::rc::Rc<FreightProxy>>>>;
+
+    /// Plugins, provided to a freight, shared with its callables
+    type ProxyList =
+        std::rc::Rc<std::cell::RefCell<Vec<std::rc::Rc<FreightProxy>>>>;
+
+    /// Weak handles, provided to a freight, shared with the test
+    type HandleList =
+        std::rc::Rc<std::cell::RefCell<Vec<WeakFreightProxy>>>;
+
+    /// A freight, that writes down every call it gets
+    #[derive(Debug, Default)]
+    struct TestFreight {
+
+        /// Name of the freight, every entry of the log starts with
+        name: String,
+
+        /// Calls the freight got, shared with the test
+        log: std::rc::Rc<std::cell::RefCell<Vec<String>>>,
+
+        /// Proxy, the freight calls from inside of its own call to
+        /// [`Freight::get_function_list`], if any
+        reenter: ProxySlot,
+
+        /// Requests, returned from [`Freight::init`]
+        requests: Vec<InterplugRequest>,
+
+        /// Plugins, provided to the freight, until they are withdrawn
+        provided: ProxyList,
+
+        /// Weak handles, provided to the freight
+        handles: HandleList,
+
+        /// Request, the function of the freight fails with, until
+        /// some plugin is provided to the freight, if any
+        dependency: Option<InterplugRequest>,
+
+        /// State, exported by the freight, if any
+        state: Option<Vec<u8>>,
+
+        /// Whether the freight fails to import the state
+        rejects_state: bool,
+
+        /// Settings, the freight can be limited in, if it declares them
+        schema: Option<LimitationSchema>,
+    }
+
+    /// The callable of the function of [`TestFreight`]
+    #[derive(Clone, Debug)]
+    struct TestCallable {
+
+        /// Calls, the freight got, shared with the test
+        log: std::rc::Rc<std::cell::RefCell<Vec<String>>>,
+
+        /// Plugins, provided to the freight
+        provided: ProxyList,
+
+        /// Request, the call fails with, until some plugin is provided
+        dependency: Option<InterplugRequest>,
+    }
+
+    impl DuskCallable for TestCallable {
+        fn call (
+            self: &mut Self,
+            _args: Vec<Object>,
+        ) -> Result<Object, Error> {
+
+            self.log.borrow_mut().push("called".to_string());
+            if let Some(dependency) = &self.dependency {
+                if self.provided.borrow().is_empty() {
+                    return Err(DependencyError(dependency.clone()));
+                }
+            }
+            let data_type: &'static Type = Box::leak(Box::new(Type {
+                name: "dusk-test-result".to_string(),
+                ..Default::default()
+            }));
+            return Ok(Object::new(
+                    Box::new(ForeignObject::new(Vec::new())),
+                    data_type,
+                    0));
+        }
+    }
+
+    impl TestFreight {
+
+        /// Write the call provided down
+        fn note (
+            self: &Self,
+            call: String,
+        ) {
+
+            self.log.borrow_mut().push(format!("{} {}", self.name, call));
+        }
+    }
+
+    impl Freight for TestFreight {
+        fn init (
+            self: &mut Self,
+            limitations: &Option<Vec<Limitation>>,
+        ) -> Vec<InterplugRequest> {
+
+            self.note("init".to_string());
+            if let Some(limitations) = limitations {
+                self.update_limitations(limitations);
+            }
+            return self.requests.clone();
+        }
+
+        fn update_limitations (
+            self: &mut Self,
+            limitations: &Vec<Limitation>,
+        ) {
+
+            let limitations: Vec<String> = limitations.iter()
+                .map(|limitation| limitation.to_string())
+                .collect();
+            self.note(format!("limited [{}]", limitations.join(", ")));
+        }
+
+        fn get_limitation_schema (
+            self: &mut Self,
+        ) -> Option<LimitationSchema> {
+
+            return self.schema.clone();
+        }
+
+        fn interplug_provide (
+            self: &mut Self,
+            _request: InterplugRequest,
+            freight_proxy: std::rc::Rc<FreightProxy>,
+        ) {
+
+            self.note(format!(
+                    "provided {} {}",
+                    freight_proxy.name,
+                    freight_proxy.version,
+            ));
+            self.provided.borrow_mut().push(freight_proxy);
+        }
+
+        fn interplug_provide_weak (
+            self: &mut Self,
+            _request: InterplugRequest,
+            freight_proxy: WeakFreightProxy,
+        ) {
+
+            self.note(format!(
+                    "provided weak {} {}",
+                    freight_proxy.name,
+                    freight_proxy.version,
+            ));
+            self.handles.borrow_mut().push(freight_proxy);
+        }
+
+        fn interplug_deny (
+            self: &mut Self,
+            _request: InterplugRequest,
+        ) {
+
+            self.note("denied".to_string());
+        }
+
+        fn interplug_withdraw (
+            self: &mut Self,
+            _request: InterplugRequest,
+        ) {
+
+            self.note("withdrawn".to_string());
+            self.provided.borrow_mut().clear();
+        }
+
+        fn interplug_replace (
+            self: &mut Self,
+            _request: InterplugRequest,
+            freight_proxy: std::rc::Rc<FreightProxy>,
+        ) {
+
+            self.note(format!(
+                    "replaced {} {}",
+                    freight_proxy.name,
+                    freight_proxy.version,
+            ));
+            self.provided.borrow_mut().clear();
+            self.provided.borrow_mut().push(freight_proxy);
+        }
+
+        fn shutdown (self: &mut Self) {
+            self.note("shutdown".to_string());
+        }
+
+        fn export_state (
+            self: &mut Self,
+        ) -> Result<Option<Vec<u8>>, Error> {
+
+            return Ok(self.state.clone());
+        }
+
+        fn import_state (
+            self: &mut Self,
+            state: Vec<u8>,
+        ) -> Result<(), Error> {
+
+            if self.rejects_state {
+                self.note("rejected state".to_string());
+                return Err(ValueError("Unknown state".to_string()));
+            }
+            self.note(format!("imported {:?}", state));
+            self.state = Some(state);
+            return Ok(());
+        }
+
+        fn get_root_modules (self: &mut Self) -> Vec<Module> {
+            Vec::new()
+        }
+
+        fn get_function_list (
+            self: &mut Self,
+        ) -> Result<Vec<Function>, Error> {
+
+            let reenter: Option<std::rc::Rc<FreightProxy>> =
+                self.reenter.borrow().clone();
+            if let Some(mut proxy) = reenter {
+                let result: Result<Vec<Type>, Error> = proxy.get_type_list();
+                self.note(format!("reentered {}", result.is_ok()));
+            }
+            return Ok(vec![
+                Function {
+                    name: "test".to_string(),
+                    callable: Box::new(TestCallable {
+                        log: self.log.clone(),
+                        provided: self.provided.clone(),
+                        dependency: self.dependency.clone(),
+                    }),
+                    ..Default::default()
+                },
+            ]);
+        }
+    }
+
+    /// Build a proxy of version 1.0 around the freight provided
+    fn test_proxy (freight: TestFreight) -> FreightProxy {
+        versioned_proxy(freight, 1)
+    }
+
+    /// Build a proxy of the major version provided around the
+    /// freight provided
+    fn versioned_proxy (
+        freight: TestFreight,
+        major: usize,
+    ) -> FreightProxy {
+
+        FreightProxy::new(
+            freight.name.clone(),
+            Version { major: major, ..Default::default() },
+            Version { major: major, ..Default::default() },
+            Box::new(freight))
+    }
+
+    /// Build a freight with the name and requests provided, writing
+    /// to the log provided
+    fn test_freight (
+        name: &str,
+        requests: Vec<InterplugRequest>,
+        log: &std::rc::Rc<std::cell::RefCell<Vec<String>>>,
+    ) -> TestFreight {
+
+        TestFreight {
+            name: name.to_string(),
+            log: log.clone(),
+            requests: requests,
+            ..Default::default()
+        }
+    }
+
+    /// Build a request for any version of the plugin provided
+    fn request_all (plugin: &str) -> InterplugRequest {
+        PlugRequestAll {
+            plugin: plugin.to_string(),
+            version: VersionReq::any(),
+        }
+    }
+
+    #[test]
+    fn add_and_shutdown () {
+        let log: std::rc::Rc<std::cell::RefCell<Vec<String>>> =
+            std::rc::Rc::default();
+        let mut host: PluginHost = PluginHost::new();
+        let old: usize = host.add(
+            versioned_proxy(test_freight("base", Vec::new(), &log), 1));
+        let new: usize = host.add(
+            versioned_proxy(test_freight("base", Vec::new(), &log), 2));
+        let user: usize = host.add(test_proxy(test_freight(
+                    "user", vec![request_all("base")], &log)));
+        let missing: usize = host.add(test_proxy(test_freight(
+                    "missing", vec![RequestCrucial {
+                        request: Box::new(request_all("nothing")),
+                    }], &log)));
+
+        assert_eq!(host.len(), 4);
+        assert_eq!(host.get_ids_by_name(&"base".to_string()), vec![old, new]);
+        assert!(host.get_ids_by_name(&"other".to_string()).is_empty());
+        assert_eq!(
+            host.get_id(&"base".to_string(),
+                &Version { major: 2, ..Default::default() }),
+            Some(new));
+
+        // Nothing is handed out before it is initialized
+        assert!(host.get_by_id(user).is_none());
+        assert!(host.get_latest(&"base".to_string()).is_none());
+
+        assert!(matches!(host.init_all(), Err(DependencyError(_))));
+        assert_eq!(host.get_requests(user).unwrap(), vec![request_all("base")]);
+        assert!(host.get_by_id(user).is_some());
+        assert!(host.get_by_id(missing).is_none());
+        assert_eq!(
+            host.get_latest(&"base".to_string()).unwrap().version.major, 2);
+        assert!(log.borrow().contains(&"user provided base 2.0.0.0".to_string()));
+        assert!(log.borrow().contains(&"missing denied".to_string()));
+        assert!(host.get_by_id(4).is_none());
+        assert!(host.get_requests(4).is_err());
+
+        // The plugin, that got the one shut down, is told first
+        log.borrow_mut().clear();
+        host.shutdown(new).unwrap();
+        assert_eq!(
+            *log.borrow(),
+            vec!["user withdrawn".to_string(), "base shutdown".to_string()]);
+        assert!(host.get_by_id(new).is_none());
+        assert_eq!(
+            host.get_latest(&"base".to_string()).unwrap().version.major, 1);
+
+        // Shutting a plugin down twice does nothing
+        log.borrow_mut().clear();
+        host.shutdown(new).unwrap();
+        assert!(log.borrow().is_empty());
+        assert!(host.init_plugin(new).is_err());
+        assert!(host.shutdown(4).is_err());
+    }
+
+    #[test]
+    fn reentrant_call () {
+        let freight: TestFreight = TestFreight {
+            name: "test".to_string(),
+            ..Default::default()
+        };
+        let log: std::rc::Rc<std::cell::RefCell<Vec<String>>> =
+            freight.log.clone();
+        let reenter: ProxySlot = freight.reenter.clone();
+        let mut proxy: std::rc::Rc<FreightProxy> =
+            std::rc::Rc::new(test_proxy(freight));
+        *reenter.borrow_mut() = Some(proxy.clone());
+
+        // The nested call fails instead of panicking, and the outer
+        // one still succeeds
+        assert!(proxy.get_function_list().is_ok());
+        assert_eq!(*log.borrow(), vec!["test reentered false".to_string()]);
+        assert!(proxy.get_type_list().is_ok());
+        *reenter.borrow_mut() = None;
+    }
+
+    #[test]
+    fn call_retry () {
+        let log: std::rc::Rc<std::cell::RefCell<Vec<String>>> =
+            std::rc::Rc::default();
+        let mut host: PluginHost = PluginHost::new();
+        let base: usize =
+            host.add(test_proxy(test_freight("base", Vec::new(), &log)));
+        let user: usize = host.add(test_proxy(TestFreight {
+            dependency: Some(request_all("base")),
+            ..test_freight("user", Vec::new(), &log)
+        }));
+        let lonely: usize = host.add(test_proxy(TestFreight {
+            dependency: Some(request_all("nothing")),
+            ..test_freight("lonely", Vec::new(), &log)
+        }));
+        host.init_all().unwrap();
+
+        // The dependency is provided once, and the call is made again
+        log.borrow_mut().clear();
+        assert!(host.call(user, 0, Vec::new()).is_ok());
+        assert_eq!(
+            *log.borrow(),
+            vec![
+                "called".to_string(),
+                "user provided base 1.0.0.0".to_string(),
+                "called".to_string(),
+            ]);
+
+        // Once provided, the dependency is not requested again
+        log.borrow_mut().clear();
+        assert!(host.call(user, 0, Vec::new()).is_ok());
+        assert_eq!(*log.borrow(), vec!["called".to_string()]);
+
+        // The request, that can not be fulfilled, is denied once and
+        // the call is not made again
+        log.borrow_mut().clear();
+        assert!(matches!(
+                host.call(lonely, 0, Vec::new()),
+                Err(RuntimeError(_))));
+        assert_eq!(
+            *log.borrow(),
+            vec!["called".to_string(), "lonely denied".to_string()]);
+
+        assert!(host.call(base, 1, Vec::new()).is_err());
+        assert!(host.call(3, 0, Vec::new()).is_err());
+    }
+
+    #[test]
+    fn replace () {
+        let log: std::rc::Rc<std::cell::RefCell<Vec<String>>> =
+            std::rc::Rc::default();
+        let mut host: PluginHost = PluginHost::new();
+        let base: usize = host.add(test_proxy(TestFreight {
+            state: Some(vec![1, 2]),
+            ..test_freight("base", Vec::new(), &log)
+        }));
+        let user: usize = host.add(test_proxy(test_freight(
+                    "user", vec![request_all("base")], &log)));
+        host.init_all().unwrap();
+        let old: std::rc::Rc<FreightProxy> = host.get_by_id(base).unwrap();
+
+        // The new instance is initialized, gets the state and is given
+        // to the plugins, the old one was provided to
+        log.borrow_mut().clear();
+        host.replace(
+            base,
+            versioned_proxy(test_freight("base", Vec::new(), &log), 2))
+            .unwrap();
+        assert_eq!(
+            *log.borrow(),
+            vec![
+                "base init".to_string(),
+                "base imported [1, 2]".to_string(),
+                "base shutdown".to_string(),
+                "user replaced base 2.0.0.0".to_string(),
+            ]);
+        assert_eq!(host.get_by_id(base).unwrap().version.major, 2);
+        assert!(old.clone().get_function_list().unwrap().is_empty());
+        drop(old);
+
+        // The new instance, that can not take the state over, is
+        // dropped, and the old one is kept just as it was
+        log.borrow_mut().clear();
+        let kept: std::rc::Rc<FreightProxy> = host.get_by_id(base).unwrap();
+        assert!(host.replace(
+                base,
+                versioned_proxy(TestFreight {
+                    rejects_state: true,
+                    ..test_freight("base", Vec::new(), &log)
+                }, 3))
+            .is_err());
+        assert_eq!(
+            *log.borrow(),
+            vec![
+                "base init".to_string(),
+                "base rejected state".to_string(),
+                "base shutdown".to_string(),
+            ]);
+        assert!(std::rc::Rc::ptr_eq(&host.get_by_id(base).unwrap(), &kept));
+        assert_eq!(kept.clone().get_function_list().unwrap().len(), 1);
+        assert_eq!(
+            host.get_id(&"base".to_string(),
+                &Version { major: 2, ..Default::default() }),
+            Some(base));
+
+        // The plugin, it was provided to, is still told it goes away
+        log.borrow_mut().clear();
+        drop(kept);
+        host.shutdown(base).unwrap();
+        assert_eq!(
+            *log.borrow(),
+            vec!["user withdrawn".to_string(), "base shutdown".to_string()]);
+
+        assert!(host.replace(user, test_proxy(
+                    test_freight("base", Vec::new(), &log))).is_err());
+        assert!(host.replace(2, test_proxy(
+                    test_freight("base", Vec::new(), &log))).is_err());
+    }
+
+    #[test]
+    fn weak_handles () {
+        let log: std::rc::Rc<std::cell::RefCell<Vec<String>>> =
+            std::rc::Rc::default();
+        let handles: HandleList = std::rc::Rc::default();
+        let mut host: PluginHost = PluginHost::new();
+
+        // The plugins depend on each other, so the request, that
+        // closes the cycle, is fulfilled with a weak handle
+        let first: usize = host.add(test_proxy(TestFreight {
+            handles: handles.clone(),
+            ..test_freight("first", vec![request_all("second")], &log)
+        }));
+        let second: usize = host.add(test_proxy(TestFreight {
+            handles: handles.clone(),
+            ..test_freight("second", vec![request_all("first")], &log)
+        }));
+        host.init_all().unwrap();
+        assert_eq!(handles.borrow().len(), 1);
+        let handle: WeakFreightProxy = handles.borrow()[0].clone();
+        let (provider, requester): (usize, &str) = match handle.name.as_str() {
+            "first" => (first, "second"),
+            _ => (second, "first"),
+        };
+        assert!(log.borrow().contains(&format!(
+                    "{} provided weak {} 1.0.0.0",
+                    requester,
+                    handle.name)));
+        assert!(handle.is_loaded());
+        assert!(std::rc::Rc::ptr_eq(
+                &handle.upgrade().unwrap(),
+                &host.get_by_id(provider).unwrap()));
+
+        // The handle does not keep the plugin from being dropped, and
+        // every copy of it fails to upgrade afterwards
+        host.shutdown(provider).unwrap();
+        assert!(!handle.is_loaded());
+        assert!(!handles.borrow()[0].is_loaded());
+        match handle.upgrade() {
+            Err(RuntimeError(message)) => assert_eq!(
+                message,
+                format!("Plugin {} 1.0.0.0 was unloaded", handle.name)),
+            other => panic!("Unexpected result: {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn rejected_limitations () {
+        let log: std::rc::Rc<std::cell::RefCell<Vec<String>>> =
+            std::rc::Rc::default();
+        let mut schema: LimitationSchema = LimitationSchema::new();
+        schema.add(LimitationSetting::new(
+                "threads".to_string(),
+                "Amount of worker threads".to_string(),
+                8)
+            .with_range(Some(1), Some(64)));
+        let mut host: PluginHost = PluginHost::new();
+        host.limitations = Some(vec![
+            Limitation::Top { setting: "threads".to_string(), limit: 4 },
+            Limitation::Top { setting: "memory".to_string(), limit: 4 },
+            Limitation::Bottom { setting: "threads".to_string(), limit: 0 },
+        ]);
+        let strict: usize = host.add(test_proxy(TestFreight {
+            schema: Some(schema),
+            ..test_freight("strict", Vec::new(), &log)
+        }));
+        let open: usize =
+            host.add(test_proxy(test_freight("open", Vec::new(), &log)));
+        host.init_all().unwrap();
+
+        // Only the plugin, that declares a schema, gets the
+        // limitations filtered
+        assert!(log.borrow().contains(
+                &"strict limited [threads <= 4]".to_string()));
+        assert!(log.borrow().contains(
+                &"open limited [threads <= 4, memory <= 4, threads >= 0]"
+                    .to_string()));
+        let rejected: Vec<RejectedLimitation> =
+            host.get_rejected_limitations(strict).unwrap();
+        assert_eq!(rejected.len(), 2);
+        assert_eq!(rejected[0].reason, LimitationRejection::UnknownSetting);
+        assert_eq!(
+            rejected[1].reason,
+            LimitationRejection::BelowMinimum { min: 1 });
+        assert!(host.get_rejected_limitations(open).unwrap().is_empty());
+        assert!(host.get_rejected_limitations(2).is_err());
+        assert_eq!(
+            host.all_rejected_limitations().iter()
+                .map(|(id, _)| *id)
+                .collect::<Vec<usize>>(),
+            vec![strict, strict]);
+
+        // The proxy forgets them, once the limitations fit
+        let proxy: std::rc::Rc<FreightProxy> = host.get_by_id(strict).unwrap();
+        assert_eq!(proxy.get_rejected_limitations().len(), 2);
+        proxy.clone().update_limitations(&vec![
+            Limitation::Top { setting: "threads".to_string(), limit: 2 },
+        ]);
+        assert!(proxy.get_rejected_limitations().is_empty());
+    }
+
+    #[test]
+    fn shutdown_order () {
+        let log: std::rc::Rc<std::cell::RefCell<Vec<String>>> =
+            std::rc::Rc::default();
+        let mut host: PluginHost = PluginHost::new();
+
+        // The dependents are added before the plugins they depend on,
+        // so shutting down in reverse order would be wrong
+        host.add(test_proxy(test_freight(
+                    "top", vec![request_all("middle")], &log)));
+        host.add(test_proxy(test_freight(
+                    "middle", vec![request_all("bottom")], &log)));
+        host.add(test_proxy(test_freight("bottom", Vec::new(), &log)));
+        let never: usize =
+            host.add(test_proxy(test_freight("never", Vec::new(), &log)));
+        for id in 0..never {
+            host.init_plugin(id).unwrap();
+        }
+
+        // Plugins, that were never initialized, are not shut down
+        log.borrow_mut().clear();
+        drop(host);
+        assert_eq!(
+            *log.borrow(),
+            vec![
+                "top shutdown".to_string(),
+                "middle shutdown".to_string(),
+                "bottom shutdown".to_string(),
+            ]);
     }
 }
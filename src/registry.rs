@@ -0,0 +1,141 @@
+// Copyright (C) 2021 by Andy Gozas <andy@gozas.me>
+//
+// This file is part of Dusk API.
+//
+// Dusk API is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Dusk API is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Dusk API.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Module, containing the registry of plugins, that are linked into
+//! the program statically, for the systems that do not allow runtime
+//! dynamic library loading
+//!
+//! A plugin crate, exported with [`export_static_freight!`] instead
+//! of [`export_freight!`], adds its declaration to
+//! [`STATIC_FREIGHTS`] when it is linked into the program. The
+//! declarations are collected by the linker, so the program does not
+//! need to know what plugins it was linked with. They can then be
+//! listed with [`PluginDiscovery`] (see
+//! [`PluginDiscovery::include_static`]) and loaded with
+//! [`FreightProxy::load_static`] or [`PluginHost::load_static`],
+//! without any library being opened.
+
+use crate::*;
+
+/// Functions, returning the declarations of every plugin, exported
+/// with [`export_static_freight!`] and linked into the program
+#[::linkme::distributed_slice]
+pub static STATIC_FREIGHTS: [fn () -> FreightDeclaration];
+
+/// Get the declarations of all the plugins, statically linked into
+/// the program
+pub fn static_declarations () -> Vec<FreightDeclaration> {
+    STATIC_FREIGHTS.iter()
+        .map(|declaration| declaration())
+        .collect()
+}
+
+/// Find the declaration of a statically linked plugin by its name
+/// and, if provided, its version. If the version is not provided,
+/// the latest version is chosen
+pub fn find_static_declaration (
+    name: &str,
+    version: Option<&Version>,
+) -> Option<FreightDeclaration> {
+
+    let mut result: Option<FreightDeclaration> = None;
+    for declaration in static_declarations() {
+        if declaration.name != name {
+            continue;
+        }
+        match version {
+            Some(version) if declaration.freight_version != *version =>
+                continue,
+            _ => (),
+        }
+        result = match result {
+            Some(found) if !declaration.freight_version.is_preferred_to(
+                &found.freight_version) => Some(found),
+            _ => Some(declaration),
+        };
+    }
+    return result;
+}
+
+/// A macro, used instead of [`export_freight!`] to export a plugin,
+/// that is going to be linked into the program statically
+///
+/// Unlike [`export_freight!`], this macro does not export any symbol
+/// with a fixed name, so any amount of plugins may be linked into the
+/// same program. The arguments are exactly the same.
+///
+/// # Example
+/// ``` rust, ignore
+/// dusk_api::export_static_freight!(
+///     "test",
+///     Version {major: 1, minor: 23, ..Default::default() },
+///     register);
+///
+/// pub fn register (registrar: &mut dyn FreightRegistrar) {
+///     registrar.register_freight(Box::new(MyFreight));
+/// }
+/// ```
+#[macro_export]
+macro_rules! export_static_freight {
+    ($name:expr, $version:expr, $register:expr) => {
+        $crate::export_static_freight!($name, $version, $version, $register);
+    };
+    ($name:expr, $version:expr, $back_version:expr, $register:expr) => {
+        #[doc(hidden)]
+        #[$crate::linkme::distributed_slice($crate::STATIC_FREIGHTS)]
+        #[linkme(crate = $crate::linkme)]
+        static STATIC_FREIGHT_DECLARATION:
+            fn () -> $crate::FreightDeclaration = {
+                fn declaration () -> $crate::FreightDeclaration {
+                    $crate::FreightDeclaration {
+                        rustc_version: $crate::RUSTC_VERSION.to_string(),
+                        api_version: $crate::API_VERSION.to_string(),
+                        layout_fingerprint: $crate::LAYOUT_FINGERPRINT,
+                        freight_version: $version,
+                        backwards_compat_version: $back_version,
+                        name: $name.to_string(),
+                        register: $register,
+                    }
+                }
+                declaration
+            };
+    };
+}
+
+/// A macro, used instead of [`export_plugin!`] to export a plugin,
+/// that is going to be linked into the program statically (see
+/// [`export_static_freight!`])
+///
+/// # Example
+///
+/// ``` rust, ignore
+/// dusk_api::export_static_plugin!(
+///     "test",
+///     Version {major: 1, minor: 23, ..Default::default() },
+///     MyFreight);
+/// ```
+#[macro_export]
+macro_rules! export_static_plugin {
+    ($name: expr, $version: expr, $freight: ident) => {
+        $crate::register_freight!($freight, freight_registry_function);
+        $crate::export_static_freight!($name, $version, freight_registry_function);
+    };
+    ($name: expr, $version: expr, $back_version: expr, $freight: ident) => {
+        $crate::register_freight!($freight, freight_registry_function);
+        $crate::export_static_freight!($name, $version, $back_version, freight_registry_function);
+    };
+}
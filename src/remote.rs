@@ -0,0 +1,1026 @@
+// Copyright (C) 2021 by Andy Gozas <andy@gozas.me>
+//
+// This file is part of Dusk API.
+//
+// Dusk API is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Dusk API is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Dusk API.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Module, containing everything needed to use a [`Freight`], that
+//! can not be called directly, because it was built with a
+//! different compiler or is located in another process
+//!
+//! Every call to such a freight is encoded into bytes, passed
+//! through a [`FreightChannel`] to a [`FreightServer`] on the
+//! other side, which decodes it, calls the actual freight, and
+//! encodes the result the same way. [`RemoteFreight`] is the
+//! [`Freight`] implementor, that does the encoding on the calling
+//! side.
+//!
+//! [`Object`] values are passed using [`DkDump`] and [`DkLoad`].
+//! On the receiving side they are rebuilt with the [`Type`] of the
+//! same name and ID, if one was registered with
+//! [`RemoteFreight::register_type`] or
+//! [`FreightServer::register_type`], or kept as bytes in a
+//! [`ForeignObject`] otherwise.
+
+use crate::*;
+use crate::wire::*;
+
+/// Code of the [`Freight::init`] call
+const CALL_INIT: u8 = 0;
+
+/// Code of the [`Freight::update_limitations`] call
+const CALL_UPDATE_LIMITATIONS: u8 = 1;
+
+/// Code of the [`Freight::interplug_provide`] call
+const CALL_INTERPLUG_PROVIDE: u8 = 2;
+
+/// Code of the [`Freight::interplug_deny`] call
+const CALL_INTERPLUG_DENY: u8 = 3;
+
+/// Code of the [`Freight::interplug_replace`] call
+const CALL_INTERPLUG_REPLACE: u8 = 4;
+
+/// Code of the [`Freight::export_state`] call
+const CALL_EXPORT_STATE: u8 = 5;
+
+/// Code of the [`Freight::import_state`] call
+const CALL_IMPORT_STATE: u8 = 6;
+
+/// Code of the [`Freight::get_root_modules`] call
+const CALL_GET_ROOT_MODULES: u8 = 7;
+
+/// Code of the [`Freight::get_operator_list`] call
+const CALL_GET_OPERATOR_LIST: u8 = 8;
+
+/// Code of a call to a [`DuskCallable`] of one of the functions
+const CALL_FUNCTION: u8 = 9;
+
+/// Code of the [`Freight::interplug_withdraw`] call
+const CALL_INTERPLUG_WITHDRAW: u8 = 10;
+
+/// Code of the [`Freight::shutdown`] call
+const CALL_SHUTDOWN: u8 = 11;
+
+/// Code of the [`Freight::get_limitation_schema`] call
+const CALL_GET_LIMITATION_SCHEMA: u8 = 12;
+
+/// Code of the [`Freight::get_effective_limitations`] call
+const CALL_GET_EFFECTIVE_LIMITATIONS: u8 = 13;
+
+/// Trait, implemented for everything an encoded call to a
+/// [`FreightServer`] can be passed through
+pub trait FreightChannel {
+
+    /// Pass the encoded call to the [`FreightServer`] on the other
+    /// side and return its encoded reply
+    fn exchange (
+        self: &mut Self,
+        request: Vec<u8>,
+    ) -> Result<Vec<u8>, Error>;
+
+    /// Make the proxy provided usable from the other side of the
+    /// channel, returning the handle the [`FreightServer`] there
+    /// opens it by
+    ///
+    /// By default plugins can not be shared through a channel, so
+    /// every [`Freight::interplug_provide`] call is turned into a
+    /// [`Freight::interplug_deny`] one
+    fn share_proxy (
+        self: &mut Self,
+        _proxy: std::rc::Rc<FreightProxy>,
+    ) -> Result<Vec<u8>, Error> {
+
+        Err(NotImplementedError(
+                "Plugins can not be shared through this channel"
+                .to_string()
+        ))
+    }
+}
+
+impl std::fmt::Debug for dyn FreightChannel {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        f.pad("FreightChannel")
+    }
+}
+
+/// The largest number of types, that are made up for objects of
+/// unknown types on each thread. Objects of any other unknown type
+/// are rejected, so the other side of a channel can not make this
+/// one leak memory without a limit
+pub(crate) const MAX_FOREIGN_TYPES: usize = 4096;
+
+thread_local! {
+    /// Types, that were made up for objects of unknown types. They
+    /// have to live as long as the objects built with them, which may
+    /// outlive any channel, so they are leaked, but only once for each
+    /// name and ID, no matter how many channels pass them
+    static FOREIGN_TYPES: std::cell::RefCell<Vec<&'static Type>> =
+        const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Get the type, made up for the objects of an unknown type with the
+/// name and ID provided, making it up if it was not made up yet
+fn foreign_type (
+    name: String,
+    tp_id: usize,
+) -> Result<&'static Type, Error> {
+
+    FOREIGN_TYPES.with(|types| {
+        let mut types: std::cell::RefMut<'_, Vec<&'static Type>> =
+            types.borrow_mut();
+        if let Some(data_type) = types.iter()
+            .find(|tp| tp.name == name && tp.tp_id == tp_id) {
+
+            return Ok(*data_type);
+        }
+        if types.len() >= MAX_FOREIGN_TYPES {
+            return Err(ValueError(
+                    format!(
+                        "Too many unknown types were passed, {} is rejected",
+                        name,
+                    )));
+        }
+        let data_type: &'static Type = Box::leak(Box::new(Type {
+            name: name,
+            tp_id: tp_id,
+            generator: foreign_generator,
+            native_id: TypeId::of::<ForeignObject>(),
+            ..Default::default()
+        }));
+        types.push(data_type);
+        return Ok(data_type);
+    })
+}
+
+/// A table of types, used to rebuild the [`Object`] values, passed
+/// through a [`FreightChannel`]
+#[derive(Debug, Default)]
+pub(crate) struct TypeTable {
+
+    /// Types, objects may be rebuilt with
+    types: Vec<&'static Type>,
+
+    /// Types, that were added by [`TypeTable::register_modules`] and
+    /// are owned by the table, freed when it is dropped
+    owned: Vec<*mut Type>,
+}
+
+impl TypeTable {
+
+    /// Add a type, objects may be rebuilt with
+    pub(crate) fn register (
+        self: &mut Self,
+        data_type: &'static Type,
+    ) {
+
+        self.types.push(data_type);
+    }
+
+    /// Add every type, defined in the modules provided or any of
+    /// their submodules
+    ///
+    /// The types are owned by the table and freed when it is dropped
+    ///
+    /// # Safety
+    ///
+    /// Every object, built with the table, has to be dropped before
+    /// the table is, as it refers to the type it was built with
+    pub(crate) unsafe fn register_modules (
+        self: &mut Self,
+        modules: Vec<Module>,
+    ) {
+
+        for module in modules {
+            for data_type in module.types {
+                let data_type: *mut Type = Box::into_raw(Box::new(data_type));
+                self.owned.push(data_type);
+                // SAFETY: the type is only freed when the table is
+                // dropped, and the caller guarantees nothing refers to
+                // it by then
+                self.register(&*data_type);
+            }
+            self.register_modules(module.submodules);
+        }
+    }
+
+    /// Rebuild an object from its type name, type ID, flags and the
+    /// data, gotten from [`DkDump::dk_dump`]
+    ///
+    /// Objects of the types, that are not in the table, are kept as
+    /// a [`ForeignObject`], failing if there are more than
+    /// [`MAX_FOREIGN_TYPES`] of such types already
+    pub(crate) fn build_object (
+        self: &mut Self,
+        name: String,
+        tp_id: usize,
+        flags: u32,
+        data: Vec<u8>,
+    ) -> Result<Object, Error> {
+
+        for data_type in &self.types {
+            if data_type.name == name && data_type.tp_id == tp_id {
+                let mut result: Box<dyn DkAny> = (data_type.generator)()?;
+                result.dk_load(data, &mut 0)?;
+                return Ok(Object::new(result, data_type, flags));
+            }
+        }
+
+        let data_type: &'static Type = foreign_type(name, tp_id)?;
+        return Ok(Object::new(
+                Box::new(ForeignObject::new(data)),
+                data_type,
+                flags,
+        ));
+    }
+}
+
+impl Drop for TypeTable {
+    fn drop (self: &mut Self) {
+        self.types.clear();
+        for data_type in self.owned.drain(..) {
+            // SAFETY: the pointer was gotten from `Box::into_raw` in
+            // `TypeTable::register_modules` and is only freed here
+            drop(unsafe { Box::from_raw(data_type) });
+        }
+    }
+}
+
+/// Generator of the types, that were passed through a
+/// [`FreightChannel`], as their objects can not be created on this
+/// side
+pub(crate) fn foreign_generator () -> Result<Box<dyn DkAny>, Error> {
+    Err(NotImplementedError(
+            "Objects of a foreign type can not be created".to_string()
+    ))
+}
+
+/// An object of a type, that is not known on this side of a
+/// [`FreightChannel`]
+///
+/// It holds the data the object was dumped into, so it can be
+/// passed back unchanged
+#[derive(Debug)]
+pub struct ForeignObject {
+    dk_obj_core: ObjCore,
+
+    /// The data, gotten from [`DkDump::dk_dump`] on the other side
+    pub data: Vec<u8>,
+}
+
+impl ForeignObject {
+
+    /// Create an object holding the data provided
+    pub fn new (data: Vec<u8>) -> ForeignObject {
+        ForeignObject {
+            dk_obj_core: ObjCore::new(),
+            data: data,
+        }
+    }
+}
+
+impl Clone for ForeignObject {
+    fn clone (
+        self: &ForeignObject,
+    ) -> ForeignObject {
+
+        ForeignObject::new(self.data.clone())
+    }
+}
+
+impl DkGen for ForeignObject {
+    fn dk_new (_freight: Object) -> Result<Box<dyn DkAny>, Error> {
+        foreign_generator()
+    }
+}
+
+impl DkRefCount for ForeignObject {
+    fn dk_incref (
+        self: &Self,
+    ) -> Result<usize, Error> {
+
+        self.dk_obj_core.incref()
+    }
+
+    fn dk_decref (
+        self: &Self,
+    ) -> Result<usize, Error> {
+
+        self.dk_obj_core.decref()
+    }
+}
+
+impl DkRWLock for ForeignObject {
+    fn dk_lock_ex (
+        self: &Self,
+    ) -> Result<(), Error> {
+
+        self.dk_obj_core.lock_ex()
+    }
+
+    fn dk_try_lock_ex (
+        self: &Self,
+    ) -> Result<bool, Error> {
+
+        self.dk_obj_core.try_lock_ex()
+    }
+
+    fn dk_lock (
+        self: &Self,
+    ) -> Result<(), Error> {
+
+        self.dk_obj_core.lock()
+    }
+
+    fn dk_try_lock (
+        self: &Self,
+    ) -> Result<bool, Error> {
+
+        self.dk_obj_core.try_lock()
+    }
+
+    fn dk_unlock (
+        self: &Self,
+    ) -> Result<(), Error> {
+
+        self.dk_obj_core.unlock()
+    }
+}
+
+impl DkGet for ForeignObject {
+    fn dk_get (
+        self: &Self,
+    ) -> Result<Box<dyn DkAny>, Error> {
+
+        Ok(Box::new(self.clone()))
+    }
+}
+
+impl DkSet for ForeignObject {
+    fn dk_set (
+        self: &mut Self,
+        new_data: &Box<dyn DkAny>,
+    ) -> Result<(), Error> {
+
+        let new_data_any: &dyn Any = &**new_data as &dyn Any;
+        match new_data_any.downcast_ref::<ForeignObject>() {
+            Some(new_data_object) => {
+                self.data = new_data_object.data.clone();
+                return Ok(());
+            },
+            _ => {
+                return Err(TypeError(
+                        "Expected an object of a foreign type".to_string()
+                ));
+            },
+        }
+    }
+}
+
+impl DkDump for ForeignObject {
+    fn dk_dump (
+        self: &Self,
+    ) -> Result<Vec<u8>, Error> {
+
+        Ok(self.data.clone())
+    }
+}
+
+impl DkLoad for ForeignObject {
+    fn dk_load (
+        self: &mut Self,
+        new_data: Vec<u8>,
+        cursor: &mut usize,
+    ) -> Result<(), Error> {
+
+        if *cursor > new_data.len() {
+            return Err(IndexError(
+                    "Cursor is out of the data bounds".to_string()
+            ));
+        }
+        self.data = new_data[*cursor..].to_vec();
+        *cursor = new_data.len();
+        Ok(())
+    }
+}
+
+/// Encode a call, pass it through the channel and decode the reply
+fn remote_call<T> (
+    channel: &std::cell::RefCell<Box<dyn FreightChannel>>,
+    types: &std::cell::RefCell<TypeTable>,
+    request: WireWriter,
+) -> Result<T, Error> where T: Wire {
+
+    let reply: Vec<u8> = match channel.try_borrow_mut() {
+        Ok(mut channel) => channel.exchange(request.buffer)?,
+        Err(_) => return Err(RuntimeError(
+                "Remote freight called from inside of its own call"
+                .to_string()
+        )),
+    };
+    let mut types = types.borrow_mut();
+    let mut reader: WireReader<'_> = WireReader::new(&reply, &mut types);
+    let result: Result<T, Error> = reader.read()?;
+    return result;
+}
+
+/// A [`Freight`] implementor, that passes every call through a
+/// [`FreightChannel`] to a [`FreightServer`] on the other side
+///
+/// As most of the [`Freight`] functions can not return an error,
+/// the ones that fail act as if the plugin was empty. The error
+/// can then be gotten with [`RemoteFreight::take_error`].
+///
+/// # Example
+///
+/// ``` rust, ignore
+/// let freight: RemoteFreight = RemoteFreight::new(Box::new(channel));
+/// let mut proxy: FreightProxy = FreightProxy::new(
+///     "test".to_string(),
+///     Version { major: 1, ..Default::default() },
+///     Version { major: 1, ..Default::default() },
+///     Box::new(freight));
+/// ```
+#[derive(Debug)]
+pub struct RemoteFreight {
+
+    /// Channel, shared with every callable gotten from the freight
+    channel: std::rc::Rc<std::cell::RefCell<Box<dyn FreightChannel>>>,
+
+    /// Types, the objects passed back are rebuilt with
+    types: std::rc::Rc<std::cell::RefCell<TypeTable>>,
+
+    /// The last error, that could not be returned
+    error: Option<Error>,
+}
+
+impl RemoteFreight {
+
+    /// Create a freight, that passes every call through the channel
+    /// provided
+    pub fn new (channel: Box<dyn FreightChannel>) -> RemoteFreight {
+        RemoteFreight {
+            channel: std::rc::Rc::new(std::cell::RefCell::new(channel)),
+            types: std::rc::Rc::new(
+                std::cell::RefCell::new(TypeTable::default())),
+            error: None,
+        }
+    }
+
+    /// Register a type, the objects of which may be passed back by
+    /// the freight, so they are rebuilt with it instead of being
+    /// kept as a [`ForeignObject`]
+    pub fn register_type (
+        self: &mut Self,
+        data_type: &'static Type,
+    ) {
+
+        self.types.borrow_mut().register(data_type);
+    }
+
+    /// Take the last error, that happened in a function, that could
+    /// not return it
+    pub fn take_error (self: &mut Self) -> Option<Error> {
+        self.error.take()
+    }
+
+    /// Encode the call code and pass the call through the channel
+    fn call<T> (
+        self: &Self,
+        code: u8,
+        write: impl FnOnce(&mut WireWriter) -> Result<(), Error>,
+    ) -> Result<T, Error> where T: Wire {
+
+        let mut request: WireWriter = WireWriter::new();
+        request.write(&code)?;
+        write(&mut request)?;
+        remote_call(&self.channel, &self.types, request)
+    }
+
+    /// Remember the error, if there is one, returning the value or
+    /// the default one
+    fn unwrap_or_store<T> (
+        self: &mut Self,
+        result: Result<T, Error>,
+    ) -> T where T: Default {
+
+        match result {
+            Ok(value) => value,
+            Err(err) => {
+                self.error = Some(err);
+                T::default()
+            },
+        }
+    }
+
+    /// Build a callable, that calls the function with the ID
+    /// provided on the other side
+    fn remote_callable (
+        self: &Self,
+        fn_id: usize,
+    ) -> Box<dyn DuskCallable> {
+
+        Box::new(RemoteCallable {
+            channel: self.channel.clone(),
+            types: self.types.clone(),
+            fn_id: fn_id,
+        })
+    }
+
+    /// Make the functions provided call the freight on the other side
+    fn attach_callables (
+        self: &Self,
+        functions: &mut Vec<Function>,
+    ) {
+
+        for function in functions {
+            function.callable = self.remote_callable(function.fn_id);
+        }
+    }
+
+    /// Make every function in the modules provided and all of their
+    /// submodules call the freight on the other side
+    fn attach_module_callables (
+        self: &Self,
+        modules: &mut Vec<Module>,
+    ) {
+
+        for module in modules {
+            self.attach_callables(&mut module.functions);
+            self.attach_callables(&mut module.constants);
+            for data_type in &mut module.types {
+                self.attach_callables(&mut data_type.methods);
+                self.attach_callables(&mut data_type.fields);
+                for implementation in &mut data_type.trait_implementations {
+                    for method in &mut implementation.methods {
+                        method.function.callable =
+                            self.remote_callable(method.function.fn_id);
+                    }
+                }
+            }
+            self.attach_module_callables(&mut module.submodules);
+        }
+    }
+
+    /// Provide the proxy to the freight on the other side with the
+    /// call code provided, or deny the request if the proxy can not
+    /// be shared through the channel
+    fn provide (
+        self: &mut Self,
+        code: u8,
+        request: InterplugRequest,
+        freight_proxy: std::rc::Rc<FreightProxy>,
+    ) {
+
+        let handle: Result<Vec<u8>, Error> = match self.channel.try_borrow_mut() {
+            Ok(mut channel) => channel.share_proxy(freight_proxy.clone()),
+            Err(_) => Err(RuntimeError(
+                    "Remote freight called from inside of its own call"
+                    .to_string()
+            )),
+        };
+        let result: Result<(), Error> = match handle {
+            Ok(handle) => self.call(code, |writer| {
+                writer.write(&request)?;
+                writer.write(&freight_proxy.name)?;
+                writer.write(&freight_proxy.version)?;
+                writer.write(&freight_proxy.backwards_compat_version)?;
+                writer.write(&handle)
+            }),
+            Err(_) => self.call(CALL_INTERPLUG_DENY, |writer| {
+                writer.write(&request)
+            }),
+        };
+        self.unwrap_or_store(result);
+    }
+}
+
+impl Freight for RemoteFreight {
+    fn init (
+        self: &mut Self,
+        limitations: &Option<Vec<Limitation>>,
+    ) -> Vec<InterplugRequest> {
+
+        let result: Result<Vec<InterplugRequest>, Error> =
+            self.call(CALL_INIT, |writer| writer.write(limitations));
+        self.unwrap_or_store(result)
+    }
+
+    fn update_limitations (
+        self: &mut Self,
+        limitations: &Vec<Limitation>,
+    ) {
+
+        let result: Result<(), Error> =
+            self.call(CALL_UPDATE_LIMITATIONS, |writer| {
+                writer.write(limitations)
+            });
+        self.unwrap_or_store(result)
+    }
+
+    fn get_limitation_schema (
+        self: &mut Self,
+    ) -> Option<LimitationSchema> {
+
+        let result: Result<Option<LimitationSchema>, Error> =
+            self.call(CALL_GET_LIMITATION_SCHEMA, |_writer| Ok(()));
+        self.unwrap_or_store(result)
+    }
+
+    fn get_effective_limitations (
+        self: &mut Self,
+    ) -> Option<Vec<EffectiveSetting>> {
+
+        let result: Result<Option<Vec<EffectiveSetting>>, Error> =
+            self.call(CALL_GET_EFFECTIVE_LIMITATIONS, |_writer| Ok(()));
+        self.unwrap_or_store(result)
+    }
+
+    fn interplug_provide (
+        self: &mut Self,
+        request: InterplugRequest,
+        freight_proxy: std::rc::Rc<FreightProxy>,
+    ) {
+
+        self.provide(CALL_INTERPLUG_PROVIDE, request, freight_proxy);
+    }
+
+    fn interplug_deny (
+        self: &mut Self,
+        request: InterplugRequest,
+    ) {
+
+        let result: Result<(), Error> =
+            self.call(CALL_INTERPLUG_DENY, |writer| writer.write(&request));
+        self.unwrap_or_store(result)
+    }
+
+    fn interplug_replace (
+        self: &mut Self,
+        request: InterplugRequest,
+        freight_proxy: std::rc::Rc<FreightProxy>,
+    ) {
+
+        self.provide(CALL_INTERPLUG_REPLACE, request, freight_proxy);
+    }
+
+    fn interplug_withdraw (
+        self: &mut Self,
+        request: InterplugRequest,
+    ) {
+
+        let result: Result<(), Error> =
+            self.call(CALL_INTERPLUG_WITHDRAW, |writer| writer.write(&request));
+        self.unwrap_or_store(result)
+    }
+
+    fn shutdown (self: &mut Self) {
+        let result: Result<(), Error> =
+            self.call(CALL_SHUTDOWN, |_writer| Ok(()));
+        self.unwrap_or_store(result)
+    }
+
+    fn export_state (
+        self: &mut Self,
+    ) -> Result<Option<Vec<u8>>, Error> {
+
+        self.call(CALL_EXPORT_STATE, |_writer| Ok(()))
+    }
+
+    fn import_state (
+        self: &mut Self,
+        state: Vec<u8>,
+    ) -> Result<(), Error> {
+
+        self.call(CALL_IMPORT_STATE, |writer| writer.write(&state))
+    }
+
+    fn get_root_modules (self: &mut Self) -> Vec<Module> {
+        let result: Result<Vec<Module>, Error> =
+            self.call(CALL_GET_ROOT_MODULES, |_writer| Ok(()));
+        let mut modules: Vec<Module> = self.unwrap_or_store(result);
+        self.attach_module_callables(&mut modules);
+        return modules;
+    }
+
+    fn get_operator_list (self: &mut Self) -> Vec<Function> {
+        let result: Result<Vec<Function>, Error> =
+            self.call(CALL_GET_OPERATOR_LIST, |_writer| Ok(()));
+        let mut operators: Vec<Function> = self.unwrap_or_store(result);
+        self.attach_callables(&mut operators);
+        return operators;
+    }
+}
+
+/// Dusk callable, that calls a function of a [`RemoteFreight`] on
+/// the other side of its [`FreightChannel`]
+#[derive(Clone)]
+pub struct RemoteCallable {
+    channel: std::rc::Rc<std::cell::RefCell<Box<dyn FreightChannel>>>,
+    types: std::rc::Rc<std::cell::RefCell<TypeTable>>,
+    fn_id: usize,
+}
+
+impl DuskCallable for RemoteCallable {
+    fn call (
+        self: &mut Self,
+        args: Vec<Object>
+    ) -> Result<Object, Error> {
+
+        let mut request: WireWriter = WireWriter::new();
+        request.write(&CALL_FUNCTION)?;
+        request.write(&self.fn_id)?;
+        request.write(&args)?;
+        remote_call(&self.channel, &self.types, request)
+    }
+}
+
+impl std::fmt::Debug for RemoteCallable {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        f.debug_struct("RemoteCallable")
+            .field("fn_id", &self.fn_id)
+            .finish()
+    }
+}
+
+/// Function, used by a [`FreightServer`] to open a proxy, shared
+/// through a [`FreightChannel`], by its handle
+pub type ProxyOpener = fn (&[u8]) -> Result<Box<dyn Freight>, Error>;
+
+/// Function, used by default to open a proxy, shared through a
+/// [`FreightChannel`], which always fails
+fn no_shared_proxies (
+    _handle: &[u8],
+) -> Result<Box<dyn Freight>, Error> {
+
+    Err(NotImplementedError(
+            "Plugins can not be shared through this channel".to_string()
+    ))
+}
+
+/// A structure, that decodes the calls made by a [`RemoteFreight`],
+/// calls the actual [`Freight`] and encodes the results
+///
+/// A panic inside of the freight is caught and passed back as an
+/// [`Error::RuntimeError`]
+pub struct FreightServer {
+
+    /// The actual freight
+    ///
+    /// Declared before the types, so that it is dropped first, along
+    /// with any object, passed to it, that it kept
+    freight: Box<dyn Freight>,
+
+    /// Types, the objects passed as arguments are rebuilt with.
+    /// Built from the freight modules on the first function call
+    types: Option<TypeTable>,
+
+    /// Types, registered before the table was built
+    registered: Vec<&'static Type>,
+
+    /// Function, used to open the proxies, shared through the
+    /// channel, by their handles
+    open_proxy: ProxyOpener,
+}
+
+impl FreightServer {
+
+    /// Create a server for the freight provided
+    pub fn new (freight: Box<dyn Freight>) -> FreightServer {
+        FreightServer::with_proxy_opener(freight, no_shared_proxies)
+    }
+
+    /// Create a server for the freight provided, that opens the
+    /// proxies, shared with [`FreightChannel::share_proxy`] on the
+    /// other side, with the function provided
+    pub fn with_proxy_opener (
+        freight: Box<dyn Freight>,
+        open_proxy: ProxyOpener,
+    ) -> FreightServer {
+
+        FreightServer {
+            freight: freight,
+            types: None,
+            registered: Vec::new(),
+            open_proxy: open_proxy,
+        }
+    }
+
+    /// Register a type, the objects of which may be passed as
+    /// arguments, in addition to the ones defined by the freight
+    pub fn register_type (
+        self: &mut Self,
+        data_type: &'static Type,
+    ) {
+
+        match &mut self.types {
+            Some(types) => types.register(data_type),
+            None => self.registered.push(data_type),
+        }
+    }
+
+    /// Handle an encoded call, returning the encoded reply
+    pub fn handle (
+        self: &mut Self,
+        request: &[u8],
+    ) -> Vec<u8> {
+
+        let result: std::thread::Result<Result<Vec<u8>, Error>> =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                self.dispatch(request)
+            }));
+
+        let error: Error = match result {
+            Ok(Ok(reply)) => return reply,
+            Ok(Err(err)) => err,
+            Err(_) => RuntimeError(
+                "Plugin panicked while handling the call".to_string()
+            ),
+        };
+        let mut writer: WireWriter = WireWriter::new();
+        match writer.write(&Err::<(), Error>(error)) {
+            Ok(()) => writer.buffer,
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Get the type table, building it if it was not built yet
+    fn types (self: &mut Self) -> &mut TypeTable {
+        if self.types.is_none() {
+            let mut types: TypeTable = TypeTable::default();
+            for data_type in self.registered.drain(..) {
+                types.register(data_type);
+            }
+            // SAFETY: the objects, built with the table, are only
+            // passed to the freight, which is dropped before the table
+            unsafe { types.register_modules(self.freight.get_root_modules()) };
+            self.types = Some(types);
+        }
+        match &mut self.types {
+            Some(types) => types,
+            None => unreachable!(),
+        }
+    }
+
+    /// Decode and perform the call, encoding the result
+    fn dispatch (
+        self: &mut Self,
+        request: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+
+        // Objects are only passed to this side as function arguments
+        // so the type table is only needed for those calls
+        if request.first() == Some(&CALL_FUNCTION) {
+            let (fn_id, args): (usize, Vec<Object>) =
+                decode_function_call(request, self.types())?;
+            let mut callable: Box<dyn DuskCallable> =
+                self.freight.get_callable_by_id(fn_id)?;
+            return encode(&callable.call(args));
+        }
+
+        let mut types: TypeTable = TypeTable::default();
+        let mut reader: WireReader<'_> = WireReader::new(request, &mut types);
+        match reader.read::<u8>()? {
+            CALL_INIT => {
+                let limitations: Option<Vec<Limitation>> = reader.read()?;
+                encode(&Ok(self.freight.init(&limitations)))
+            },
+            CALL_UPDATE_LIMITATIONS => {
+                let limitations: Vec<Limitation> = reader.read()?;
+                self.freight.update_limitations(&limitations);
+                encode(&Ok(()))
+            },
+            CALL_GET_LIMITATION_SCHEMA => {
+                encode(&Ok(self.freight.get_limitation_schema()))
+            },
+            CALL_GET_EFFECTIVE_LIMITATIONS => {
+                encode(&Ok(self.freight.get_effective_limitations()))
+            },
+            code @ CALL_INTERPLUG_PROVIDE | code @ CALL_INTERPLUG_REPLACE => {
+                let request: InterplugRequest = reader.read()?;
+                let name: String = reader.read()?;
+                let version: Version = reader.read()?;
+                let backwards_compat_version: Version = reader.read()?;
+                let handle: Vec<u8> = reader.read()?;
+                let proxy: std::rc::Rc<FreightProxy> =
+                    std::rc::Rc::new(FreightProxy::new(
+                            name,
+                            version,
+                            backwards_compat_version,
+                            (self.open_proxy)(&handle)?,
+                    ));
+                if code == CALL_INTERPLUG_PROVIDE {
+                    self.freight.interplug_provide(request, proxy);
+                } else {
+                    self.freight.interplug_replace(request, proxy);
+                }
+                encode(&Ok(()))
+            },
+            CALL_INTERPLUG_DENY => {
+                let request: InterplugRequest = reader.read()?;
+                self.freight.interplug_deny(request);
+                encode(&Ok(()))
+            },
+            CALL_INTERPLUG_WITHDRAW => {
+                let request: InterplugRequest = reader.read()?;
+                self.freight.interplug_withdraw(request);
+                encode(&Ok(()))
+            },
+            CALL_SHUTDOWN => {
+                self.freight.shutdown();
+                encode(&Ok(()))
+            },
+            CALL_EXPORT_STATE => {
+                encode(&self.freight.export_state())
+            },
+            CALL_IMPORT_STATE => {
+                let state: Vec<u8> = reader.read()?;
+                encode(&self.freight.import_state(state))
+            },
+            CALL_GET_ROOT_MODULES => {
+                encode(&Ok(self.freight.get_root_modules()))
+            },
+            CALL_GET_OPERATOR_LIST => {
+                encode(&Ok(self.freight.get_operator_list()))
+            },
+            code => Err(ValueError(
+                    format!(
+                        "Unknown call code {}",
+                        code,
+                    ))),
+        }
+    }
+}
+
+impl std::fmt::Debug for FreightServer {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        f.debug_struct("FreightServer")
+            .field("freight", &self.freight)
+            .finish()
+    }
+}
+
+/// Decode the function ID and the arguments of a function call
+fn decode_function_call (
+    request: &[u8],
+    types: &mut TypeTable,
+) -> Result<(usize, Vec<Object>), Error> {
+
+    let mut reader: WireReader<'_> = WireReader::new(request, types);
+    let _code: u8 = reader.read()?;
+    let fn_id: usize = reader.read()?;
+    let args: Vec<Object> = reader.read()?;
+    return Ok((fn_id, args));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn foreign_types () {
+        let mut first: TypeTable = TypeTable::default();
+        let mut second: TypeTable = TypeTable::default();
+        let object: Object = first.build_object(
+            "dusk-test-unknown".to_string(), 1, 0, vec![1, 2, 3]).unwrap();
+        let other: Object = second.build_object(
+            "dusk-test-unknown".to_string(), 1, 0, Vec::new()).unwrap();
+
+        // Every table reuses the same made up type
+        assert!(std::ptr::eq(object.get_type(), other.get_type()));
+        assert_eq!(object.get_type().name, "dusk-test-unknown");
+
+        for tp_id in 0..MAX_FOREIGN_TYPES {
+            let _ = first.build_object(
+                "dusk-test-many".to_string(), tp_id, 0, Vec::new());
+        }
+        assert!(first.build_object(
+                "dusk-test-rejected".to_string(), 0, 0, Vec::new()).is_err());
+
+        // Types, that were made up already, are still used
+        assert!(second.build_object(
+                "dusk-test-unknown".to_string(), 1, 0, Vec::new()).is_ok());
+    }
+}
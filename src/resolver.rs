@@ -0,0 +1,1225 @@
+// Copyright (C) 2021 by Andy Gozas <andy@gozas.me>
+//
+// This file is part of Dusk API.
+//
+// Dusk API is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Dusk API is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Dusk API.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Module, containing everything needed to decide which plugins
+//! should fulfill the interplugin requests, before any of them are
+//! actually provided
+
+use crate::*;
+
+/// A trait, implemented by some type of a plugin, that can be used
+/// to fulfill [`InterplugRequest::TraitRequest`] and
+/// [`InterplugRequest::TraitRequestAll`] requests
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImplementedTrait {
+
+    /// The plugin that defines the trait
+    pub plugin: String,
+
+    /// Trait identifier in that plugin
+    pub trait_id: usize,
+
+    /// Version of the plugin, that defines the trait
+    pub version: Version,
+
+    /// In trait IDs of the methods, that are implemented, or
+    /// [`None`] if every method is assumed to be implemented
+    pub fn_ids: Option<Vec<usize>>,
+}
+
+impl From<&TraitImplementation> for ImplementedTrait {
+    fn from (implementation: &TraitImplementation) -> ImplementedTrait {
+        ImplementedTrait {
+            plugin: implementation.plugin.clone(),
+            trait_id: implementation.trait_id,
+            version: implementation.version.clone(),
+            fn_ids: Some(implementation.methods.iter()
+                .map(|method| method.fn_trait_id as usize)
+                .collect()),
+        }
+    }
+}
+
+impl ImplementedTrait {
+
+    /// Check if the implementation could be used to fulfill the
+    /// request provided, returning every reason it could not
+    ///
+    /// The implementation fits a [`InterplugRequest::TraitRequest`] or
+    /// [`InterplugRequest::TraitRequestAll`] request, if it is made
+    /// for the same trait of a matching version of the plugin, that
+    /// defines it, and, for a [`InterplugRequest::TraitRequest`],
+    /// implements every method requested
+    pub fn check_request (
+        self: &Self,
+        request: &InterplugRequest,
+    ) -> Result<(), Vec<Rejection>> {
+
+        let (trait_id, fn_ids): (usize, &[usize]) = match request {
+            TraitRequest { trait_id, fn_ids, .. } => (*trait_id, fn_ids),
+            TraitRequestAll { trait_id, .. } => (*trait_id, &[]),
+            _ => return Err(vec![Rejection::UnsupportedRequest]),
+        };
+        if self.trait_id != trait_id {
+            return Err(vec![Rejection::NotImplemented]);
+        }
+
+        let mut reasons: Vec<Rejection> = match request.check_provider(
+            &self.plugin,
+            &self.version,
+            &self.version) {
+
+            Ok(()) => Vec::new(),
+            Err(reasons) => reasons,
+        };
+        check_functions(fn_ids, &self.fn_ids, &mut reasons);
+        if reasons.is_empty() {
+            return Ok(());
+        }
+        return Err(reasons);
+    }
+}
+
+/// A plugin, that may be used by the [`Resolver`] to fulfill the
+/// requests of other plugins
+#[derive(Clone, Debug)]
+pub struct AvailablePlugin {
+
+    /// The plugin name
+    pub name: String,
+
+    /// The plugin version
+    pub version: Version,
+
+    /// The earliest version, the plugin is compatible with
+    pub backwards_compat_version: Version,
+
+    /// Requests, returned by the plugin init function, which have
+    /// to be resolved as well if the plugin is used. Should be left
+    /// empty if the plugin was not initialized yet
+    pub requests: Vec<InterplugRequest>,
+
+    /// Traits, implemented by the types of the plugin
+    pub traits: Vec<ImplementedTrait>,
+
+    /// IDs of the functions, the plugin provides, or [`None`] if
+    /// every function requested is assumed to be provided
+    pub functions: Option<Vec<usize>>,
+}
+
+impl AvailablePlugin {
+
+    /// Create a plugin with no requests and no implemented traits
+    pub fn new (
+        name: String,
+        version: Version,
+        backwards_compat_version: Version,
+    ) -> AvailablePlugin {
+
+        AvailablePlugin {
+            name: name,
+            version: version,
+            backwards_compat_version: backwards_compat_version,
+            requests: Vec::new(),
+            traits: Vec::new(),
+            functions: None,
+        }
+    }
+
+    /// Check if the plugin could be used to fulfill the request
+    /// provided (see [`InterplugRequest::check_provider`]), returning
+    /// every reason it could not
+    ///
+    /// Trait requests are checked against every trait the plugin
+    /// implements with the same ID, and only the reasons for the
+    /// closest one are returned
+    pub fn check_request (
+        self: &Self,
+        request: &InterplugRequest,
+    ) -> Result<(), Vec<Rejection>> {
+
+        match request {
+            TraitRequest { .. } | TraitRequestAll { .. } => (),
+            PlugRequest { fn_ids, .. } => {
+                let mut reasons: Vec<Rejection> = match request.check_provider(
+                    &self.name,
+                    &self.version,
+                    &self.backwards_compat_version) {
+
+                    Ok(()) => Vec::new(),
+                    Err(reasons) => reasons,
+                };
+                check_functions(fn_ids, &self.functions, &mut reasons);
+                if reasons.is_empty() {
+                    return Ok(());
+                }
+                return Err(reasons);
+            },
+            _ => return request.check_provider(
+                &self.name,
+                &self.version,
+                &self.backwards_compat_version),
+        };
+
+        let mut closest: Vec<Rejection> = vec![Rejection::NotImplemented];
+        for implemented in &self.traits {
+            match implemented.check_request(request) {
+                Ok(()) => return Ok(()),
+                Err(reasons) => if reasons != [Rejection::NotImplemented] &&
+                    (closest == [Rejection::NotImplemented] ||
+                        reasons.len() < closest.len()) {
+
+                    closest = reasons;
+                },
+            }
+        }
+        return Err(closest);
+    }
+}
+
+/// A request, that was decided to be fulfilled
+#[derive(Clone, Debug)]
+pub struct Provision {
+
+    /// ID of the plugin, that made the request
+    pub requester: usize,
+
+    /// The request, either [`InterplugRequest::PlugRequest`],
+    /// [`InterplugRequest::PlugRequestAll`],
+    /// [`InterplugRequest::TraitRequest`] or
+    /// [`InterplugRequest::TraitRequestAll`]
+    pub request: InterplugRequest,
+
+    /// ID of the plugin, that should be provided to fulfill it
+    pub provider: usize,
+
+    /// Whether the provider depends on the requesting plugin, either
+    /// directly or through other plugins, so it should be provided as
+    /// a [`WeakFreightProxy`] once it is initialized, instead of
+    /// being initialized before the requesting plugin
+    pub weak: bool,
+}
+
+/// A request, that was decided to be denied
+#[derive(Clone, Debug)]
+pub struct Denial {
+
+    /// ID of the plugin, that made the request
+    pub requester: usize,
+
+    /// The request, exactly as it was made
+    pub request: InterplugRequest,
+}
+
+/// A plan of answering the requests of a plugin, made by the
+/// [`Resolver`]
+#[derive(Clone, Debug, Default)]
+pub struct Resolution {
+
+    /// IDs of all the plugins used, in the order they should be
+    /// initialized in: every plugin comes after all the plugins it
+    /// is provided with, except for the weak provisions. The plugin,
+    /// the requests of which were resolved, is the last one
+    pub order: Vec<usize>,
+
+    /// The requests, that should be fulfilled, and the plugins that
+    /// should fulfill them
+    pub provisions: Vec<Provision>,
+
+    /// The requests, that should be denied
+    pub denials: Vec<Denial>,
+}
+
+/// An explanation of whether a request can be fulfilled, made by
+/// [`Resolver::explain`]
+///
+/// A request for a specific plugin or trait lists every plugin,
+/// that was considered to fulfill it, while every other request
+/// contains the explanations of the requests nested in it. When
+/// printed, the explanation is rendered as an indented tree
+///
+/// # Example
+/// ``` text
+/// [missing] crucial
+///   [missing] either of
+///     [missing] plugin storage >=1.2.0.0, <2.0.0.0
+///       storage 1.1.0.0: version 1.1.0.0 is older than required (>=1.2.0.0, <2.0.0.0)
+///     [missing] plugin cache *
+///       no plugin cache is available
+/// ```
+#[derive(Clone, Debug)]
+pub struct Explanation {
+
+    /// The request explained
+    pub request: InterplugRequest,
+
+    /// Whether the request can be fulfilled
+    pub fulfilled: bool,
+
+    /// Explanations of the requests, nested in this one
+    pub nested: Vec<Explanation>,
+
+    /// The plugins, considered to fulfill the request
+    pub candidates: Vec<CandidateExplanation>,
+}
+
+/// An explanation of whether a plugin can be used to fulfill a
+/// request (see [`Explanation`])
+#[derive(Clone, Debug)]
+pub struct CandidateExplanation {
+
+    /// ID of the plugin
+    pub id: usize,
+
+    /// The plugin name
+    pub name: String,
+
+    /// The plugin version
+    pub version: Version,
+
+    /// The reasons the plugin does not fit the request
+    pub rejections: Vec<Rejection>,
+
+    /// Whether the plugin depends on the requesting one, so it would
+    /// be provided as a [`WeakFreightProxy`]
+    pub weak: bool,
+
+    /// Explanations of the crucial requests of the plugin itself,
+    /// that can not be fulfilled
+    pub unresolved: Vec<Explanation>,
+}
+
+impl CandidateExplanation {
+
+    /// Check if the plugin can be used to fulfill the request
+    pub fn fits (self: &Self) -> bool {
+        self.rejections.is_empty() && self.unresolved.is_empty()
+    }
+}
+
+impl Explanation {
+
+    /// Write the explanation, indented by the depth provided
+    fn render (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+        depth: usize,
+    ) -> std::fmt::Result {
+
+        let status: &str = match self.fulfilled {
+            true => "[ok]",
+            false => "[missing]",
+        };
+        writeln!(f, "{:indent$}{} {}", "", status, self.request,
+            indent = depth * 2)?;
+        for nested in &self.nested {
+            nested.render(f, depth + 1)?;
+        }
+
+        let plugin: &String = match &self.request {
+            PlugRequest { plugin, .. } | PlugRequestAll { plugin, .. } |
+                TraitRequest { plugin, .. } |
+                TraitRequestAll { plugin, .. } => plugin,
+            _ => return Ok(()),
+        };
+        if self.candidates.is_empty() {
+            return match self.request {
+                TraitRequest { .. } | TraitRequestAll { .. } =>
+                    writeln!(f, "{:indent$}no plugin implements traits of {}",
+                        "", plugin, indent = depth * 2 + 2),
+                _ => writeln!(f, "{:indent$}no plugin {} is available",
+                    "", plugin, indent = depth * 2 + 2),
+            };
+        }
+        for candidate in &self.candidates {
+            write!(f, "{:indent$}{} {}: ", "", candidate.name,
+                candidate.version, indent = depth * 2 + 2)?;
+            if candidate.fits() {
+                match candidate.weak {
+                    true => writeln!(f, "fits, as a weak handle, \
+                        as it depends on the requesting plugin")?,
+                    false => writeln!(f, "fits")?,
+                }
+                continue;
+            }
+            let mut reasons: Vec<String> = candidate.rejections.iter()
+                .map(|rejection| rejection.to_string())
+                .collect();
+            if !candidate.unresolved.is_empty() {
+                reasons.push("its own crucial requests can not be fulfilled"
+                    .to_string());
+            }
+            writeln!(f, "{}", reasons.join("; "))?;
+            for unresolved in &candidate.unresolved {
+                unresolved.render(f, depth + 2)?;
+            }
+        }
+        return Ok(());
+    }
+}
+
+impl std::fmt::Display for Explanation {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        self.render(f, 0)
+    }
+}
+
+/// A structure, that decides which of the available plugins should
+/// be used to fulfill the requests of a plugin, along with all the
+/// requests of the plugins used
+///
+/// Every [`InterplugRequest::RequestCrucial`] request and every
+/// request nested in [`InterplugRequest::RequestEach`] or
+/// [`InterplugRequest::RequestEither`] has to be fulfilled, while the
+/// rest of the requests, made by a plugin directly, and every
+/// [`InterplugRequest::RequestOptional`] request are fulfilled if
+/// possible and denied otherwise. If several plugins fit a request,
+/// the preferred version is tried first (see
+/// [`Version::is_preferred_to`]), and if either the plugin or one of
+/// the plugins it needs can not be used after all, the resolver
+/// backtracks and tries the next one, or the next alternative of a
+/// [`InterplugRequest::RequestEither`] request.
+///
+/// If a plugin depends on the plugin, that requests it, either
+/// directly or through other plugins, it is used only if no other
+/// plugin fits the request, and is provided as a [`WeakFreightProxy`]
+/// after it is initialized (see [`Provision::weak`]), so that the
+/// plugins do not keep each other from being dropped.
+///
+/// # Example
+///
+/// ```
+/// use dusk_api::*;
+///
+/// let mut resolver: Resolver = Resolver::new();
+///
+/// let mut app: AvailablePlugin = AvailablePlugin::new(
+///     "app".to_string(), Version::default(), Version::default());
+/// app.requests.push(RequestCrucial {
+///     request: Box::new(PlugRequestAll {
+///         plugin: "storage".to_string(),
+///         version: "^1.2".parse().unwrap(),
+///     }),
+/// });
+/// let app: usize = resolver.add(app);
+///
+/// let old: usize = resolver.add(AvailablePlugin::new(
+///     "storage".to_string(), "1.1".parse().unwrap(), "1.0".parse().unwrap()));
+/// let new: usize = resolver.add(AvailablePlugin::new(
+///     "storage".to_string(), "1.4".parse().unwrap(), "1.0".parse().unwrap()));
+///
+/// let resolution: Resolution = resolver.resolve(app).unwrap();
+/// assert_eq!(resolution.order, vec![new, app]);
+/// assert_eq!(resolution.provisions[0].provider, new);
+/// assert!(resolver.check_provider(old, &resolution.provisions[0].request).is_err());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Resolver {
+
+    /// Whether pre-release plugins may be used to fulfill the
+    /// requests
+    pub pre_releases: PreReleasePolicy,
+
+    plugins: Vec<AvailablePlugin>,
+}
+
+/// A request, that still has to be resolved
+#[derive(Clone, Copy, Debug)]
+struct Goal<'a> {
+
+    /// ID of the plugin, that made the request
+    requester: usize,
+
+    /// The request itself
+    request: &'a InterplugRequest,
+
+    /// Whether the request should be denied, instead of failing the
+    /// resolution, if it can not be fulfilled
+    optional: bool,
+}
+
+/// The state of a plugin of a [`PluginHost`], that is taken into
+/// account, when the requests of another one are resolved (see
+/// [`Resolver::resolve_hosted`])
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum HostedState {
+
+    /// The plugin is loaded, but not initialized yet, so its own
+    /// requests are not known
+    Loaded,
+
+    /// The plugin is being initialized, so it depends on the plugin,
+    /// the requests of which are resolved
+    Initializing,
+
+    /// The plugin is initialized, and its requests are answered
+    Ready,
+
+    /// The plugin failed or was shut down, so it can not be provided
+    Unusable,
+}
+
+/// The decisions, made by the resolver so far
+#[derive(Clone, Debug)]
+struct ResolverState {
+
+    /// Whether the requests of each plugin are being resolved
+    used: Vec<bool>,
+
+    /// Whether each plugin can not be provided at all
+    excluded: Vec<bool>,
+
+    /// IDs of the plugins, each plugin is provided with
+    dependencies: Vec<Vec<usize>>,
+
+    /// The requests, that were decided to be fulfilled
+    provisions: Vec<Provision>,
+
+    /// The requests, that were decided to be denied
+    denials: Vec<Denial>,
+}
+
+impl Resolver {
+
+    /// Create a resolver with no plugins available
+    pub fn new () -> Resolver {
+        Resolver {
+            pre_releases: PreReleasePolicy::default(),
+            plugins: Vec::new(),
+        }
+    }
+
+    /// Make the plugin provided available, returning its ID
+    pub fn add (
+        self: &mut Self,
+        plugin: AvailablePlugin,
+    ) -> usize {
+
+        self.plugins.push(plugin);
+        return self.plugins.len() - 1;
+    }
+
+    /// Get all the available plugins, in the order of their IDs
+    pub fn plugins (self: &Self) -> &[AvailablePlugin] {
+        &self.plugins
+    }
+
+    /// Check if the plugin with the ID provided could be used to
+    /// fulfill the request, returning every reason it could not
+    /// (see [`AvailablePlugin::check_request`] and
+    /// [`PreReleasePolicy`])
+    pub fn check_provider (
+        self: &Self,
+        id: usize,
+        request: &InterplugRequest,
+    ) -> Result<(), Vec<Rejection>> {
+
+        let plugin: &AvailablePlugin = match self.plugins.get(id) {
+            Some(plugin) => plugin,
+            None => return Err(vec![Rejection::NotLoaded]),
+        };
+
+        let mut reasons: Vec<Rejection> = match plugin.check_request(request) {
+            Ok(()) => Vec::new(),
+            Err(reasons) => reasons,
+        };
+        self.pre_releases.apply(&plugin.version, &mut reasons);
+
+        if reasons.is_empty() {
+            return Ok(());
+        }
+        return Err(reasons);
+    }
+
+    /// Get the IDs of all the plugins, that could be used to fulfill
+    /// the request, the preferred ones first
+    pub fn find_providers (
+        self: &Self,
+        request: &InterplugRequest,
+    ) -> Vec<usize> {
+
+        let mut result: Vec<usize> = (0..self.plugins.len())
+            .filter(|id| self.check_provider(*id, request).is_ok())
+            .collect();
+        result.sort_by(|first, second| {
+            let first: &Version = &self.plugins[*first].version;
+            let second: &Version = &self.plugins[*second].version;
+            if first.is_preferred_to(second) {
+                std::cmp::Ordering::Less
+            } else if second.is_preferred_to(first) {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        });
+        return result;
+    }
+
+    /// Decide how to answer all the requests of the plugin with the
+    /// ID provided, along with the requests of every plugin, that
+    /// should be provided to it
+    ///
+    /// If a crucial request of the plugin can not be fulfilled, it is
+    /// returned as an [`Error::DependencyError`]
+    pub fn resolve (
+        self: &Self,
+        id: usize,
+    ) -> Result<Resolution, Error> {
+
+        if id >= self.plugins.len() {
+            return Err(IndexError(
+                    format!(
+                        "Plugin with index {} does not exist",
+                        id,
+                    )));
+        }
+
+        let mut state: ResolverState = self.initial_state();
+        state.used[id] = true;
+        return self.resolve_goals(id, self.plugin_goals(id), state);
+    }
+
+    /// Decide how to answer the requests provided, made by the plugin
+    /// of a [`PluginHost`] with the ID provided, given the states of
+    /// all the plugins of the host, under the same IDs
+    ///
+    /// The requests of the initialized plugins are not resolved again,
+    /// the failed ones are never provided, and the ones, that are
+    /// being initialized, are only provided weakly, as they depend on
+    /// the plugin, the requests of which are resolved
+    pub(crate) fn resolve_hosted (
+        self: &Self,
+        id: usize,
+        requests: &[InterplugRequest],
+        states: &[HostedState],
+    ) -> Result<Resolution, Error> {
+
+        if id >= self.plugins.len() || states.len() != self.plugins.len() {
+            return Err(IndexError(
+                    format!(
+                        "Plugin with index {} does not exist",
+                        id,
+                    )));
+        }
+
+        let mut state: ResolverState = self.initial_state();
+        for (other, hosted) in states.iter().enumerate() {
+            state.used[other] = *hosted != HostedState::Loaded;
+            state.excluded[other] = *hosted == HostedState::Unusable;
+            if *hosted == HostedState::Initializing && other != id {
+                state.dependencies[other].push(id);
+            }
+        }
+        state.used[id] = true;
+        return self.resolve_goals(id, goals_of(id, requests), state);
+    }
+
+    /// Resolve the goals provided, made by the plugin with the ID
+    /// provided, starting from the state provided, and build the
+    /// resolution out of the decisions made
+    fn resolve_goals<'a> (
+        self: &'a Self,
+        id: usize,
+        goals: Vec<Goal<'a>>,
+        state: ResolverState,
+    ) -> Result<Resolution, Error> {
+
+        let state: ResolverState =
+            match self.solve(goals.clone(), state.clone()) {
+                Some(state) => state,
+                None => return Err(DependencyError(
+                        self.failed_request(&goals, &state))),
+            };
+
+        let mut order: Vec<usize> = Vec::new();
+        let mut visited: Vec<bool> = vec![false; self.plugins.len()];
+        visit(id, &state.dependencies, &mut visited, &mut order);
+        return Ok(Resolution {
+            order: order,
+            provisions: state.provisions,
+            denials: state.denials,
+        });
+    }
+
+    /// Get the state, in which no decisions were made yet
+    fn initial_state (self: &Self) -> ResolverState {
+        ResolverState {
+            used: vec![false; self.plugins.len()],
+            excluded: vec![false; self.plugins.len()],
+            dependencies: vec![Vec::new(); self.plugins.len()],
+            provisions: Vec::new(),
+            denials: Vec::new(),
+        }
+    }
+
+    /// Explain whether each of the requests of the plugin with the ID
+    /// provided can be fulfilled, and why not, if it can not
+    ///
+    /// Every request is explained on its own, so a request may be
+    /// explained as fulfilled, even if [`Resolver::resolve`] denies
+    /// it, because the plugins it needs conflict with the ones needed
+    /// by the other requests
+    pub fn explain (
+        self: &Self,
+        id: usize,
+    ) -> Result<Vec<Explanation>, Error> {
+
+        if id >= self.plugins.len() {
+            return Err(IndexError(
+                    format!(
+                        "Plugin with index {} does not exist",
+                        id,
+                    )));
+        }
+
+        let mut path: Vec<usize> = vec![id];
+        return Ok(self.plugins[id].requests.iter()
+            .map(|request| self.explain_nested(request, &mut path))
+            .collect());
+    }
+
+    /// Explain whether the request provided, made by the plugin with
+    /// the ID provided, can be fulfilled, and why not, if it can not
+    ///
+    /// Unlike [`Resolver::explain`], the request does not have to be
+    /// one of the requests, the plugin returned from its init
+    /// function, so it may be, for example, the one a function of
+    /// the plugin returned in [`Error::DependencyError`]
+    pub fn explain_request (
+        self: &Self,
+        id: usize,
+        request: &InterplugRequest,
+    ) -> Explanation {
+
+        let mut path: Vec<usize> = vec![id];
+        return self.explain_nested(request, &mut path);
+    }
+
+    /// Explain the request, made by the last plugin in the path, the
+    /// rest of the plugins in which are the ones it is going to be
+    /// provided to
+    fn explain_nested (
+        self: &Self,
+        request: &InterplugRequest,
+        path: &mut Vec<usize>,
+    ) -> Explanation {
+
+        let mut result: Explanation = Explanation {
+            request: request.clone(),
+            fulfilled: false,
+            nested: Vec::new(),
+            candidates: Vec::new(),
+        };
+
+        let plugin: &String = match request {
+            RequestEither { requests } | RequestEach { requests } => {
+                result.nested = requests.iter()
+                    .map(|request| self.explain_nested(request, path))
+                    .collect();
+                result.fulfilled = match request {
+                    RequestEither { .. } =>
+                        result.nested.iter().any(|nested| nested.fulfilled),
+                    _ => result.nested.iter().all(|nested| nested.fulfilled),
+                };
+                return result;
+            },
+            RequestCrucial { request } | RequestOptional { request } => {
+                let nested: Explanation = self.explain_nested(request, path);
+                result.fulfilled = nested.fulfilled;
+                result.nested.push(nested);
+                return result;
+            },
+            PlugRequest { plugin, .. } | PlugRequestAll { plugin, .. } |
+                TraitRequest { plugin, .. } |
+                TraitRequestAll { plugin, .. } => plugin,
+        };
+
+        for (id, available) in self.plugins.iter().enumerate() {
+            let considered: bool = match request {
+                TraitRequest { .. } | TraitRequestAll { .. } =>
+                    available.traits.iter()
+                        .any(|implemented| implemented.plugin == *plugin),
+                _ => available.name == *plugin,
+            };
+            if !considered {
+                continue;
+            }
+
+            let mut candidate: CandidateExplanation = CandidateExplanation {
+                id: id,
+                name: available.name.clone(),
+                version: available.version.clone(),
+                rejections: match self.check_provider(id, request) {
+                    Ok(()) => Vec::new(),
+                    Err(reasons) => reasons,
+                },
+                unresolved: Vec::new(),
+                weak: path.contains(&id),
+            };
+            if !candidate.weak && candidate.rejections.is_empty() {
+                path.push(id);
+                for own in &available.requests {
+                    if let RequestCrucial { .. } = own {
+                        let explanation: Explanation =
+                            self.explain_nested(own, path);
+                        if !explanation.fulfilled {
+                            candidate.unresolved.push(explanation);
+                        }
+                    }
+                }
+                path.pop();
+            }
+            result.fulfilled |= candidate.fits();
+            result.candidates.push(candidate);
+        }
+        return result;
+    }
+
+    /// Get the goals for all the requests of the plugin provided
+    fn plugin_goals (
+        self: &Self,
+        id: usize,
+    ) -> Vec<Goal<'_>> {
+
+        goals_of(id, &self.plugins[id].requests)
+    }
+
+    /// Find the crucial request out of the goals provided, that could
+    /// not be fulfilled, starting from the state provided
+    fn failed_request (
+        self: &Self,
+        goals: &[Goal<'_>],
+        state: &ResolverState,
+    ) -> InterplugRequest {
+
+        let mut first: Option<&InterplugRequest> = None;
+        for goal in goals {
+            if goal.optional || matches!(goal.request, RequestOptional { .. }) {
+                continue;
+            }
+            if self.solve(vec![*goal], state.clone()).is_none() {
+                return goal.request.clone();
+            }
+            first = first.or(Some(goal.request));
+        }
+        // Every request could be fulfilled on its own, but not all of
+        // them together
+        match first {
+            Some(request) => request.clone(),
+            None => RequestEach {
+                requests: goals.iter()
+                    .map(|goal| goal.request.clone())
+                    .collect(),
+            },
+        }
+    }
+
+    /// Resolve the goals provided, one by one, returning the state
+    /// after all of them are resolved, or [`None`] if they can not be
+    /// resolved with the decisions, made so far
+    fn solve<'a> (
+        self: &'a Self,
+        goals: Vec<Goal<'a>>,
+        mut state: ResolverState,
+    ) -> Option<ResolverState> {
+
+        let goal: Goal<'a> = match goals.first() {
+            Some(goal) => *goal,
+            None => return Some(state),
+        };
+        let rest = |first: Vec<Goal<'a>>| -> Vec<Goal<'a>> {
+            let mut result: Vec<Goal<'a>> = first;
+            result.extend_from_slice(&goals[1..]);
+            result
+        };
+
+        if goal.optional {
+            let required: Goal<'a> = Goal {
+                optional: false,
+                ..goal
+            };
+            if let Some(state) = self.solve(rest(vec![required]), state.clone()) {
+                return Some(state);
+            }
+            state.denials.push(Denial {
+                requester: goal.requester,
+                request: goal.request.clone(),
+            });
+            return self.solve(rest(Vec::new()), state);
+        }
+
+        let subgoal = |request: &'a InterplugRequest, optional: bool| Goal {
+            requester: goal.requester,
+            request: request,
+            optional: optional,
+        };
+        match goal.request {
+            RequestCrucial { request } =>
+                self.solve(rest(vec![subgoal(request, false)]), state),
+            RequestOptional { request } => {
+                if let Some(state) =
+                    self.solve(rest(vec![subgoal(request, false)]), state.clone()) {
+
+                    return Some(state);
+                }
+                state.denials.push(Denial {
+                    requester: goal.requester,
+                    request: goal.request.clone(),
+                });
+                self.solve(rest(Vec::new()), state)
+            },
+            RequestEach { requests } => self.solve(
+                rest(requests.iter()
+                    .map(|request| subgoal(request, false))
+                    .collect()),
+                state),
+            RequestEither { requests } => {
+                for request in requests {
+                    if let Some(state) = self.solve(
+                        rest(vec![subgoal(request, false)]),
+                        state.clone()) {
+
+                        return Some(state);
+                    }
+                }
+                None
+            },
+            PlugRequest { .. } | PlugRequestAll { .. } |
+                TraitRequest { .. } | TraitRequestAll { .. } => {
+
+                // The plugins, that depend on the requesting one, are
+                // only used if there is no other choice
+                let mut providers: Vec<(usize, bool)> = self
+                    .find_providers(goal.request)
+                    .into_iter()
+                    .filter(|provider| !state.excluded[*provider])
+                    .map(|provider| (provider, depends_on(
+                                provider,
+                                goal.requester,
+                                &state.dependencies)))
+                    .collect();
+                providers.sort_by_key(|(_, weak)| *weak);
+
+                for (provider, weak) in providers {
+                    let mut state: ResolverState = state.clone();
+                    if !weak {
+                        state.dependencies[goal.requester].push(provider);
+                    }
+                    state.provisions.push(Provision {
+                        requester: goal.requester,
+                        request: goal.request.clone(),
+                        provider: provider,
+                        weak: weak,
+                    });
+                    let mut next: Vec<Goal<'a>> = Vec::new();
+                    if !state.used[provider] {
+                        state.used[provider] = true;
+                        next = self.plugin_goals(provider);
+                    }
+                    if let Some(state) = self.solve(rest(next), state) {
+                        return Some(state);
+                    }
+                }
+                None
+            },
+        }
+    }
+}
+
+/// Get the goals for the requests provided, made by the plugin with
+/// the ID provided
+fn goals_of (
+    id: usize,
+    requests: &[InterplugRequest],
+) -> Vec<Goal<'_>> {
+
+    requests.iter()
+        .map(|request| Goal {
+            requester: id,
+            request: request,
+            optional: !matches!(request,
+                RequestCrucial { .. } | RequestOptional { .. }),
+        })
+        .collect()
+}
+
+/// Add the [`Rejection::MissingFunctions`] reason, if some of the
+/// requested functions are not in the list of the provided ones
+fn check_functions (
+    requested: &[usize],
+    provided: &Option<Vec<usize>>,
+    reasons: &mut Vec<Rejection>,
+) {
+
+    let provided: &Vec<usize> = match provided {
+        Some(provided) => provided,
+        None => return,
+    };
+    let missing: Vec<usize> = requested.iter()
+        .filter(|fn_id| !provided.contains(fn_id))
+        .cloned()
+        .collect();
+    if !missing.is_empty() {
+        reasons.push(Rejection::MissingFunctions {
+            fn_ids: missing,
+        });
+    }
+}
+
+/// Check if the first plugin provided is, or is going to be provided
+/// with the second one, either directly or through other plugins
+fn depends_on (
+    dependent: usize,
+    dependency: usize,
+    dependencies: &[Vec<usize>],
+) -> bool {
+
+    let mut visited: Vec<bool> = vec![false; dependencies.len()];
+    let mut stack: Vec<usize> = vec![dependent];
+    while let Some(id) = stack.pop() {
+        if id == dependency {
+            return true;
+        }
+        if visited[id] {
+            continue;
+        }
+        visited[id] = true;
+        stack.extend(dependencies[id].iter());
+    }
+    return false;
+}
+
+/// Add the plugin provided to the initialization order after all of
+/// its dependencies
+fn visit (
+    id: usize,
+    dependencies: &[Vec<usize>],
+    visited: &mut Vec<bool>,
+    order: &mut Vec<usize>,
+) {
+
+    if visited[id] {
+        return;
+    }
+    visited[id] = true;
+    for dependency in &dependencies[id] {
+        visit(*dependency, dependencies, visited, order);
+    }
+    order.push(id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a plugin of the version provided, compatible with every
+    /// version down to `1.0`, that makes the requests provided
+    fn plugin (
+        name: &str,
+        version: &str,
+        requests: Vec<InterplugRequest>,
+    ) -> AvailablePlugin {
+
+        let mut result: AvailablePlugin = AvailablePlugin::new(
+            name.to_string(),
+            version.parse().unwrap(),
+            "1.0".parse().unwrap());
+        result.requests = requests;
+        return result;
+    }
+
+    /// Request the plugin with the name and requirement provided
+    fn plug (
+        name: &str,
+        requirement: &str,
+    ) -> InterplugRequest {
+
+        PlugRequest {
+            plugin: name.to_string(),
+            fn_ids: Vec::new(),
+            version: requirement.parse().unwrap(),
+        }
+    }
+
+    /// Make the request provided crucial
+    fn crucial (request: InterplugRequest) -> InterplugRequest {
+        RequestCrucial {
+            request: Box::new(request),
+        }
+    }
+
+    /// Get the pairs of the requesters and the providers, along with
+    /// whether the provision is weak
+    fn provided (resolution: &Resolution) -> Vec<(usize, usize, bool)> {
+        resolution.provisions.iter()
+            .map(|provision|
+                (provision.requester, provision.provider, provision.weak))
+            .collect()
+    }
+
+    #[test]
+    fn backtracks_to_an_older_version () {
+        let mut resolver: Resolver = Resolver::new();
+        let app: usize = resolver.add(plugin(
+                "app", "1.0", vec![crucial(plug("storage", "^1.0"))]));
+        let old: usize = resolver.add(plugin("storage", "1.1", Vec::new()));
+        let new: usize = resolver.add(plugin(
+                "storage", "1.4", vec![crucial(plug("missing", "*"))]));
+
+        let resolution: Resolution = resolver.resolve(app).unwrap();
+        assert_eq!(resolution.order, vec![old, app]);
+        assert_eq!(provided(&resolution), vec![(app, old, false)]);
+        assert!(resolver.resolve(new).is_err());
+    }
+
+    #[test]
+    fn backtracks_to_another_alternative () {
+        let mut resolver: Resolver = Resolver::new();
+        let app: usize = resolver.add(plugin("app", "1.0", vec![crucial(
+                        RequestEither {
+                            requests: vec![plug("fast", "*"), plug("slow", "*")],
+                        })]));
+        let fast: usize = resolver.add(plugin(
+                "fast", "1.0", vec![crucial(plug("gpu", "*"))]));
+        let slow: usize = resolver.add(plugin(
+                "slow", "1.0", vec![crucial(plug("cpu", "*"))]));
+        let cpu: usize = resolver.add(plugin("cpu", "1.0", Vec::new()));
+
+        let resolution: Resolution = resolver.resolve(app).unwrap();
+        assert_eq!(resolution.order, vec![cpu, slow, app]);
+        assert_eq!(provided(&resolution), vec![(app, slow, false), (slow, cpu, false)]);
+        assert!(!resolution.order.contains(&fast));
+    }
+
+    #[test]
+    fn backtracks_through_dependencies () {
+        // The latest storage needs a codec, that can not be used
+        // itself, so the older storage is used instead
+        let mut resolver: Resolver = Resolver::new();
+        let app: usize = resolver.add(plugin(
+                "app", "1.0", vec![crucial(plug("storage", "^1.0"))]));
+        let old: usize = resolver.add(plugin(
+                "storage", "1.1", vec![crucial(plug("codec", "^1.0"))]));
+        resolver.add(plugin(
+                "storage", "1.4", vec![crucial(plug("codec", "^2.0"))]));
+        let codec: usize = resolver.add(plugin("codec", "1.2", Vec::new()));
+        resolver.add(plugin(
+                "codec", "2.0", vec![crucial(plug("missing", "*"))]));
+
+        let resolution: Resolution = resolver.resolve(app).unwrap();
+        assert_eq!(resolution.order, vec![codec, old, app]);
+        assert_eq!(provided(&resolution), vec![(app, old, false), (old, codec, false)]);
+    }
+
+    #[test]
+    fn denies_optional_requests_as_a_whole () {
+        let mut resolver: Resolver = Resolver::new();
+        let each: InterplugRequest = RequestEach {
+            requests: vec![plug("log", "*"), plug("missing", "*")],
+        };
+        let app: usize = resolver.add(plugin("app", "1.0", vec![each.clone()]));
+        resolver.add(plugin("log", "1.0", Vec::new()));
+
+        let resolution: Resolution = resolver.resolve(app).unwrap();
+        assert!(resolution.provisions.is_empty());
+        assert_eq!(resolution.denials.len(), 1);
+        assert_eq!(resolution.denials[0].request, each);
+        assert_eq!(resolution.order, vec![app]);
+    }
+
+    #[test]
+    fn returns_the_failed_crucial_request () {
+        let mut resolver: Resolver = Resolver::new();
+        let failed: InterplugRequest = crucial(plug("missing", "*"));
+        let app: usize = resolver.add(plugin("app", "1.0", vec![
+                    crucial(plug("log", "*")),
+                    failed.clone(),
+                ]));
+        resolver.add(plugin("log", "1.0", Vec::new()));
+
+        match resolver.resolve(app) {
+            Err(DependencyError(request)) => assert_eq!(request, failed),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(resolver.resolve(app + 10).is_err());
+    }
+
+    #[test]
+    fn closes_cycles_with_weak_provisions () {
+        let mut resolver: Resolver = Resolver::new();
+        let first: usize = resolver.add(plugin(
+                "first", "1.0", vec![crucial(plug("second", "*"))]));
+        let second: usize = resolver.add(plugin(
+                "second", "1.0", vec![crucial(plug("first", "*"))]));
+
+        let resolution: Resolution = resolver.resolve(first).unwrap();
+        assert_eq!(resolution.order, vec![second, first]);
+        assert_eq!(
+            provided(&resolution),
+            vec![(first, second, false), (second, first, true)]);
+    }
+
+    #[test]
+    fn prefers_plugins_outside_of_the_cycle () {
+        // The second plugin can get the cache from either the first
+        // plugin, that depends on it, or the standalone one
+        let mut resolver: Resolver = Resolver::new();
+        let first: usize = resolver.add(plugin(
+                "cache", "1.2", vec![crucial(plug("second", "*"))]));
+        let second: usize = resolver.add(plugin(
+                "second", "1.0", vec![crucial(plug("cache", "*"))]));
+        let standalone: usize = resolver.add(plugin("cache", "1.1", Vec::new()));
+
+        let resolution: Resolution = resolver.resolve(first).unwrap();
+        assert_eq!(resolution.order, vec![standalone, second, first]);
+        assert_eq!(
+            provided(&resolution),
+            vec![(first, second, false), (second, standalone, false)]);
+    }
+
+    #[test]
+    fn resolves_hosted_plugins () {
+        let mut resolver: Resolver = Resolver::new();
+        let app: usize = resolver.add(plugin("app", "1.0", Vec::new()));
+        let failed: usize = resolver.add(plugin("log", "1.4", Vec::new()));
+        let log: usize = resolver.add(plugin("log", "1.2", Vec::new()));
+        let parent: usize = resolver.add(plugin("parent", "1.0", Vec::new()));
+        let states: [HostedState; 4] = [
+            HostedState::Initializing,
+            HostedState::Unusable,
+            HostedState::Loaded,
+            HostedState::Initializing,
+        ];
+
+        // The plugin, that is being initialized, depends on the
+        // requesting one, so it is only provided weakly
+        let requests: Vec<InterplugRequest> = vec![
+            crucial(plug("log", "*")),
+            crucial(plug("parent", "*")),
+        ];
+        let resolution: Resolution =
+            resolver.resolve_hosted(app, &requests, &states).unwrap();
+        assert_eq!(
+            provided(&resolution),
+            vec![(app, log, false), (app, parent, true)]);
+        assert!(!resolution.order.contains(&failed));
+    }
+}
@@ -36,6 +36,7 @@ impl U8 {
             fields: Vec::new(),
             trait_implementations: Vec::new(),
             native_id: TypeId::of::<U8>(),
+            library: None,
         }
     }
 }
@@ -0,0 +1,741 @@
+// Copyright (C) 2021 by Andy Gozas <andy@gozas.me>
+//
+// This file is part of Dusk API.
+//
+// Dusk API is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Dusk API is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Dusk API.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Module, containing everything needed to use one loaded plugin
+//! from several threads at once
+//!
+//! A [`FreightProxy`] is shared with [`std::rc::Rc`] and keeps the
+//! lists it got from the freight in a [`std::cell::RefCell`], so it
+//! can only ever be used by the thread it was created in. A
+//! [`SyncFreightProxy`] keeps them behind a [`std::sync::Mutex`]
+//! instead and is shared with [`std::sync::Arc`].
+//!
+//! Neither [`Freight`] nor [`DuskCallable`] require their
+//! implementors to be [`Send`], so only the plugins, that register
+//! their freight with [`FreightRegistrar::register_sync_freight`]
+//! (for example using [`register_sync_freight!`]), can be loaded
+//! this way. The callables a plugin provides are never handed out:
+//! every function, gotten from a [`SyncFreightProxy`], is given a
+//! [`SyncCallable`] instead, which calls the original one with the
+//! proxy locked.
+
+use crate::*;
+
+/// Imported freight and everything already gotten from it, that may
+/// be moved to another thread
+#[derive(Debug)]
+struct SendCache (FreightCache);
+
+impl SendCache {
+
+    /// Build a cache around the freight provided, which has to be
+    /// `Send`, as it is moved to another thread along with the cache
+    fn new (freight: Box<dyn Freight + Send>) -> SendCache {
+        SendCache(FreightCache::new(freight))
+    }
+}
+
+// The library and its verified copy, kept by the cache, are moved
+// along with it, so they have to be `Send` on their own
+const _: fn () = || {
+    fn assert_send<T: Send> () {}
+    assert_send::<std::sync::Arc<libloading::Library>>();
+    assert_send::<VerifiedLibrary>();
+};
+
+// SAFETY: A SendCache is only built by `SendCache::new`, so the
+// freight it keeps is `Send`, and so are the library and the copy it
+// was loaded from (see the assertion above). The rest is the state,
+// the requests and limitations, which are `Send`, and the functions,
+// types and modules, gotten from the freight, whose callables may
+// not be. Those are only created, cloned, called and dropped with
+// the mutex of the SyncFreightProxy locked, and every copy, handed
+// out of it, gets its callables replaced with a `SyncCallable` before
+// the mutex is unlocked (see `SyncFreightProxy::detach_functions`).
+// So they only move to another thread along with the freight they
+// came from, and are never used by two threads at once
+unsafe impl Send for SendCache {}
+
+/// A structure, that contains a Freight object, like a
+/// [`FreightProxy`] does, but may be shared between threads using
+/// [`std::sync::Arc`]
+///
+/// [`Freight`] is implemented for [`std::sync::Arc<SyncFreightProxy>`],
+/// so it is used exactly like a shared [`FreightProxy`]. The calls
+/// are made one at a time: a thread, calling into the proxy, waits
+/// until the call made by any other thread is finished. Calling
+/// into a proxy from inside of a call to the same proxy is not
+/// allowed and blocks forever.
+///
+/// A thread safe plugin may not be provided with a
+/// [`FreightProxy`], as it can not be moved to another thread along
+/// with it, so a request, answered with
+/// [`Freight::interplug_provide`], is denied instead. Use
+/// [`Freight::interplug_provide_sync`] to provide another
+/// [`SyncFreightProxy`] to it.
+///
+/// # Example
+///
+/// ``` rust, ignore
+/// let my_f_proxy: std::sync::Arc<SyncFreightProxy> = std::sync::Arc::new(
+///     unsafe { SyncFreightProxy::load("/bin/libtest_plug.so") }.expect("fail"));
+///
+/// let mut threads: Vec<std::thread::JoinHandle<()>> = Vec::new();
+/// for _ in 0..4 {
+///     let mut proxy: std::sync::Arc<SyncFreightProxy> = my_f_proxy.clone();
+///     threads.push(std::thread::spawn(move || {
+///         let fnlist: Vec<Function> = proxy.get_function_list().unwrap();
+///         for func in fnlist {
+///             println!("{}, {}", func.name, func.fn_id);
+///         }
+///     }));
+/// }
+/// for thread in threads {
+///     thread.join().unwrap();
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SyncFreightProxy {
+
+    /// Imported freight along with everything already gotten from
+    /// it, solely for internal purposes
+    inner: std::sync::Mutex<SendCache>,
+
+    /// Imported freights name as a static string
+    pub name: String,
+
+    /// Imported freights version
+    pub version: Version,
+
+    /// The earliest version, for which the code was designed, this
+    /// code can safely be run with the new plugin version
+    pub backwards_compat_version: Version,
+}
+
+/// Registrar, that only accepts the freights, registered as `Send`
+#[derive(Default)]
+struct SyncRegistrar {
+
+    /// The freight, registered with
+    /// [`FreightRegistrar::register_sync_freight`]
+    freight: Option<Box<dyn Freight + Send>>,
+
+    /// Whether a freight was registered with
+    /// [`FreightRegistrar::register_freight`] instead
+    not_sync: bool,
+}
+
+impl FreightRegistrar for SyncRegistrar {
+    fn register_freight (
+        self: &mut Self,
+        _freight: Box<dyn Freight>,
+    ) {
+        self.not_sync = true;
+    }
+
+    fn register_sync_freight (
+        self: &mut Self,
+        freight: Box<dyn Freight + Send>,
+    ) {
+        self.freight = Some(freight);
+    }
+}
+
+impl SyncFreightProxy {
+
+    /// Function, used to build a [`SyncFreightProxy`] object from a
+    /// library path
+    ///
+    /// The plugin has to be built with exactly the same compiler
+    /// version and layout of the core structures as the program
+    /// using it, as the freights, used through the C ABI, can not be
+    /// moved to another thread. If the plugin did not register its
+    /// freight with [`FreightRegistrar::register_sync_freight`],
+    /// [`Error::ImportError`] is returned
+    ///
+    /// # Safety
+    ///
+    /// The library is loaded, which runs its initialization code and
+    /// the register function of the plugin, so it has to be a Dusk
+    /// API plugin, that is safe to run. A freight, it registers as
+    /// `Send`, is trusted to really be safe to move to another thread
+    pub unsafe fn load (
+        lib_path: &str,
+    ) -> Result<SyncFreightProxy, Error> {
+
+        // Import the library
+        let lib : std::sync::Arc<libloading::Library>;
+        match libloading::Library::new(lib_path) {
+            Ok(library) => lib = std::sync::Arc::new(library),
+            Err(lib_err) => return(Err(LoadingError (lib_err))),
+        }
+
+        // The declaration may only be read if its layout matches
+        check_library_layout(&lib)?;
+
+        // Get the plugin declaration structure from this lib
+        let declaration: FreightDeclaration;
+        match lib.get::<*mut FreightDeclaration>(
+            b"freight_declaration\0") {
+
+            Ok(decl) => declaration = decl.read(),
+            Err(lib_err) => return(Err(LoadingError (lib_err))),
+        }
+
+        let result: SyncFreightProxy =
+            SyncFreightProxy::load_from_declaration(&declaration)?;
+        result.cache().0.lib = Some(lib);
+
+        // Return the result
+        Ok(result)
+    }
+
+    /// Function, used to build a [`SyncFreightProxy`] object from a
+    /// library path, checking the library with the verifier provided
+    /// first and loading the private copy of the checked contents,
+    /// kept for as long as the proxy is (see
+    /// [`FreightProxy::load_verified`])
+    ///
+    /// # Safety
+    ///
+    /// See [`SyncFreightProxy::load`]. The check only makes sure the
+    /// library is the one expected, not that its code is sound
+    pub unsafe fn load_verified (
+        lib_path: &str,
+        verifier: &LibraryVerifier,
+    ) -> Result<SyncFreightProxy, Error> {
+
+        let copy: VerifiedLibrary =
+            verifier.copy_verified(std::path::Path::new(lib_path))?;
+        let result: SyncFreightProxy = SyncFreightProxy::load(copy.path_str()?)?;
+        result.cache().0.verified = Some(copy);
+        return Ok(result);
+    }
+
+    /// Build a [`SyncFreightProxy`] from the latest version of a
+    /// plugin, statically linked into the program (see
+    /// [`STATIC_FREIGHTS`])
+    pub fn load_static (
+        name: &str,
+    ) -> Result<SyncFreightProxy, Error> {
+
+        match find_static_declaration(name, None) {
+            Some(declaration) =>
+                SyncFreightProxy::load_from_declaration(&declaration),
+            None => Err(ImportError (
+                    format!(
+                        "No plugin named {} is linked statically",
+                        name,
+                    ))),
+        }
+    }
+
+    /// Build a [`SyncFreightProxy`] from a [`FreightDeclaration`],
+    /// checking the versions
+    ///
+    /// # Warning
+    /// This is an internal function, see
+    /// [`FreightProxy::load_from_declaration`] before use
+    pub fn load_from_declaration (
+        declaration: &FreightDeclaration,
+    ) -> Result<SyncFreightProxy, Error> {
+
+        check_declaration(declaration)?;
+
+        let mut registrar: SyncRegistrar = SyncRegistrar::default();
+        (declaration.register)(&mut registrar);
+        if registrar.not_sync {
+            return Err(ImportError (
+                    format!(
+                        "Plugin {} is not thread safe",
+                        declaration.name,
+                    )));
+        }
+
+        let freight: Box<dyn Freight + Send> = match registrar.freight {
+            Some(freight) => freight,
+            None => Box::new(EmptyFreight{}),
+        };
+        return Ok(SyncFreightProxy::new(
+                declaration.name.clone(),
+                declaration.freight_version.clone(),
+                declaration.backwards_compat_version.clone(),
+                freight));
+    }
+
+    /// Build a [`SyncFreightProxy`] around a [`Freight`] implementor,
+    /// that was not imported from a library
+    pub fn new (
+        name: String,
+        version: Version,
+        backwards_compat_version: Version,
+        freight: Box<dyn Freight + Send>,
+    ) -> SyncFreightProxy {
+
+        SyncFreightProxy {
+            inner: std::sync::Mutex::new(
+                SendCache::new(freight)),
+            name: name,
+            version: version,
+            backwards_compat_version: backwards_compat_version,
+        }
+    }
+
+    /// Call the function with the ID provided, waiting for any call,
+    /// made by another thread, to finish first
+    pub fn call (
+        self: &Self,
+        fn_id: usize,
+        args: Vec<Object>,
+    ) -> Result<Object, Error> {
+
+        let mut cache: std::sync::MutexGuard<'_, SendCache> = self.cache();
+        let mut callable: Box<dyn DuskCallable> =
+            cache.0.get_callable_by_id(fn_id)?;
+        callable.call(args)
+    }
+
+    /// Get the requests, that were provided to the plugin since it
+    /// was last initialized
+    pub fn provided_requests (self: &Self) -> Vec<InterplugRequest> {
+        self.cache().0.provided_requests()
+    }
+
+    /// Get the requests, that were denied to the plugin since it was
+    /// last initialized
+    pub fn denied_requests (self: &Self) -> Vec<InterplugRequest> {
+        self.cache().0.denied_requests()
+    }
+
+    /// Get the limitations, that did not fit the schema of the plugin
+    /// (see [`FreightProxy::get_rejected_limitations`])
+    pub fn get_rejected_limitations (
+        self: &Self,
+    ) -> Vec<RejectedLimitation> {
+
+        self.cache().0.rejected_limitations()
+    }
+
+    /// Get every function of the plugin along with whether it can be
+    /// used with the requests, provided and denied so far (see
+    /// [`FreightProxy::get_function_availability`])
+    pub fn get_function_availability (
+        self: &std::sync::Arc<Self>,
+    ) -> Result<Vec<FunctionAvailability>, Error> {
+
+        let mut result: Vec<FunctionAvailability> =
+            self.cache().0.get_function_availability()?;
+        for status in &mut result {
+            status.function.callable = Box::new(SyncCallable {
+                proxy: self.clone(),
+                fn_id: status.function.fn_id,
+            });
+        }
+        return Ok(result);
+    }
+
+    /// Get only the functions of the plugin, that can be used with
+    /// the requests, provided so far
+    pub fn get_available_functions (
+        self: &std::sync::Arc<Self>,
+    ) -> Result<Vec<Function>, Error> {
+
+        return Ok(self.get_function_availability()?.into_iter()
+            .filter(|status| status.availability == Availability::Available)
+            .map(|status| status.function)
+            .collect());
+    }
+
+    /// Lock the cache, ignoring a panic, that may have happened
+    /// inside of a call, made by another thread, just like a
+    /// [`FreightProxy`] does
+    fn cache (self: &Self) -> std::sync::MutexGuard<'_, SendCache> {
+        match self.inner.lock() {
+            Ok(cache) => cache,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Make the functions provided call back into this proxy instead
+    /// of calling their own callables
+    fn detach_functions (
+        self: &std::sync::Arc<Self>,
+        functions: &mut Vec<Function>,
+    ) {
+
+        for function in functions {
+            function.callable = Box::new(SyncCallable {
+                proxy: self.clone(),
+                fn_id: function.fn_id,
+            });
+        }
+    }
+
+    /// Make the methods of the types provided call back into this
+    /// proxy
+    fn detach_types (
+        self: &std::sync::Arc<Self>,
+        types: &mut Vec<Type>,
+    ) {
+
+        for data_type in types {
+            self.detach_functions(&mut data_type.methods);
+            self.detach_functions(&mut data_type.fields);
+            for implementation in &mut data_type.trait_implementations {
+                for method in &mut implementation.methods {
+                    method.function.callable = Box::new(SyncCallable {
+                        proxy: self.clone(),
+                        fn_id: method.function.fn_id,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Make every function in the modules provided and all of their
+    /// submodules call back into this proxy
+    fn detach_modules (
+        self: &std::sync::Arc<Self>,
+        modules: &mut Vec<Module>,
+    ) {
+
+        for module in modules {
+            self.detach_functions(&mut module.functions);
+            self.detach_functions(&mut module.constants);
+            self.detach_types(&mut module.types);
+            self.detach_modules(&mut module.submodules);
+        }
+    }
+}
+
+/// Dusk callable, that calls a function of a plugin, loaded as a
+/// [`SyncFreightProxy`], by its ID
+///
+/// Unlike the callable it replaces, it may be used from any thread
+#[derive(Clone)]
+pub struct SyncCallable {
+    proxy: std::sync::Arc<SyncFreightProxy>,
+    fn_id: usize,
+}
+
+impl DuskCallable for SyncCallable {
+    fn call (
+        self: &mut Self,
+        args: Vec<Object>
+    ) -> Result<Object, Error> {
+
+        self.proxy.call(self.fn_id, args)
+    }
+}
+
+impl std::fmt::Debug for SyncCallable {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        f.debug_struct("SyncCallable")
+            .field("freight", &self.proxy.name)
+            .field("fn_id", &self.fn_id)
+            .finish()
+    }
+}
+
+// Implementation of trait Freight for a shared thread safe proxy.
+// Every function and type handed out gets its callables replaced
+// before the proxy is unlocked, so none of the callables of the
+// freight ever leave the mutex
+impl Freight for std::sync::Arc<SyncFreightProxy> {
+
+    fn init (
+        self: &mut Self,
+        limitations: &Option<Vec<Limitation>>,
+    ) -> Vec<InterplugRequest> {
+
+        self.cache().0.init(limitations)
+    }
+
+    fn update_limitations (
+        self: &mut Self,
+        limitations: &Vec<Limitation>,
+    ) {
+
+        self.cache().0.update_limitations(limitations)
+    }
+
+    fn get_limitation_schema (
+        self: &mut Self,
+    ) -> Option<LimitationSchema> {
+
+        self.cache().0.get_limitation_schema()
+    }
+
+    fn get_effective_limitations (
+        self: &mut Self,
+    ) -> Option<Vec<EffectiveSetting>> {
+
+        self.cache().0.get_effective_limitations()
+    }
+
+    // A FreightProxy can not be moved to another thread, so it can
+    // not be provided to a thread safe plugin
+    fn interplug_provide (
+        self: &mut Self,
+        request: InterplugRequest,
+        _freight_proxy: std::rc::Rc<FreightProxy>,
+    ) {
+
+        self.cache().0.interplug_deny(request);
+    }
+
+    fn interplug_provide_sync (
+        self: &mut Self,
+        request: InterplugRequest,
+        freight_proxy: std::sync::Arc<SyncFreightProxy>,
+    ) {
+
+        self.cache().0.interplug_provide_sync(request, freight_proxy);
+    }
+
+    // Neither can a weak handle to it
+    fn interplug_provide_weak (
+        self: &mut Self,
+        request: InterplugRequest,
+        _freight_proxy: WeakFreightProxy,
+    ) {
+
+        self.cache().0.interplug_deny(request);
+    }
+
+    fn interplug_deny (
+        self: &mut Self,
+        request: InterplugRequest,
+    ) {
+
+        self.cache().0.interplug_deny(request);
+    }
+
+    fn interplug_replace (
+        self: &mut Self,
+        request: InterplugRequest,
+        _freight_proxy: std::rc::Rc<FreightProxy>,
+    ) {
+
+        self.cache().0.interplug_deny(request);
+    }
+
+    fn interplug_withdraw (
+        self: &mut Self,
+        request: InterplugRequest,
+    ) {
+
+        self.cache().0.interplug_withdraw(request);
+    }
+
+    fn shutdown (self: &mut Self) {
+        self.cache().0.shutdown()
+    }
+
+    fn export_state (
+        self: &mut Self,
+    ) -> Result<Option<Vec<u8>>, Error> {
+
+        self.cache().0.export_state()
+    }
+
+    fn import_state (
+        self: &mut Self,
+        state: Vec<u8>,
+    ) -> Result<(), Error> {
+
+        self.cache().0.import_state(state)
+    }
+
+    fn get_root_modules (self: &mut Self) -> Vec<Module> {
+        let mut cache: std::sync::MutexGuard<'_, SendCache> = self.cache();
+        let mut modules: Vec<Module> = cache.0.get_root_modules();
+        self.detach_modules(&mut modules);
+        return modules;
+    }
+
+    fn get_operator_list (self: &mut Self) -> Vec<Function> {
+        let mut cache: std::sync::MutexGuard<'_, SendCache> = self.cache();
+        let mut functions: Vec<Function> = cache.0.get_operator_list();
+        self.detach_functions(&mut functions);
+        return functions;
+    }
+
+    fn get_callable_list (
+        self: &mut Self,
+    ) -> Result<Vec<Box<dyn DuskCallable>>, Error> {
+
+        let amount: usize = self.cache().0.get_callable_list()?.len();
+        let mut result: Vec<Box<dyn DuskCallable>> = Vec::new();
+        for fn_id in 0..amount {
+            result.push(Box::new(SyncCallable {
+                proxy: self.clone(),
+                fn_id: fn_id,
+            }));
+        }
+        return Ok(result);
+    }
+
+    fn get_callable_by_id (
+        self: &mut Self,
+        id: usize,
+    ) -> Result<Box<dyn DuskCallable>, Error> {
+
+        self.cache().0.get_callable_by_id(id)?;
+        return Ok(Box::new(SyncCallable {
+            proxy: self.clone(),
+            fn_id: id,
+        }));
+    }
+
+    fn get_function_list (
+        self: &mut Self,
+    ) -> Result<Vec<Function>, Error> {
+
+        let mut cache: std::sync::MutexGuard<'_, SendCache> = self.cache();
+        let mut functions: Vec<Function> = cache.0.get_function_list()?;
+        self.detach_functions(&mut functions);
+        return Ok(functions);
+    }
+
+    fn get_function_by_id (
+        self: &mut Self,
+        id: usize,
+    ) -> Result<Function, Error> {
+
+        let mut cache: std::sync::MutexGuard<'_, SendCache> = self.cache();
+        let mut functions: Vec<Function> =
+            vec![cache.0.get_function_by_id(id)?];
+        self.detach_functions(&mut functions);
+        return Ok(functions.remove(0));
+    }
+
+    fn get_functions_by_name (
+        self: &mut Self,
+        name: &String,
+    ) -> Result<Vec<Function>, Error> {
+
+        let mut cache: std::sync::MutexGuard<'_, SendCache> = self.cache();
+        let mut functions: Vec<Function> =
+            cache.0.get_functions_by_name(name)?;
+        self.detach_functions(&mut functions);
+        return Ok(functions);
+    }
+
+    fn get_type_list (
+        self: &mut Self,
+    ) -> Result<Vec<Type>, Error> {
+
+        let mut cache: std::sync::MutexGuard<'_, SendCache> = self.cache();
+        let mut types: Vec<Type> = cache.0.get_type_list()?;
+        self.detach_types(&mut types);
+        return Ok(types);
+    }
+
+    fn get_type_by_id (
+        self: &mut Self,
+        id: usize,
+    ) -> Result<Type, Error> {
+
+        let mut cache: std::sync::MutexGuard<'_, SendCache> = self.cache();
+        let mut types: Vec<Type> = vec![cache.0.get_type_by_id(id)?];
+        self.detach_types(&mut types);
+        return Ok(types.remove(0));
+    }
+
+    fn get_type_by_native_id (
+        self: &mut Self,
+        native_id: TypeId,
+    ) -> Result<Type, Error> {
+
+        let mut cache: std::sync::MutexGuard<'_, SendCache> = self.cache();
+        let mut types: Vec<Type> =
+            vec![cache.0.get_type_by_native_id(native_id)?];
+        self.detach_types(&mut types);
+        return Ok(types.remove(0));
+    }
+
+    fn get_types_by_name (
+        self: &mut Self,
+        name: &String,
+    ) -> Result<Vec<Type>, Error> {
+
+        let mut cache: std::sync::MutexGuard<'_, SendCache> = self.cache();
+        let mut types: Vec<Type> = cache.0.get_types_by_name(name)?;
+        self.detach_types(&mut types);
+        return Ok(types);
+    }
+
+    fn get_trait_definition_list (
+        self: &mut Self,
+    ) -> Result<Vec<TraitDefinition>, Error> {
+
+        self.cache().0.get_trait_definition_list()
+    }
+
+    fn get_trait_definition_by_id (
+        self: &mut Self,
+        id: usize,
+    ) -> Result<TraitDefinition, Error> {
+
+        self.cache().0.get_trait_definition_by_id(id)
+    }
+
+    fn get_trait_definitions_by_name (
+        self: &mut Self,
+        name: &String,
+    ) -> Result<Vec<TraitDefinition>, Error> {
+
+        self.cache().0.get_trait_definitions_by_name(name)
+    }
+
+    fn get_module_list (
+        self: &mut Self,
+    ) -> Result<Vec<Module>, Error> {
+
+        let mut cache: std::sync::MutexGuard<'_, SendCache> = self.cache();
+        let mut modules: Vec<Module> = cache.0.get_module_list()?;
+        self.detach_modules(&mut modules);
+        return Ok(modules);
+    }
+
+    fn get_module_by_id (
+        self: &mut Self,
+        id: usize,
+    ) -> Result<Module, Error> {
+
+        let mut cache: std::sync::MutexGuard<'_, SendCache> = self.cache();
+        let mut modules: Vec<Module> = vec![cache.0.get_module_by_id(id)?];
+        self.detach_modules(&mut modules);
+        return Ok(modules.remove(0));
+    }
+
+    fn get_modules_by_name (
+        self: &mut Self,
+        name: &String,
+    ) -> Result<Vec<Module>, Error> {
+
+        let mut cache: std::sync::MutexGuard<'_, SendCache> = self.cache();
+        let mut modules: Vec<Module> = cache.0.get_modules_by_name(name)?;
+        self.detach_modules(&mut modules);
+        return Ok(modules);
+    }
+}
@@ -80,6 +80,16 @@ pub struct TraitFunctionDefinition {
     pub no_check_args: bool,
 }
 
+impl PinLibrary for TraitFunctionDefinition {
+    fn pin_library (
+        self: &mut Self,
+        lib: &std::sync::Arc<libloading::Library>,
+    ) {
+
+        self.parameters.pin_library(lib);
+    }
+}
+
 impl Default for TraitFunctionDefinition {
     fn default () -> TraitFunctionDefinition {
         TraitFunctionDefinition {
@@ -98,7 +108,7 @@ impl Default for TraitFunctionDefinition {
 /// A TraitFunction object contains
 /// * its number in trait
 /// * the underlying function
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct TraitFunction {
 
     /// Function ID, used to call this function
@@ -112,12 +122,13 @@ pub struct TraitFunction {
     pub function: Function,
 }
 
-impl Default for TraitFunction {
-    fn default () -> TraitFunction {
-        TraitFunction {
-            fn_trait_id: 0,
-            function: Default::default(),
-        }
+impl PinLibrary for TraitFunction {
+    fn pin_library (
+        self: &mut Self,
+        lib: &std::sync::Arc<libloading::Library>,
+    ) {
+
+        self.function.pin_library(lib);
     }
 }
 
@@ -136,6 +147,16 @@ pub struct TraitDefinition {
     pub methods: Vec<TraitFunctionDefinition>,
 }
 
+impl PinLibrary for TraitDefinition {
+    fn pin_library (
+        self: &mut Self,
+        lib: &std::sync::Arc<libloading::Library>,
+    ) {
+
+        self.methods.pin_library(lib);
+    }
+}
+
 impl Default for TraitDefinition {
     fn default () -> TraitDefinition {
         TraitDefinition {
@@ -165,6 +186,16 @@ pub struct TraitImplementation {
     pub methods: Vec<TraitFunction>,
 }
 
+impl PinLibrary for TraitImplementation {
+    fn pin_library (
+        self: &mut Self,
+        lib: &std::sync::Arc<libloading::Library>,
+    ) {
+
+        self.methods.pin_library(lib);
+    }
+}
+
 /// TODO: trait proxy not perfect for the cause yet
 #[derive(Clone, Debug)]
 pub struct TraitProxy {
@@ -186,6 +217,79 @@ pub struct TraitProxy {
     pub tp_id: usize,
 
     /// The vector, linking IDs of the Trait functions to the actual
-    /// general plugin function IDs
+    /// general plugin function IDs. The trait functions, that are
+    /// not implemented, are linked to [`usize::MAX`]
     pub function_links: Vec<usize>,
 }
+
+impl TraitProxy {
+
+    /// Build a proxy of the trait implementation provided, made for
+    /// the type provided of the plugin provided
+    pub fn new (
+        freight_proxy: std::rc::Rc<FreightProxy>,
+        tp: &Type,
+        implementation: &TraitImplementation,
+    ) -> TraitProxy {
+
+        let mut function_links: Vec<usize> = Vec::new();
+        for method in &implementation.methods {
+            let position: usize = method.fn_trait_id as usize;
+            if position >= function_links.len() {
+                function_links.resize(position + 1, usize::MAX);
+            }
+            function_links[position] = method.function.fn_id;
+        }
+
+        TraitProxy {
+            plugin: implementation.plugin.clone(),
+            trait_id: implementation.trait_id,
+            version: implementation.version.clone(),
+            freight_proxy: freight_proxy,
+            tp_id: tp.tp_id,
+            function_links: function_links,
+        }
+    }
+
+    /// Find every implementation of a trait by the types of the
+    /// plugin provided, that fits the
+    /// [`InterplugRequest::TraitRequest`] or
+    /// [`InterplugRequest::TraitRequestAll`] request provided (see
+    /// [`ImplementedTrait::check_request`])
+    ///
+    /// # Example
+    ///
+    /// ``` rust, ignore
+    /// let request: InterplugRequest = TraitRequest {
+    ///     plugin: "shapes".to_string(),
+    ///     trait_id: 0,
+    ///     fn_ids: vec![0, 1],
+    ///     version: "^1.2".parse().unwrap(),
+    /// };
+    /// for implementation in TraitProxy::find(&my_f_proxy, &request).unwrap() {
+    ///     let area: usize = implementation.function_links[0];
+    ///     println!("{}", implementation.freight_proxy
+    ///         .clone().get_function_by_id(area).unwrap().name);
+    /// }
+    /// ```
+    pub fn find (
+        freight_proxy: &std::rc::Rc<FreightProxy>,
+        request: &InterplugRequest,
+    ) -> Result<Vec<TraitProxy>, Error> {
+
+        let mut result: Vec<TraitProxy> = Vec::new();
+        for tp in freight_proxy.clone().get_type_list()? {
+            for implementation in &tp.trait_implementations {
+                if ImplementedTrait::from(implementation)
+                    .check_request(request).is_ok() {
+
+                    result.push(TraitProxy::new(
+                            freight_proxy.clone(),
+                            &tp,
+                            implementation));
+                }
+            }
+        }
+        return Ok(result);
+    }
+}
@@ -42,6 +42,12 @@ pub struct Type {
     /// of the type in the type vector, **NOT** the native [`TypeId`]**
     pub tp_id: usize,
 
+    /// Function, that creates the data of a new object of this type
+    ///
+    /// Its code, and the code of the data it returns, is located in
+    /// the plugin library, so objects should be created with
+    /// [`Type::generate`] instead of calling it directly, which keeps
+    /// the library loaded while the object is in use
     pub generator : fn () -> Result<Box<dyn DkAny>, Error>,
 
     /// If an object of this type should have some functions, that
@@ -72,6 +78,49 @@ pub struct Type {
     /// See [`std::any::TypeId`] documentation to find out how
     /// to get a type id of a type
     pub native_id: TypeId,
+
+    /// The library the type was imported from, which keeps the
+    /// generator valid while the type is in use
+    ///
+    /// Set by [`FreightProxy`] (see [`PinLibrary`]), should be left
+    /// [`None`] by plugins
+    pub library: Option<std::sync::Arc<libloading::Library>>,
+}
+
+impl Type {
+
+    /// Create a new object of this type with the flags provided,
+    /// pinning the library the type was imported from to it (see
+    /// [`PinLibrary`])
+    ///
+    /// This is the way objects of a type, provided by a plugin,
+    /// should be created, as otherwise nothing stops the library from
+    /// being unloaded while the object is still in use
+    pub fn generate (
+        self: &'static Self,
+        flags: u32,
+    ) -> Result<Object, Error> {
+
+        let data: Box<dyn DkAny> = (self.generator)()?;
+        let mut object: Object = Object::new(data, self, flags);
+        if let Some(lib) = &self.library {
+            object.pin_library(lib);
+        }
+        return Ok(object);
+    }
+}
+
+impl PinLibrary for Type {
+    fn pin_library (
+        self: &mut Self,
+        lib: &std::sync::Arc<libloading::Library>,
+    ) {
+
+        self.methods.pin_library(lib);
+        self.fields.pin_library(lib);
+        self.trait_implementations.pin_library(lib);
+        self.library = Some(lib.clone());
+    }
 }
 
 impl Default for Type {
@@ -84,6 +133,7 @@ impl Default for Type {
             fields: Vec::new(),
             trait_implementations: Vec::new(),
             native_id: TypeId::of::<U8>(),
+            library: None,
         }
     }
 }
@@ -18,6 +18,8 @@
 //! Module, containing everything needed for version control of
 //! plugin versions, compiler versions and API versions
 
+use crate::*;
+
 /// Api version parameter, passed from the build script.
 ///
 /// For the program that uses the plugin to work correctly it
@@ -25,6 +27,159 @@
 /// it as a static variable
 pub static API_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Fingerprint of the memory layout of the structures, shared
+/// between a plugin and the program using it, either directly or
+/// inside of other structures: [`Object`], [`Function`],
+/// [`Parameter`], [`Type`], [`Module`], the trait structures,
+/// [`FreightDeclaration`], [`InterplugRequest`], [`Version`],
+/// [`Limitation`] and the other limitation structures, [`Error`],
+/// the freight proxies and the [`Freight`] trait object
+///
+/// It is computed from the sizes and alignments of those structures
+/// and their layout versions, so a plugin, built with another Dusk
+/// API version, is only rejected if something it shares with the
+/// program has actually changed. It is embedded in every
+/// [`FreightDeclaration`] and checked instead of the
+/// [`API_VERSION`] when the plugin is loaded
+///
+/// # Changing the shared structures
+///
+/// The sizes and alignments only catch some of the changes, and none
+/// of the changes of the trait objects, so whenever a shared
+/// structure is changed:
+///
+/// 1. If a method is added to, removed from, reordered in or changed
+///    in the [`Freight`] trait, increase `FREIGHT_LAYOUT_VERSION`
+/// 2. If a field or a variant of a shared structure is added,
+///    removed, reordered or changed, or a method of a trait object
+///    it holds is, increase the layout version of that structure
+/// 3. If a new structure is passed between a plugin and the program
+///    using it, give it a layout version and add it to the list the
+///    fingerprint is computed from
+pub const LAYOUT_FINGERPRINT: u64 = layout_fingerprint();
+
+/// Layout version of [`Object`] along with the [`DkAny`] trait
+/// object it holds
+const OBJECT_LAYOUT_VERSION: u64 = 1;
+
+/// Layout version of [`Function`] along with the [`DuskCallable`]
+/// trait object it holds
+const FUNCTION_LAYOUT_VERSION: u64 = 1;
+
+/// Layout version of [`Parameter`] and [`Kwarg`]
+const PARAMETER_LAYOUT_VERSION: u64 = 1;
+
+/// Layout version of [`Type`]
+const TYPE_LAYOUT_VERSION: u64 = 1;
+
+/// Layout version of [`Module`]
+const MODULE_LAYOUT_VERSION: u64 = 1;
+
+/// Layout version of [`TraitDefinition`] and
+/// [`TraitFunctionDefinition`]
+const TRAIT_DEFINITION_LAYOUT_VERSION: u64 = 1;
+
+/// Layout version of [`TraitImplementation`] and [`TraitFunction`]
+const TRAIT_IMPLEMENTATION_LAYOUT_VERSION: u64 = 1;
+
+/// Layout version of [`FreightDeclaration`]
+const DECLARATION_LAYOUT_VERSION: u64 = 1;
+
+/// Layout version of [`InterplugRequest`]
+const REQUEST_LAYOUT_VERSION: u64 = 1;
+
+/// Layout version of [`Version`], [`VersionReq`] and
+/// [`VersionComparator`]
+const VERSION_LAYOUT_VERSION: u64 = 1;
+
+/// Layout version of [`Limitation`] and [`LimitationValue`]
+const LIMITATION_LAYOUT_VERSION: u64 = 1;
+
+/// Layout version of [`LimitationSchema`] and [`LimitationSetting`]
+const LIMITATION_SCHEMA_LAYOUT_VERSION: u64 = 1;
+
+/// Layout version of [`EffectiveSetting`], [`LimitationOutcome`],
+/// [`LimitationStatus`] and [`LimitationRejection`]
+const EFFECTIVE_SETTING_LAYOUT_VERSION: u64 = 1;
+
+/// Layout version of [`Error`]
+const ERROR_LAYOUT_VERSION: u64 = 1;
+
+/// Layout version of [`FreightProxy`] and [`WeakFreightProxy`],
+/// along with the cache they hold
+const PROXY_LAYOUT_VERSION: u64 = 1;
+
+/// Layout version of [`SyncFreightProxy`]
+const SYNC_PROXY_LAYOUT_VERSION: u64 = 1;
+
+/// Layout version of the [`Freight`] trait object. Has to be
+/// increased whenever a method is added to, removed from or changed
+/// in the [`Freight`] trait, as that changes its vtable
+const FREIGHT_LAYOUT_VERSION: u64 = 5;
+
+/// Get the layout version provided, along with the size and
+/// alignment of the structure
+const fn layout_of<T> (version: u64) -> [u64; 3] {
+    [
+        version,
+        std::mem::size_of::<T>() as u64,
+        std::mem::align_of::<T>() as u64,
+    ]
+}
+
+/// Compute the [`LAYOUT_FINGERPRINT`], hashing the layout versions,
+/// sizes and alignments of the shared structures with FNV-1a
+const fn layout_fingerprint () -> u64 {
+    let layout: [[u64; 3]; 29] = [
+        layout_of::<Object>(OBJECT_LAYOUT_VERSION),
+        layout_of::<Function>(FUNCTION_LAYOUT_VERSION),
+        layout_of::<Parameter>(PARAMETER_LAYOUT_VERSION),
+        layout_of::<Kwarg>(PARAMETER_LAYOUT_VERSION),
+        layout_of::<Type>(TYPE_LAYOUT_VERSION),
+        layout_of::<Module>(MODULE_LAYOUT_VERSION),
+        layout_of::<TraitDefinition>(TRAIT_DEFINITION_LAYOUT_VERSION),
+        layout_of::<TraitFunctionDefinition>(TRAIT_DEFINITION_LAYOUT_VERSION),
+        layout_of::<TraitImplementation>(TRAIT_IMPLEMENTATION_LAYOUT_VERSION),
+        layout_of::<TraitFunction>(TRAIT_IMPLEMENTATION_LAYOUT_VERSION),
+        layout_of::<FreightDeclaration>(DECLARATION_LAYOUT_VERSION),
+        layout_of::<InterplugRequest>(REQUEST_LAYOUT_VERSION),
+        layout_of::<Version>(VERSION_LAYOUT_VERSION),
+        layout_of::<VersionReq>(VERSION_LAYOUT_VERSION),
+        layout_of::<VersionComparator>(VERSION_LAYOUT_VERSION),
+        layout_of::<Limitation>(LIMITATION_LAYOUT_VERSION),
+        layout_of::<LimitationValue>(LIMITATION_LAYOUT_VERSION),
+        layout_of::<LimitationSchema>(LIMITATION_SCHEMA_LAYOUT_VERSION),
+        layout_of::<LimitationSetting>(LIMITATION_SCHEMA_LAYOUT_VERSION),
+        layout_of::<EffectiveSetting>(EFFECTIVE_SETTING_LAYOUT_VERSION),
+        layout_of::<LimitationOutcome>(EFFECTIVE_SETTING_LAYOUT_VERSION),
+        layout_of::<LimitationStatus>(EFFECTIVE_SETTING_LAYOUT_VERSION),
+        layout_of::<LimitationRejection>(EFFECTIVE_SETTING_LAYOUT_VERSION),
+        layout_of::<Error>(ERROR_LAYOUT_VERSION),
+        layout_of::<FreightProxy>(PROXY_LAYOUT_VERSION),
+        layout_of::<WeakFreightProxy>(PROXY_LAYOUT_VERSION),
+        layout_of::<FreightCache>(PROXY_LAYOUT_VERSION),
+        layout_of::<SyncFreightProxy>(SYNC_PROXY_LAYOUT_VERSION),
+        layout_of::<Box<dyn Freight>>(FREIGHT_LAYOUT_VERSION),
+    ];
+
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let mut i: usize = 0;
+    while i < layout.len() {
+        let mut field: usize = 0;
+        while field < 3 {
+            let mut byte: u32 = 0;
+            while byte < 8 {
+                hash ^= (layout[i][field] >> (byte * 8)) & 0xff;
+                hash = hash.wrapping_mul(0x100000001b3);
+                byte += 1;
+            }
+            field += 1;
+        }
+        i += 1;
+    }
+    return hash;
+}
+
 /// Rust compiler version parameter, passed from the compiler.
 ///
 /// If plugin is compiled with a different rust compiler version
@@ -64,18 +219,30 @@ pub static RUSTC_VERSION: &str = env!("RUSTC_VERSION");
 /// e.g in 1.2.3.4, 1 is major, 2 is minor, 3 is release and 4
 /// is build
 ///
+/// A version may also have a pre-release tag (`1.2.3.4-rc.2`), which
+/// makes it precede the same version without one, and build metadata
+/// (`1.2.3.4+nightly`), which is ignored when comparing versions.
+/// Pre-release tags are compared the way semantic versioning
+/// specifies: identifier by identifier, numeric identifiers
+/// numerically and before the alphanumeric ones, which are compared
+/// in ASCII order, a shorter tag preceding a longer one, if all of
+/// its identifiers are equal
+///
 /// # Example
 ///
 /// ```
 /// let a = dusk_api::Version { major: 1, ..Default::default() };
 /// let b = dusk_api::Version { minor: 1, ..Default::default() };
-/// let c = dusk_api::Version { major: 0, minor: 2, release: 1, build: 0 };
+/// let c = dusk_api::Version { major: 0, minor: 2, release: 1, ..Default::default() };
+/// let d = dusk_api::Version { major: 1, pre_release: "rc.2".to_string(), ..Default::default() };
 ///
 /// assert_eq!(a.cmp(&b), std::cmp::Ordering::Greater);
 /// assert_eq!(b.cmp(&c), std::cmp::Ordering::Less);
 /// assert_eq!(a.cmp(&c), std::cmp::Ordering::Greater);
+/// assert_eq!(d.cmp(&a), std::cmp::Ordering::Less);
+/// assert_eq!(d.cmp(&c), std::cmp::Ordering::Greater);
 /// ```
-#[derive(Copy, Clone, Debug, Eq)]
+#[derive(Clone, Debug, Default, Eq)]
 pub struct Version {
 
     /// Major version number
@@ -89,6 +256,15 @@ pub struct Version {
 
     /// Build version number
     pub build: usize,
+
+    /// Pre-release tag: dot separated identifiers, such as `alpha.1`,
+    /// or an empty string for a stable version
+    pub pre_release: String,
+
+    /// Build metadata: dot separated identifiers, such as
+    /// `nightly.20210510`, or an empty string if there is none. It is
+    /// ignored when comparing versions
+    pub metadata: String,
 }
 
 impl Ord for Version {
@@ -117,7 +293,47 @@ impl Ord for Version {
         if self.build < other.build {
             return std::cmp::Ordering::Less;
         }
-        return std::cmp::Ordering::Equal;
+        return compare_pre_releases(&self.pre_release, &other.pre_release);
+    }
+}
+
+/// Compare the pre-release tags of two versions with the same
+/// numbers
+fn compare_pre_releases (
+    first: &str,
+    second: &str,
+) -> std::cmp::Ordering {
+
+    // A stable version follows any pre-release of itself
+    match (first.is_empty(), second.is_empty()) {
+        (true, true) => return std::cmp::Ordering::Equal,
+        (true, false) => return std::cmp::Ordering::Greater,
+        (false, true) => return std::cmp::Ordering::Less,
+        (false, false) => (),
+    }
+
+    let mut first_parts: std::str::Split<'_, char> = first.split('.');
+    let mut second_parts: std::str::Split<'_, char> = second.split('.');
+    loop {
+        let (first_part, second_part): (&str, &str) =
+            match (first_parts.next(), second_parts.next()) {
+                (None, None) => return std::cmp::Ordering::Equal,
+                (None, Some(_)) => return std::cmp::Ordering::Less,
+                (Some(_), None) => return std::cmp::Ordering::Greater,
+                (Some(first_part), Some(second_part)) =>
+                    (first_part, second_part),
+            };
+        let ordering: std::cmp::Ordering =
+            match (first_part.parse::<u64>(), second_part.parse::<u64>()) {
+                (Ok(first_number), Ok(second_number)) =>
+                    first_number.cmp(&second_number),
+                (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+                (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+                (Err(_), Err(_)) => first_part.cmp(second_part),
+            };
+        if ordering != std::cmp::Ordering::Equal {
+            return ordering;
+        }
     }
 }
 
@@ -137,13 +353,765 @@ impl PartialEq for Version {
     }
 }
 
-impl Default for Version {
-    fn default () -> Version {
+impl Version {
+
+    /// Check if the version has a pre-release tag
+    pub fn is_pre_release (self: &Self) -> bool {
+        !self.pre_release.is_empty()
+    }
+
+    /// Check if the numbers of both versions are the same, whatever
+    /// their pre-release tags are
+    pub fn same_numbers (
+        self: &Self,
+        other: &Version,
+    ) -> bool {
+
+        self.major == other.major &&
+            self.minor == other.minor &&
+            self.release == other.release &&
+            self.build == other.build
+    }
+
+    /// Check if this version should be preferred to the one provided
+    /// when choosing the latest version: stable versions are always
+    /// preferred to pre-releases, so a pre-release is only chosen if
+    /// there is no stable version at all
+    pub fn is_preferred_to (
+        self: &Self,
+        other: &Version,
+    ) -> bool {
+
+        match (self.is_pre_release(), other.is_pre_release()) {
+            (false, true) => true,
+            (true, false) => false,
+            _ => self > other,
+        }
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        write!(f, "{}.{}.{}.{}", self.major, self.minor, self.release, self.build)?;
+        if self.is_pre_release() {
+            write!(f, "-{}", self.pre_release)?;
+        }
+        if !self.metadata.is_empty() {
+            write!(f, "+{}", self.metadata)?;
+        }
+        return Ok(());
+    }
+}
+
+/// Versions are parsed from up to four numbers, separated by dots,
+/// the numbers, that are not written, being zero, optionally
+/// followed by a pre-release tag after a `-` and build metadata
+/// after a `+`
+///
+/// # Example
+///
+/// ```
+/// let a: dusk_api::Version = "1.2.3.4".parse().unwrap();
+/// let b: dusk_api::Version = "1.2".parse().unwrap();
+/// let c: dusk_api::Version = "1.2-alpha.1+nightly".parse().unwrap();
+///
+/// assert_eq!(a, dusk_api::Version { major: 1, minor: 2, release: 3, build: 4, ..Default::default() });
+/// assert_eq!(b.to_string(), "1.2.0.0");
+/// assert_eq!(c.pre_release, "alpha.1");
+/// assert_eq!(c.to_string(), "1.2.0.0-alpha.1+nightly");
+/// ```
+impl std::str::FromStr for Version {
+    type Err = Error;
+
+    fn from_str (text: &str) -> Result<Version, Error> {
+        let (version, parts, wildcard): (Version, usize, bool) =
+            parse_partial(text)?;
+        if wildcard || parts == 0 {
+            return Err(ValueError(
+                    format!(
+                        "Invalid version: {}",
+                        text,
+                    )));
+        }
+        return Ok(version);
+    }
+}
+
+/// Parse a version, that may have less than four numbers or end
+/// with a wildcard (`*`, `x` or `X`), returning it along with the
+/// amount of numbers written and whether it ended with a wildcard
+fn parse_partial (
+    text: &str,
+) -> Result<(Version, usize, bool), Error> {
+
+    let invalid = || ValueError(
+        format!(
+            "Invalid version: {}",
+            text,
+        ));
+
+    // Split off the build metadata and the pre-release tag, both of
+    // which are dot separated non empty identifiers
+    let valid_tag = |tag: &str| tag.split('.').all(|part| !part.is_empty() &&
+        part.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'));
+    let (text, metadata): (&str, &str) = match text.trim().split_once('+') {
+        Some((text, metadata)) if valid_tag(metadata) => (text, metadata),
+        Some(_) => return Err(invalid()),
+        None => (text.trim(), ""),
+    };
+    let (text, pre_release): (&str, &str) = match text.split_once('-') {
+        Some((text, pre_release)) if valid_tag(pre_release) =>
+            (text, pre_release),
+        Some(_) => return Err(invalid()),
+        None => (text, ""),
+    };
+
+    let mut numbers: [usize; 4] = [0; 4];
+    let mut parts: usize = 0;
+    let mut wildcard: bool = false;
+    for part in text.split('.') {
+        if wildcard || parts == numbers.len() {
+            return Err(invalid());
+        }
+        match part {
+            "*" | "x" | "X" => wildcard = true,
+            _ => match part.parse::<usize>() {
+                Ok(number) => {
+                    numbers[parts] = number;
+                    parts += 1;
+                },
+                Err(_) => return Err(invalid()),
+            },
+        }
+    }
+    if wildcard && !(pre_release.is_empty() && metadata.is_empty()) {
+        return Err(invalid());
+    }
+
+    return Ok((
         Version {
-            major: 0,
-            minor: 0,
-            release: 0,
-            build: 0,
+            major: numbers[0],
+            minor: numbers[1],
+            release: numbers[2],
+            build: numbers[3],
+            pre_release: pre_release.to_string(),
+            metadata: metadata.to_string(),
+        },
+        parts,
+        wildcard,
+    ));
+}
+
+/// Get the smallest stable version, that is greater than every
+/// version, starting with the first `parts` numbers of the version
+/// provided, if there is one
+///
+/// If the last of those numbers is already the greatest possible
+/// one, the number before it is increased instead, and if all of
+/// them are, there is no such version
+fn bump (
+    version: &Version,
+    parts: usize,
+) -> Option<Version> {
+
+    let mut numbers: [usize; 4] = [
+        version.major,
+        version.minor,
+        version.release,
+        version.build,
+    ];
+    let mut position: usize = std::cmp::min(parts, 4);
+    while position > 0 {
+        position -= 1;
+        if let Some(number) = numbers[position].checked_add(1) {
+            numbers[position] = number;
+            for other in &mut numbers[position + 1..] {
+                *other = 0;
+            }
+            return Some(Version {
+                major: numbers[0],
+                minor: numbers[1],
+                release: numbers[2],
+                build: numbers[3],
+                ..Default::default()
+            });
+        }
+    }
+    return None;
+}
+
+/// Operation, a [`VersionComparator`] compares a version with
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VersionOp {
+
+    /// The version has to be exactly the same (`=`)
+    Exact,
+
+    /// The version has to be greater (`>`)
+    Greater,
+
+    /// The version has to be greater or the same (`>=`)
+    GreaterEq,
+
+    /// The version has to be less (`<`)
+    Less,
+
+    /// The version has to be less or the same (`<=`)
+    LessEq,
+}
+
+/// A single comparison, that is a part of a [`VersionReq`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionComparator {
+
+    /// The way the version is compared
+    pub op: VersionOp,
+
+    /// The version it is compared with
+    pub version: Version,
+}
+
+impl VersionComparator {
+
+    /// Check if the version provided passes the comparison
+    pub fn matches (
+        self: &Self,
+        version: &Version,
+    ) -> bool {
+
+        match self.op {
+            VersionOp::Exact => *version == self.version,
+            VersionOp::Greater => *version > self.version,
+            VersionOp::GreaterEq => *version >= self.version,
+            VersionOp::Less => *version < self.version,
+            VersionOp::LessEq => *version <= self.version,
+        }
+    }
+}
+
+impl std::fmt::Display for VersionComparator {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        let op: &str = match self.op {
+            VersionOp::Exact => "=",
+            VersionOp::Greater => ">",
+            VersionOp::GreaterEq => ">=",
+            VersionOp::Less => "<",
+            VersionOp::LessEq => "<=",
+        };
+        write!(f, "{}{}", op, self.version)
+    }
+}
+
+/// A requirement, a version has to match, made of any amount of
+/// comparisons, all of which have to pass
+///
+/// Requirements are parsed from comparisons, separated by commas,
+/// each of which is one of
+///
+/// * `*` -- any version
+/// * `=1.2`, `>1.2`, `>=1.2`, `<1.2`, `<=1.2` -- the numbers, that
+///   are not written, match anything, so `=1.2` is any `1.2.x.x`
+///   version and `>1.2` is anything starting from `1.3`
+/// * `^1.2` or just `1.2` -- a version, that is at least the one
+///   written, but has the same first non zero number, so any version
+///   from `1.2` up to, but not including `2.0` matches
+/// * `~1.2` -- a version, that is at least the one written, but
+///   has the same major and minor numbers
+/// * `1.2.*` -- any version, starting with `1.2`
+/// * `1.2 - 1.5` -- any version from `1.2` up to `1.5.x.x`
+///
+/// A requirement is printed as the comparisons it was turned into,
+/// so `^1.2` is printed as `>=1.2.0.0, <2.0.0.0`
+///
+/// Pre-release versions are not matched, unless one of the
+/// comparisons is made with a pre-release of the same numbers, or
+/// the requirement explicitly allows them (see
+/// [`VersionReq::pre_releases`]), so a nightly build is never picked
+/// instead of a stable one by accident
+///
+/// # Example
+///
+/// ```
+/// use dusk_api::{Version, VersionReq};
+///
+/// let req: VersionReq = "^1.2, <1.5".parse().unwrap();
+///
+/// assert!(req.matches(&"1.4.9".parse().unwrap()));
+/// assert!(!req.matches(&"1.5".parse().unwrap()));
+/// assert!(!req.matches(&"1.1".parse().unwrap()));
+/// assert_eq!(req.to_string(), ">=1.2.0.0, <2.0.0.0, <1.5.0.0");
+///
+/// assert!(!req.matches(&"1.4-nightly".parse().unwrap()));
+/// assert!(req.with_pre_releases().matches(&"1.4-nightly".parse().unwrap()));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VersionReq {
+
+    /// Comparisons, all of which have to pass for a version to
+    /// match the requirement. If there are none, any version matches
+    pub comparators: Vec<VersionComparator>,
+
+    /// Whether any pre-release version may match the requirement
+    ///
+    /// If not, a pre-release only matches, if one of the comparisons
+    /// is made with a pre-release of the same numbers, so `>=1.2-rc.1`
+    /// matches `1.2-rc.2`, but not `1.3-rc.1`
+    pub pre_releases: bool,
+}
+
+impl VersionReq {
+
+    /// Get a requirement, that any version matches
+    pub fn any () -> VersionReq {
+        VersionReq {
+            comparators: Vec::new(),
+            pre_releases: false,
+        }
+    }
+
+    /// Get a requirement, that only the version provided matches
+    pub fn exact (version: Version) -> VersionReq {
+        VersionReq {
+            comparators: vec![VersionComparator {
+                op: VersionOp::Exact,
+                version: version,
+            }],
+            pre_releases: false,
+        }
+    }
+
+    /// Get a requirement, that the version provided and every
+    /// version after it matches
+    pub fn at_least (version: Version) -> VersionReq {
+        VersionReq {
+            comparators: vec![VersionComparator {
+                op: VersionOp::GreaterEq,
+                version: version,
+            }],
+            pre_releases: false,
+        }
+    }
+
+    /// Get the same requirement, that any pre-release version may
+    /// match as well
+    pub fn with_pre_releases (mut self: Self) -> VersionReq {
+        self.pre_releases = true;
+        return self;
+    }
+
+    /// Check if the version provided matches the requirement
+    pub fn matches (
+        self: &Self,
+        version: &Version,
+    ) -> bool {
+
+        self.allows_pre_release(version) &&
+            self.comparators.iter().all(|comparator| comparator.matches(version))
+    }
+
+    /// Check if the version provided is either stable, or a
+    /// pre-release, that may match the requirement (see
+    /// [`VersionReq::pre_releases`])
+    pub fn allows_pre_release (
+        self: &Self,
+        version: &Version,
+    ) -> bool {
+
+        !version.is_pre_release() || self.pre_releases ||
+            self.comparators.iter().any(|comparator|
+                comparator.version.is_pre_release() &&
+                comparator.version.same_numbers(version))
+    }
+
+    /// Check if any version from the lowest to the highest one
+    /// provided (both included) matches the requirement
+    ///
+    /// This is used to check if a plugin may be used by the code,
+    /// written for the required versions: the plugin of some version
+    /// can be used by the code, written for any version from its
+    /// backwards compatibility version up to the version itself
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dusk_api::{Version, VersionReq};
+    ///
+    /// let req: VersionReq = Version { major: 1, minor: 4, ..Default::default() }.into();
+    ///
+    /// assert!(req.matches_range(&"1.2".parse().unwrap(), &"1.6".parse().unwrap()));
+    /// assert!(!req.matches_range(&"1.5".parse().unwrap(), &"1.6".parse().unwrap()));
+    /// ```
+    pub fn matches_range (
+        self: &Self,
+        lowest: &Version,
+        highest: &Version,
+    ) -> bool {
+
+        // Every version from the lowest one, that is not yet past
+        // the upper bound
+        let mut lowest: &Version = lowest;
+        let mut lowest_included: bool = true;
+        let mut highest: &Version = highest;
+        let mut highest_included: bool = true;
+        for comparator in &self.comparators {
+            let version: &Version = &comparator.version;
+            match comparator.op {
+                VersionOp::Exact | VersionOp::GreaterEq |
+                    VersionOp::Greater if version >= lowest => {
+
+                    let included: bool = comparator.op != VersionOp::Greater;
+                    if version > lowest || !included {
+                        lowest_included = included;
+                    }
+                    lowest = version;
+                },
+                _ => (),
+            }
+            match comparator.op {
+                VersionOp::Exact | VersionOp::LessEq |
+                    VersionOp::Less if version <= highest => {
+
+                    let included: bool = comparator.op != VersionOp::Less;
+                    if version < highest || !included {
+                        highest_included = included;
+                    }
+                    highest = version;
+                },
+                _ => (),
+            }
+        }
+        return lowest < highest ||
+            (lowest == highest && lowest_included && highest_included);
+    }
+
+    /// Find the greatest of the versions provided, that matches the
+    /// requirement, returning its position
+    pub fn best_match (
+        self: &Self,
+        versions: &[Version],
+    ) -> Option<usize> {
+
+        let mut best: Option<(usize, &Version)> = None;
+        for (position, version) in versions.iter().enumerate() {
+            if !self.matches(version) {
+                continue;
+            }
+            best = match best {
+                Some((_, best_version)) if best_version >= version => best,
+                _ => Some((position, version)),
+            };
+        }
+        return best.map(|(position, _)| position);
+    }
+
+    /// Add the comparisons, a single part of a written requirement
+    /// is turned into
+    fn push_part (
+        self: &mut Self,
+        text: &str,
+    ) -> Result<(), Error> {
+
+        // Inclusive range
+        if let Some(position) = text.find(" - ") {
+            self.push_op(">=", &text[..position])?;
+            return self.push_op("<=", &text[position + 3..]);
+        }
+
+        for op in &[">=", "<=", ">", "<", "=", "^", "~"] {
+            if let Some(rest) = text.strip_prefix(op) {
+                return self.push_op(op, rest);
+            }
         }
+        return self.push_op("", text);
+    }
+
+    /// Add the comparisons, needed to compare a version with the
+    /// one written, using the operation provided
+    fn push_op (
+        self: &mut Self,
+        op: &str,
+        text: &str,
+    ) -> Result<(), Error> {
+
+        let (version, parts, wildcard): (Version, usize, bool) =
+            parse_partial(text)?;
+        if parts == 0 && !wildcard {
+            return Err(ValueError(
+                    format!(
+                        "Invalid version: {}",
+                        text,
+                    )));
+        }
+
+        // A version, where every number is written, is compared with
+        // directly, otherwise the numbers, that are not written, may
+        // be anything
+        let exact: bool = parts == 4;
+        let significant: usize = match op {
+            "~" => std::cmp::min(parts, 2),
+            "^" | "" if op == "^" || !wildcard => {
+                let numbers: [usize; 4] = [
+                    version.major,
+                    version.minor,
+                    version.release,
+                    version.build,
+                ];
+                match numbers[..parts].iter()
+                    .position(|number| *number != 0) {
+
+                    Some(position) => position + 1,
+                    None => parts,
+                }
+            },
+            _ => parts,
+        };
+
+        // The smallest version, that is greater than every one, that
+        // starts with the numbers written. If there is none, an upper
+        // limit does not limit anything, while a lower one is never
+        // reached
+        let next: Option<Version> = bump(&version, significant);
+        let mut push = |op: VersionOp, version: &Version| {
+            self.comparators.push(VersionComparator {
+                op: op,
+                version: version.clone(),
+            });
+        };
+        match (op, next) {
+            _ if parts == 0 => (),
+            (">=", _) => push(VersionOp::GreaterEq, &version),
+            ("<", _) => push(VersionOp::Less, &version),
+            (">", _) if exact => push(VersionOp::Greater, &version),
+            (">", Some(next)) => push(VersionOp::GreaterEq, &next),
+            (">", None) => push(VersionOp::Greater, &Version {
+                major: usize::MAX,
+                minor: usize::MAX,
+                release: usize::MAX,
+                build: usize::MAX,
+                ..Default::default()
+            }),
+            ("<=", _) if exact => push(VersionOp::LessEq, &version),
+            ("<=", Some(next)) => push(VersionOp::Less, &next),
+            ("<=", None) => (),
+            ("=", _) if exact => push(VersionOp::Exact, &version),
+            (_, next) => {
+                push(VersionOp::GreaterEq, &version);
+                if let Some(next) = next {
+                    push(VersionOp::Less, &next);
+                }
+            },
+        }
+        return Ok(());
+    }
+}
+
+impl std::fmt::Display for VersionReq {
+    fn fmt (
+        self: &Self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+
+        if self.comparators.is_empty() {
+            return f.write_str("*");
+        }
+        for (position, comparator) in self.comparators.iter().enumerate() {
+            if position > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", comparator)?;
+        }
+        return Ok(());
+    }
+}
+
+impl std::str::FromStr for VersionReq {
+    type Err = Error;
+
+    fn from_str (text: &str) -> Result<VersionReq, Error> {
+        let mut result: VersionReq = VersionReq::any();
+        for part in text.split(',') {
+            result.push_part(part.trim())?;
+        }
+        return Ok(result);
+    }
+}
+
+/// A single version is turned into the requirement, that only the
+/// version provided matches, so a plugin is accepted if the version
+/// provided is in between its backwards compatibility version and
+/// its own version (see [`VersionReq::matches_range`])
+impl From<Version> for VersionReq {
+    fn from (version: Version) -> VersionReq {
+        VersionReq::exact(version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parse the version, written in the test
+    fn version (text: &str) -> Version {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn pre_release_precedence () {
+        let ordered: Vec<Version> = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.0.1-alpha",
+            "1.0.0.1",
+        ].iter().map(|text| version(text)).collect();
+
+        for (position, lower) in ordered.iter().enumerate() {
+            for higher in &ordered[position + 1..] {
+                assert!(lower < higher, "{} < {}", lower, higher);
+                assert!(higher > lower, "{} > {}", higher, lower);
+            }
+        }
+    }
+
+    #[test]
+    fn numeric_identifiers () {
+        // Numeric identifiers are compared as numbers and always come
+        // before the alphanumeric ones
+        assert!(version("1.0-2") < version("1.0-10"));
+        assert!(version("1.0-10") < version("1.0-a"));
+        assert!(version("1.0-rc.9") < version("1.0-rc.10"));
+        assert!(version("1.0-rc.1a") > version("1.0-rc.10"));
+        assert!(version("1.0-Beta") < version("1.0-beta"));
+    }
+
+    #[test]
+    fn metadata_is_ignored () {
+        assert_eq!(version("1.2+nightly"), version("1.2"));
+        assert_eq!(version("1.2-rc.1+a"), version("1.2-rc.1+b"));
+        assert_eq!(
+            version("1.2+a").cmp(&version("1.2+b")),
+            std::cmp::Ordering::Equal);
+        assert!(version("1.2-rc.1+z") < version("1.2+a"));
+        assert_eq!(version("1.2-rc.1+nightly.5").metadata, "nightly.5");
+    }
+
+    #[test]
+    fn stable_versions_are_preferred () {
+        assert!(version("1.0").is_preferred_to(&version("2.0-rc.1")));
+        assert!(!version("2.0-rc.1").is_preferred_to(&version("1.0")));
+        assert!(version("2.0-rc.2").is_preferred_to(&version("2.0-rc.1")));
+    }
+
+    /// Parse the requirement, written in the test
+    fn requirement (text: &str) -> VersionReq {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn caret_requirements () {
+        assert_eq!(requirement("^1.2").to_string(), ">=1.2.0.0, <2.0.0.0");
+        assert_eq!(requirement("1.2").to_string(), ">=1.2.0.0, <2.0.0.0");
+        assert_eq!(requirement("^0.2.3").to_string(), ">=0.2.3.0, <0.3.0.0");
+        assert_eq!(requirement("^0.0.3").to_string(), ">=0.0.3.0, <0.0.4.0");
+        assert_eq!(requirement("^0").to_string(), ">=0.0.0.0, <1.0.0.0");
+        assert_eq!(requirement("^1.2.*").to_string(), ">=1.2.0.0, <2.0.0.0");
+
+        assert!(requirement("^1.2").matches(&version("1.9.9.9")));
+        assert!(!requirement("^1.2").matches(&version("2.0")));
+        assert!(!requirement("^1.2").matches(&version("1.1.9")));
+    }
+
+    #[test]
+    fn tilde_requirements () {
+        assert_eq!(requirement("~1.2.3").to_string(), ">=1.2.3.0, <1.3.0.0");
+        assert_eq!(requirement("~1").to_string(), ">=1.0.0.0, <2.0.0.0");
+        assert_eq!(requirement("~0.0.3").to_string(), ">=0.0.3.0, <0.1.0.0");
+
+        assert!(requirement("~1.2.3").matches(&version("1.2.9")));
+        assert!(!requirement("~1.2.3").matches(&version("1.3")));
+    }
+
+    #[test]
+    fn comparison_requirements () {
+        assert_eq!(requirement(">1.2").to_string(), ">=1.3.0.0");
+        assert_eq!(requirement(">1.2.3.4").to_string(), ">1.2.3.4");
+        assert_eq!(requirement("<=1.2").to_string(), "<1.3.0.0");
+        assert_eq!(requirement("=1.2").to_string(), ">=1.2.0.0, <1.3.0.0");
+        assert_eq!(requirement("=1.2.3.4").to_string(), "=1.2.3.4");
+        assert_eq!(requirement(">=1.2, <1.5").to_string(), ">=1.2.0.0, <1.5.0.0");
+
+        assert!(requirement("<=1.2").matches(&version("1.2.9")));
+        assert!(!requirement(">1.2").matches(&version("1.2.9")));
+    }
+
+    #[test]
+    fn range_and_wildcard_requirements () {
+        assert_eq!(requirement("1.2 - 1.5").to_string(), ">=1.2.0.0, <1.6.0.0");
+        assert_eq!(requirement("1.2.*").to_string(), ">=1.2.0.0, <1.3.0.0");
+        assert_eq!(requirement("1.x").to_string(), ">=1.0.0.0, <2.0.0.0");
+        assert_eq!(requirement("*").to_string(), "*");
+        assert_eq!(requirement("*"), VersionReq::any());
+
+        assert!(requirement("1.2 - 1.5").matches(&version("1.5.9")));
+        assert!(!requirement("1.2 - 1.5").matches(&version("1.6")));
+        assert!(requirement("*").matches(&version("7.0")));
+    }
+
+    #[test]
+    fn overflowing_requirements () {
+        let max: usize = usize::MAX;
+        assert_eq!(
+            requirement(&format!("^{}", max)).to_string(),
+            format!(">={}.0.0.0", max));
+        assert_eq!(
+            requirement(&format!("~1.{}", max)).to_string(),
+            format!(">=1.{}.0.0, <2.0.0.0", max));
+        assert!(requirement(&format!("<={}", max)).matches(&version("9.0")));
+    }
+
+    #[test]
+    fn invalid_requirements () {
+        for text in &["", "^", ">=", "1.2.3.4.5", "1.*.3", "a.b", "1.2 -"] {
+            assert!(text.parse::<VersionReq>().is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn pre_release_requirements () {
+        assert!(!requirement("^1.2").matches(&version("1.4-nightly")));
+        assert!(requirement(">=1.2-rc.1").matches(&version("1.2-rc.2")));
+        assert!(!requirement(">=1.2-rc.1").matches(&version("1.3-rc.1")));
+        assert!(requirement("^1.2").with_pre_releases()
+            .matches(&version("1.4-nightly")));
+    }
+
+    #[test]
+    fn range_requirements () {
+        let exact: VersionReq = version("1.4").into();
+        assert!(exact.matches_range(&version("1.2"), &version("1.6")));
+        assert!(exact.matches_range(&version("1.4"), &version("1.4")));
+        assert!(!exact.matches_range(&version("1.5"), &version("1.6")));
+        assert!(!exact.matches_range(&version("1.0"), &version("1.3")));
+
+        assert!(requirement("^1.2").matches_range(&version("0.9"), &version("1.2")));
+        assert!(!requirement("^1.2").matches_range(&version("2.0"), &version("2.3")));
+        assert!(requirement(">=1.2, <1.5").matches_range(&version("1.4"), &version("2.0")));
+        assert!(!requirement(">=1.2, <1.5").matches_range(&version("1.5"), &version("2.0")));
+        assert!(!requirement(">1.5").matches_range(&version("1.0"), &version("1.5")));
+        assert!(!requirement(">1.5, <1.5").matches_range(&version("0.0"), &version("9.0")));
+        assert!(requirement("*").matches_range(&version("1.0"), &version("1.0")));
     }
 }
@@ -0,0 +1,1507 @@
+// Copyright (C) 2021 by Andy Gozas <andy@gozas.me>
+//
+// This file is part of Dusk API.
+//
+// Dusk API is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Dusk API is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Dusk API.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Module, containing the binary encoding, used to pass values
+//! between the program and a plugin, that can not share Rust values
+//! directly, because they were built with different compilers or
+//! run in different processes
+//!
+//! Every number is written as 8 little endian bytes, every string
+//! and vector is written as its length followed by its items, every
+//! enum is written as a one byte tag followed by its fields. The
+//! requests, nested in other requests, and the submodules are only
+//! decoded up to [`MAX_WIRE_DEPTH`] levels deep.
+//!
+//! [`TypeId`] values can not be passed this way, so every decoded
+//! [`TypeId`] is the [`TypeId`] of [`ForeignObject`]. Callables are
+//! not passed either, the decoded functions hold an
+//! [`EmptyCallable`] until the receiving side replaces it.
+
+use crate::*;
+use crate::remote::{TypeTable, foreign_generator};
+
+/// A structure, values are encoded into
+#[derive(Debug, Default)]
+pub(crate) struct WireWriter {
+
+    /// The encoded data
+    pub(crate) buffer: Vec<u8>,
+}
+
+/// A structure, values are decoded from
+#[derive(Debug)]
+pub(crate) struct WireReader<'a> {
+
+    /// The data being decoded
+    data: &'a [u8],
+
+    /// Position of the next byte to be read
+    cursor: usize,
+
+    /// Types, used to build the decoded objects
+    types: &'a mut TypeTable,
+
+    /// How many values, that may be nested in the values of the same
+    /// kind, the value being decoded is nested in
+    depth: usize,
+}
+
+/// The greatest depth, the requests nested in other requests and the
+/// submodules of modules are decoded to, so that a misbehaving peer
+/// can not overflow the stack of the decoding side
+pub(crate) const MAX_WIRE_DEPTH: usize = 64;
+
+/// Trait, implemented for everything that can be encoded
+pub(crate) trait Wire: Sized {
+
+    /// Encode the value, appending it to the writer
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error>;
+
+    /// Decode the value, starting at the reader cursor
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<Self, Error>;
+}
+
+impl WireWriter {
+
+    /// Create an empty writer
+    pub(crate) fn new () -> WireWriter {
+        WireWriter {
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Append raw bytes
+    pub(crate) fn write_bytes (
+        self: &mut Self,
+        bytes: &[u8],
+    ) {
+
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Encode a value
+    pub(crate) fn write<T> (
+        self: &mut Self,
+        value: &T,
+    ) -> Result<(), Error> where T: Wire {
+
+        value.write_wire(self)
+    }
+}
+
+impl <'a> WireReader<'a> {
+
+    /// Create a reader for the data provided
+    pub(crate) fn new (
+        data: &'a [u8],
+        types: &'a mut TypeTable,
+    ) -> WireReader<'a> {
+
+        WireReader {
+            data: data,
+            cursor: 0,
+            types: types,
+            depth: 0,
+        }
+    }
+
+    /// Read the amount of raw bytes provided
+    pub(crate) fn read_bytes (
+        self: &mut Self,
+        amount: usize,
+    ) -> Result<&'a [u8], Error> {
+
+        if self.data.len() - self.cursor < amount {
+            return Err(ValueError(
+                    "Unexpected end of encoded data".to_string()
+            ));
+        }
+        let result: &'a [u8] = &self.data[self.cursor..self.cursor + amount];
+        self.cursor += amount;
+        return Ok(result);
+    }
+
+    /// Decode a value
+    pub(crate) fn read<T> (
+        self: &mut Self,
+    ) -> Result<T, Error> where T: Wire {
+
+        T::read_wire(self)
+    }
+
+    /// Decode a value with the function provided, one level deeper,
+    /// failing if it is nested deeper than [`MAX_WIRE_DEPTH`]
+    fn read_nested<T, F> (
+        self: &mut Self,
+        read: F,
+    ) -> Result<T, Error> where F: FnOnce(&mut Self) -> Result<T, Error> {
+
+        if self.depth >= MAX_WIRE_DEPTH {
+            return Err(ValueError(
+                    "Encoded data is nested too deep".to_string()
+            ));
+        }
+        self.depth += 1;
+        let result: Result<T, Error> = read(self);
+        self.depth -= 1;
+        return result;
+    }
+
+    /// Decode the length of a string or a vector, making sure there
+    /// is enough data left for it to possibly be that long
+    fn read_len (self: &mut Self) -> Result<usize, Error> {
+        let len: usize = self.read()?;
+        if len > self.data.len() - self.cursor {
+            return Err(ValueError(
+                    "Encoded length exceeds the data size".to_string()
+            ));
+        }
+        return Ok(len);
+    }
+}
+
+/// Encode a value into a new buffer
+pub(crate) fn encode<T> (
+    value: &T,
+) -> Result<Vec<u8>, Error> where T: Wire {
+
+    let mut writer: WireWriter = WireWriter::new();
+    writer.write(value)?;
+    return Ok(writer.buffer);
+}
+
+/// Decode a value, that does not contain any objects, from the
+/// buffer provided
+pub(crate) fn decode<T> (
+    data: &[u8],
+) -> Result<T, Error> where T: Wire {
+
+    let mut types: TypeTable = TypeTable::default();
+    let mut reader: WireReader<'_> = WireReader::new(data, &mut types);
+    reader.read()
+}
+
+/// Build an error, reporting an unknown enum tag
+fn tag_error (
+    name: &str,
+    tag: u8,
+) -> Error {
+
+    ValueError(
+        format!(
+            "Unknown {} tag {}",
+            name,
+            tag,
+        ))
+}
+
+impl Wire for u8 {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.buffer.push(*self);
+        return Ok(());
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<u8, Error> {
+
+        Ok(reader.read_bytes(1)?[0])
+    }
+}
+
+impl Wire for u64 {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write_bytes(&self.to_le_bytes());
+        return Ok(());
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<u64, Error> {
+
+        let mut bytes: [u8; 8] = [0; 8];
+        bytes.copy_from_slice(reader.read_bytes(8)?);
+        return Ok(u64::from_le_bytes(bytes));
+    }
+}
+
+impl Wire for u32 {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&(*self as u64))
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<u32, Error> {
+
+        let value: u64 = reader.read()?;
+        if value > u32::MAX as u64 {
+            return Err(OverflowError(
+                    "Encoded number does not fit in u32".to_string()
+            ));
+        }
+        return Ok(value as u32);
+    }
+}
+
+impl Wire for usize {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&(*self as u64))
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<usize, Error> {
+
+        let value: u64 = reader.read()?;
+        if value > usize::MAX as u64 {
+            return Err(OverflowError(
+                    "Encoded number does not fit in usize".to_string()
+            ));
+        }
+        return Ok(value as usize);
+    }
+}
+
+impl Wire for isize {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&(*self as i64 as u64))
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<isize, Error> {
+
+        let value: i64 = reader.read::<u64>()? as i64;
+        if value > isize::MAX as i64 || value < isize::MIN as i64 {
+            return Err(OverflowError(
+                    "Encoded number does not fit in isize".to_string()
+            ));
+        }
+        return Ok(value as isize);
+    }
+}
+
+impl Wire for bool {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&(*self as u8))
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<bool, Error> {
+
+        match reader.read::<u8>()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(tag_error("bool", tag)),
+        }
+    }
+}
+
+impl Wire for String {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.len())?;
+        writer.write_bytes(self.as_bytes());
+        return Ok(());
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<String, Error> {
+
+        let len: usize = reader.read_len()?;
+        match String::from_utf8(reader.read_bytes(len)?.to_vec()) {
+            Ok(string) => Ok(string),
+            Err(_) => Err(ValueError(
+                    "Encoded string is not valid unicode".to_string()
+            )),
+        }
+    }
+}
+
+impl <T> Wire for Vec<T>
+where
+    T: Wire
+{
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.len())?;
+        for item in self {
+            writer.write(item)?;
+        }
+        return Ok(());
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<Vec<T>, Error> {
+
+        let len: usize = reader.read_len()?;
+        let mut result: Vec<T> = Vec::new();
+        for _i in 0..len {
+            result.push(reader.read()?);
+        }
+        return Ok(result);
+    }
+}
+
+impl <T> Wire for Option<T>
+where
+    T: Wire
+{
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        match self {
+            None => writer.write(&0u8),
+            Some(value) => {
+                writer.write(&1u8)?;
+                writer.write(value)
+            },
+        }
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<Option<T>, Error> {
+
+        match reader.read::<u8>()? {
+            0 => Ok(None),
+            1 => Ok(Some(reader.read()?)),
+            tag => Err(tag_error("option", tag)),
+        }
+    }
+}
+
+impl <T> Wire for Result<T, Error>
+where
+    T: Wire
+{
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        match self {
+            Ok(value) => {
+                writer.write(&0u8)?;
+                writer.write(value)
+            },
+            Err(err) => {
+                writer.write(&1u8)?;
+                writer.write(err)
+            },
+        }
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<Result<T, Error>, Error> {
+
+        match reader.read::<u8>()? {
+            0 => Ok(Ok(reader.read()?)),
+            1 => Ok(Err(reader.read()?)),
+            tag => Err(tag_error("result", tag)),
+        }
+    }
+}
+
+impl Wire for () {
+    fn write_wire (
+        self: &Self,
+        _writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        Ok(())
+    }
+
+    fn read_wire (
+        _reader: &mut WireReader<'_>,
+    ) -> Result<(), Error> {
+
+        Ok(())
+    }
+}
+
+impl Wire for Version {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.major)?;
+        writer.write(&self.minor)?;
+        writer.write(&self.release)?;
+        writer.write(&self.build)?;
+        writer.write(&self.pre_release)?;
+        writer.write(&self.metadata)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<Version, Error> {
+
+        Ok(Version {
+            major: reader.read()?,
+            minor: reader.read()?,
+            release: reader.read()?,
+            build: reader.read()?,
+            pre_release: reader.read()?,
+            metadata: reader.read()?,
+        })
+    }
+}
+
+impl Wire for VersionComparator {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        let op: u8 = match self.op {
+            VersionOp::Exact => 0,
+            VersionOp::Greater => 1,
+            VersionOp::GreaterEq => 2,
+            VersionOp::Less => 3,
+            VersionOp::LessEq => 4,
+        };
+        writer.write(&op)?;
+        writer.write(&self.version)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<VersionComparator, Error> {
+
+        let op: VersionOp = match reader.read::<u8>()? {
+            0 => VersionOp::Exact,
+            1 => VersionOp::Greater,
+            2 => VersionOp::GreaterEq,
+            3 => VersionOp::Less,
+            4 => VersionOp::LessEq,
+            tag => return Err(tag_error("version operation", tag)),
+        };
+        Ok(VersionComparator {
+            op: op,
+            version: reader.read()?,
+        })
+    }
+}
+
+impl Wire for VersionReq {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.comparators)?;
+        writer.write(&self.pre_releases)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<VersionReq, Error> {
+
+        Ok(VersionReq {
+            comparators: reader.read()?,
+            pre_releases: reader.read()?,
+        })
+    }
+}
+
+impl Wire for InterplugRequest {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        match self {
+            PlugRequest { plugin, fn_ids, version } => {
+                writer.write(&0u8)?;
+                writer.write(plugin)?;
+                writer.write(fn_ids)?;
+                writer.write(version)
+            },
+            TraitRequest { plugin, trait_id, fn_ids, version } => {
+                writer.write(&1u8)?;
+                writer.write(plugin)?;
+                writer.write(trait_id)?;
+                writer.write(fn_ids)?;
+                writer.write(version)
+            },
+            PlugRequestAll { plugin, version } => {
+                writer.write(&2u8)?;
+                writer.write(plugin)?;
+                writer.write(version)
+            },
+            TraitRequestAll { plugin, trait_id, version } => {
+                writer.write(&3u8)?;
+                writer.write(plugin)?;
+                writer.write(trait_id)?;
+                writer.write(version)
+            },
+            RequestEither { requests } => {
+                writer.write(&4u8)?;
+                writer.write(requests)
+            },
+            RequestEach { requests } => {
+                writer.write(&5u8)?;
+                writer.write(requests)
+            },
+            RequestCrucial { request } => {
+                writer.write(&6u8)?;
+                writer.write(&**request)
+            },
+            RequestOptional { request } => {
+                writer.write(&7u8)?;
+                writer.write(&**request)
+            },
+        }
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<InterplugRequest, Error> {
+
+        reader.read_nested(|reader: &mut WireReader<'_>| {
+            match reader.read::<u8>()? {
+                0 => Ok(PlugRequest {
+                    plugin: reader.read()?,
+                    fn_ids: reader.read()?,
+                    version: reader.read()?,
+                }),
+                1 => Ok(TraitRequest {
+                    plugin: reader.read()?,
+                    trait_id: reader.read()?,
+                    fn_ids: reader.read()?,
+                    version: reader.read()?,
+                }),
+                2 => Ok(PlugRequestAll {
+                    plugin: reader.read()?,
+                    version: reader.read()?,
+                }),
+                3 => Ok(TraitRequestAll {
+                    plugin: reader.read()?,
+                    trait_id: reader.read()?,
+                    version: reader.read()?,
+                }),
+                4 => Ok(RequestEither {
+                    requests: reader.read()?,
+                }),
+                5 => Ok(RequestEach {
+                    requests: reader.read()?,
+                }),
+                6 => Ok(RequestCrucial {
+                    request: Box::new(reader.read()?),
+                }),
+                7 => Ok(RequestOptional {
+                    request: Box::new(reader.read()?),
+                }),
+                tag => Err(tag_error("request", tag)),
+            }
+        })
+    }
+}
+
+impl Wire for Limitation {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        match self {
+            Limitation::Top { setting, limit } => {
+                writer.write(&0u8)?;
+                writer.write(setting)?;
+                writer.write(limit)
+            },
+            Limitation::Bottom { setting, limit } => {
+                writer.write(&1u8)?;
+                writer.write(setting)?;
+                writer.write(limit)
+            },
+            Limitation::Reset { setting } => {
+                writer.write(&2u8)?;
+                writer.write(setting)
+            },
+            Limitation::Set { setting, value } => {
+                writer.write(&3u8)?;
+                writer.write(setting)?;
+                writer.write(value)
+            },
+        }
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<Limitation, Error> {
+
+        match reader.read::<u8>()? {
+            0 => Ok(Limitation::Top {
+                setting: reader.read()?,
+                limit: reader.read()?,
+            }),
+            1 => Ok(Limitation::Bottom {
+                setting: reader.read()?,
+                limit: reader.read()?,
+            }),
+            2 => Ok(Limitation::Reset {
+                setting: reader.read()?,
+            }),
+            3 => Ok(Limitation::Set {
+                setting: reader.read()?,
+                value: reader.read()?,
+            }),
+            tag => Err(tag_error("limitation", tag)),
+        }
+    }
+}
+
+// Paths are sent as strings, so the ones, that are not valid
+// unicode, do not survive the trip
+impl Wire for LimitationValue {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        match self {
+            LimitationValue::Path(path) => {
+                writer.write(&0u8)?;
+                writer.write(&path.to_string_lossy().into_owned())
+            },
+            LimitationValue::String(string) => {
+                writer.write(&1u8)?;
+                writer.write(string)
+            },
+            LimitationValue::StringList(list) => {
+                writer.write(&2u8)?;
+                writer.write(list)
+            },
+            LimitationValue::Boolean(flag) => {
+                writer.write(&3u8)?;
+                writer.write(flag)
+            },
+            LimitationValue::Duration(duration) => {
+                writer.write(&4u8)?;
+                writer.write(&duration.as_secs())?;
+                writer.write(&duration.subsec_nanos())
+            },
+        }
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<LimitationValue, Error> {
+
+        match reader.read::<u8>()? {
+            0 => Ok(LimitationValue::Path(
+                reader.read::<String>()?.into())),
+            1 => Ok(LimitationValue::String(reader.read()?)),
+            2 => Ok(LimitationValue::StringList(reader.read()?)),
+            3 => Ok(LimitationValue::Boolean(reader.read()?)),
+            4 => {
+                let secs: u64 = reader.read()?;
+                let nanos: u32 = reader.read()?;
+                // Duration::new panics, if the nanoseconds carry over
+                // past the greatest amount of seconds
+                if nanos >= 1_000_000_000 {
+                    return Err(ValueError(
+                            format!(
+                                "Invalid duration nanoseconds {}",
+                                nanos,
+                            )));
+                }
+                Ok(LimitationValue::Duration(
+                    std::time::Duration::new(secs, nanos)))
+            },
+            tag => Err(tag_error("limitation value", tag)),
+        }
+    }
+}
+
+impl Wire for LimitationSetting {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.name)?;
+        writer.write(&self.description)?;
+        writer.write(&self.default)?;
+        writer.write(&self.default_value)?;
+        writer.write(&self.min)?;
+        writer.write(&self.max)?;
+        writer.write(&self.units)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<LimitationSetting, Error> {
+
+        Ok(LimitationSetting {
+            name: reader.read()?,
+            description: reader.read()?,
+            default: reader.read()?,
+            default_value: reader.read()?,
+            min: reader.read()?,
+            max: reader.read()?,
+            units: reader.read()?,
+        })
+    }
+}
+
+impl Wire for LimitationSchema {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.settings)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<LimitationSchema, Error> {
+
+        Ok(LimitationSchema {
+            settings: reader.read()?,
+        })
+    }
+}
+
+impl Wire for LimitationRejection {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        match self {
+            LimitationRejection::UnknownSetting => writer.write(&0u8),
+            LimitationRejection::WrongKind => writer.write(&4u8),
+            LimitationRejection::BelowMinimum { min } => {
+                writer.write(&1u8)?;
+                writer.write(min)
+            },
+            LimitationRejection::AboveMaximum { max } => {
+                writer.write(&2u8)?;
+                writer.write(max)
+            },
+            LimitationRejection::Conflicting { bottom, top } => {
+                writer.write(&3u8)?;
+                writer.write(bottom)?;
+                writer.write(top)
+            },
+        }
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<LimitationRejection, Error> {
+
+        match reader.read::<u8>()? {
+            0 => Ok(LimitationRejection::UnknownSetting),
+            4 => Ok(LimitationRejection::WrongKind),
+            1 => Ok(LimitationRejection::BelowMinimum {
+                min: reader.read()?,
+            }),
+            2 => Ok(LimitationRejection::AboveMaximum {
+                max: reader.read()?,
+            }),
+            3 => Ok(LimitationRejection::Conflicting {
+                bottom: reader.read()?,
+                top: reader.read()?,
+            }),
+            tag => Err(tag_error("limitation rejection", tag)),
+        }
+    }
+}
+
+impl Wire for LimitationStatus {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        match self {
+            LimitationStatus::Applied => writer.write(&0u8),
+            LimitationStatus::Clamped { applied } => {
+                writer.write(&1u8)?;
+                writer.write(applied)
+            },
+            LimitationStatus::Rejected { reason } => {
+                writer.write(&2u8)?;
+                writer.write(reason)
+            },
+        }
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<LimitationStatus, Error> {
+
+        match reader.read::<u8>()? {
+            0 => Ok(LimitationStatus::Applied),
+            1 => Ok(LimitationStatus::Clamped {
+                applied: reader.read()?,
+            }),
+            2 => Ok(LimitationStatus::Rejected {
+                reason: reader.read()?,
+            }),
+            tag => Err(tag_error("limitation status", tag)),
+        }
+    }
+}
+
+impl Wire for LimitationOutcome {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.limitation)?;
+        writer.write(&self.status)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<LimitationOutcome, Error> {
+
+        Ok(LimitationOutcome {
+            limitation: reader.read()?,
+            status: reader.read()?,
+        })
+    }
+}
+
+impl Wire for EffectiveSetting {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.name)?;
+        writer.write(&self.value)?;
+        writer.write(&self.set_value)?;
+        writer.write(&self.limitations)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<EffectiveSetting, Error> {
+
+        Ok(EffectiveSetting {
+            name: reader.read()?,
+            value: reader.read()?,
+            set_value: reader.read()?,
+            limitations: reader.read()?,
+        })
+    }
+}
+
+impl Wire for Error {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        let (tag, message): (u8, &String) = match self {
+            // The library error itself can not be rebuilt on the
+            // other side, so only its description is passed
+            LoadingError(err) => {
+                writer.write(&0u8)?;
+                return writer.write(&err.to_string());
+            },
+            DependencyError(request) => {
+                writer.write(&1u8)?;
+                return writer.write(request);
+            },
+            ImportError(message) => (2, message),
+            TypeError(message) => (3, message),
+            ValueError(message) => (4, message),
+            OsError(message) => (5, message),
+            AssertionError(message) => (6, message),
+            IndexError(message) => (7, message),
+            ZeroDivisionError(message) => (8, message),
+            OverflowError(message) => (9, message),
+            NotImplementedError(message) => (10, message),
+            RuntimeError(message) => (11, message),
+            IntegrityError(message) => (12, message),
+        };
+        writer.write(&tag)?;
+        writer.write(message)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<Error, Error> {
+
+        match reader.read::<u8>()? {
+            0 => Ok(ImportError(reader.read()?)),
+            1 => Ok(DependencyError(reader.read()?)),
+            2 => Ok(ImportError(reader.read()?)),
+            3 => Ok(TypeError(reader.read()?)),
+            4 => Ok(ValueError(reader.read()?)),
+            5 => Ok(OsError(reader.read()?)),
+            6 => Ok(AssertionError(reader.read()?)),
+            7 => Ok(IndexError(reader.read()?)),
+            8 => Ok(ZeroDivisionError(reader.read()?)),
+            9 => Ok(OverflowError(reader.read()?)),
+            10 => Ok(NotImplementedError(reader.read()?)),
+            11 => Ok(RuntimeError(reader.read()?)),
+            12 => Ok(IntegrityError(reader.read()?)),
+            tag => Err(tag_error("error", tag)),
+        }
+    }
+}
+
+impl Wire for Object {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.get_type().name)?;
+        writer.write(&self.get_type().tp_id)?;
+        writer.write(&self.get_flags()?)?;
+        writer.write(&self.dk_dump()?)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<Object, Error> {
+
+        let name: String = reader.read()?;
+        let tp_id: usize = reader.read()?;
+        let flags: u32 = reader.read()?;
+        let data: Vec<u8> = reader.read()?;
+        reader.types.build_object(name, tp_id, flags, data)
+    }
+}
+
+impl Wire for Parameter {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.any_type)?;
+        writer.write(&self.trait_only)?;
+        writer.write(&self.implements)?;
+        writer.write(&self.mutable)?;
+        writer.write(&self.keyword_only)?;
+        writer.write(&self.keyword)?;
+        writer.write(&self.default_value)?;
+        writer.write(&self.allow_multiple)?;
+        writer.write(&self.max_amount)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<Parameter, Error> {
+
+        Ok(Parameter {
+            arg_type: TypeId::of::<ForeignObject>(),
+            any_type: reader.read()?,
+            trait_only: reader.read()?,
+            implements: reader.read()?,
+            mutable: reader.read()?,
+            keyword_only: reader.read()?,
+            keyword: reader.read()?,
+            default_value: reader.read()?,
+            allow_multiple: reader.read()?,
+            max_amount: reader.read()?,
+        })
+    }
+}
+
+impl Wire for Function {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.name)?;
+        writer.write(&self.fn_id)?;
+        writer.write(&self.parameters)?;
+        writer.write(&self.no_check_args)?;
+        writer.write(&self.dependencies)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<Function, Error> {
+
+        Ok(Function {
+            name: reader.read()?,
+            callable: Box::new(EmptyCallable{}),
+            fn_id: reader.read()?,
+            parameters: reader.read()?,
+            return_type: TypeId::of::<ForeignObject>(),
+            no_check_args: reader.read()?,
+            dependencies: reader.read()?,
+        })
+    }
+}
+
+impl Wire for TraitFunctionDefinition {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.name)?;
+        writer.write(&self.fn_trait_id)?;
+        writer.write(&self.parameters)?;
+        writer.write(&self.no_check_args)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<TraitFunctionDefinition, Error> {
+
+        Ok(TraitFunctionDefinition {
+            name: reader.read()?,
+            fn_trait_id: reader.read()?,
+            parameters: reader.read()?,
+            return_type: TypeId::of::<ForeignObject>(),
+            no_check_args: reader.read()?,
+        })
+    }
+}
+
+impl Wire for TraitDefinition {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.name)?;
+        writer.write(&self.trait_id)?;
+        writer.write(&self.methods)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<TraitDefinition, Error> {
+
+        Ok(TraitDefinition {
+            name: reader.read()?,
+            trait_id: reader.read()?,
+            methods: reader.read()?,
+        })
+    }
+}
+
+impl Wire for TraitFunction {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.fn_trait_id)?;
+        writer.write(&self.function)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<TraitFunction, Error> {
+
+        Ok(TraitFunction {
+            fn_trait_id: reader.read()?,
+            function: reader.read()?,
+        })
+    }
+}
+
+impl Wire for TraitImplementation {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.plugin)?;
+        writer.write(&self.trait_id)?;
+        writer.write(&self.version)?;
+        writer.write(&self.methods)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<TraitImplementation, Error> {
+
+        Ok(TraitImplementation {
+            plugin: reader.read()?,
+            trait_id: reader.read()?,
+            version: reader.read()?,
+            methods: reader.read()?,
+        })
+    }
+}
+
+impl Wire for Type {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.name)?;
+        writer.write(&self.tp_id)?;
+        writer.write(&self.methods)?;
+        writer.write(&self.fields)?;
+        writer.write(&self.trait_implementations)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<Type, Error> {
+
+        Ok(Type {
+            name: reader.read()?,
+            tp_id: reader.read()?,
+            generator: foreign_generator,
+            methods: reader.read()?,
+            fields: reader.read()?,
+            trait_implementations: reader.read()?,
+            native_id: TypeId::of::<ForeignObject>(),
+            library: None,
+        })
+    }
+}
+
+impl Wire for Module {
+    fn write_wire (
+        self: &Self,
+        writer: &mut WireWriter,
+    ) -> Result<(), Error> {
+
+        writer.write(&self.name)?;
+        writer.write(&self.md_id)?;
+        writer.write(&self.types)?;
+        writer.write(&self.functions)?;
+        writer.write(&self.submodules)?;
+        writer.write(&self.trait_definitions)?;
+        writer.write(&self.constants)
+    }
+
+    fn read_wire (
+        reader: &mut WireReader<'_>,
+    ) -> Result<Module, Error> {
+
+        reader.read_nested(|reader: &mut WireReader<'_>| {
+            Ok(Module {
+                name: reader.read()?,
+                md_id: reader.read()?,
+                types: reader.read()?,
+                functions: reader.read()?,
+                submodules: reader.read()?,
+                trait_definitions: reader.read()?,
+                constants: reader.read()?,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wrap the request provided into as many crucial requests, as
+    /// asked for
+    fn nest (
+        request: InterplugRequest,
+        depth: usize,
+    ) -> InterplugRequest {
+
+        let mut result: InterplugRequest = request;
+        for _ in 0..depth {
+            result = RequestCrucial {
+                request: Box::new(result),
+            };
+        }
+        return result;
+    }
+
+    /// Encode the value provided and decode it back, making sure
+    /// every shorter part of the encoded data is rejected
+    fn round_trip<T> (value: &T) -> T where T: Wire {
+        let data: Vec<u8> = encode(value).unwrap();
+        for len in 0..data.len() {
+            assert!(decode::<T>(&data[..len]).is_err());
+        }
+        return decode(&data).unwrap();
+    }
+
+    /// Generator of the type, used to test passing objects
+    fn test_generator () -> Result<Box<dyn DkAny>, Error> {
+        Ok(Box::new(ForeignObject::new(Vec::new())))
+    }
+
+    #[test]
+    fn versions () {
+        let version: Version = "1.2.3-alpha.1+nightly".parse().unwrap();
+        let decoded: Version = round_trip(&version);
+        assert_eq!(decoded, version);
+        assert_eq!(decoded.metadata, "nightly");
+
+        let req: VersionReq = ">=1.2, <2".parse().unwrap();
+        assert_eq!(round_trip(&req), req);
+    }
+
+    #[test]
+    fn limitations () {
+        let limitations: Vec<Limitation> = vec![
+            Limitation::Top {
+                setting: "memory".to_string(),
+                limit: 1024,
+            },
+            Limitation::Bottom {
+                setting: "threads".to_string(),
+                limit: -1,
+            },
+            Limitation::Reset {
+                setting: "memory".to_string(),
+            },
+            Limitation::Set {
+                setting: "timeout".to_string(),
+                value: LimitationValue::Duration(
+                    std::time::Duration::from_millis(1500)),
+            },
+            Limitation::Set {
+                setting: "paths".to_string(),
+                value: LimitationValue::StringList(
+                    vec!["a".to_string(), "".to_string()]),
+            },
+        ];
+        assert_eq!(round_trip(&limitations), limitations);
+    }
+
+    #[test]
+    fn limitation_values () {
+        let values: Vec<LimitationValue> = vec![
+            std::path::PathBuf::from("/tmp/dusk test/ü").into(),
+            "quoted \"text\"\n".to_string().into(),
+            Vec::<String>::new().into(),
+            vec!["a".to_string(), "b c".to_string()].into(),
+            true.into(),
+            false.into(),
+            std::time::Duration::new(u64::MAX, 999_999_999).into(),
+            std::time::Duration::ZERO.into(),
+        ];
+        for value in &values {
+            assert_eq!(&round_trip(value), value);
+        }
+
+        assert!(matches!(
+                decode::<LimitationValue>(&[5]),
+                Err(ValueError(_))));
+        assert!(matches!(
+                decode::<LimitationValue>(&[3, 2]),
+                Err(ValueError(_))));
+
+        // Nanoseconds, that would carry over into the seconds
+        let mut data: Vec<u8> = vec![4];
+        data.extend_from_slice(&encode(&u64::MAX).unwrap());
+        data.extend_from_slice(&encode(&1_000_000_000u32).unwrap());
+        assert!(matches!(
+                decode::<LimitationValue>(&data),
+                Err(ValueError(_))));
+    }
+
+    #[test]
+    fn requests () {
+        let request: InterplugRequest = RequestEach {
+            requests: vec![
+                PlugRequest {
+                    plugin: "first".to_string(),
+                    fn_ids: vec![1, 2, 3],
+                    version: "^1.0".parse().unwrap(),
+                },
+                RequestOptional {
+                    request: Box::new(TraitRequest {
+                        plugin: "second".to_string(),
+                        trait_id: 4,
+                        fn_ids: Vec::new(),
+                        version: VersionReq::any(),
+                    }),
+                },
+                RequestEither {
+                    requests: vec![
+                        TraitRequestAll {
+                            plugin: "third".to_string(),
+                            trait_id: 5,
+                            version: VersionReq::any(),
+                        },
+                    ],
+                },
+            ],
+        };
+        assert_eq!(round_trip(&request), request);
+    }
+
+    #[test]
+    fn modules () {
+        let module: Module = Module {
+            name: "root".to_string(),
+            md_id: 1,
+            submodules: vec![
+                Module {
+                    name: "sub".to_string(),
+                    md_id: 2,
+                    ..Module::default()
+                },
+            ],
+            ..Module::default()
+        };
+        let decoded: Module = round_trip(&module);
+        assert_eq!(decoded.name, "root");
+        assert_eq!(decoded.md_id, 1);
+        assert_eq!(decoded.submodules.len(), 1);
+        assert_eq!(decoded.submodules[0].name, "sub");
+        assert_eq!(decoded.submodules[0].md_id, 2);
+    }
+
+    #[test]
+    fn objects () {
+        let data_type: &'static Type = Box::leak(Box::new(Type {
+            name: "dusk-test-wire".to_string(),
+            tp_id: 7,
+            generator: test_generator,
+            ..Default::default()
+        }));
+        let mut types: TypeTable = TypeTable::default();
+        types.register(data_type);
+
+        let object: Object = types.build_object(
+            data_type.name.clone(), 7, 0, vec![1, 2, 3]).unwrap();
+        let data: Vec<u8> = encode(&object).unwrap();
+        let mut reader: WireReader<'_> = WireReader::new(&data, &mut types);
+        let decoded: Object = reader.read().unwrap();
+        assert!(std::ptr::eq(decoded.get_type(), data_type));
+        assert_eq!(encode(&decoded).unwrap(), data);
+
+        // Objects of unknown types are kept as they are
+        let foreign: Object = decode(&data).unwrap();
+        assert!(!std::ptr::eq(foreign.get_type(), data_type));
+        assert_eq!(foreign.get_type().name, "dusk-test-wire");
+        assert_eq!(encode(&foreign).unwrap(), data);
+    }
+
+    #[test]
+    fn results () {
+        let ok: Result<String, Error> = Ok("done".to_string());
+        assert_eq!(round_trip(&ok).unwrap(), "done");
+
+        let err: Result<String, Error> = Err(RuntimeError("failed".to_string()));
+        match round_trip(&err) {
+            Err(RuntimeError(message)) => assert_eq!(message, "failed"),
+            other => panic!("Unexpected result {:?}", other),
+        }
+
+        assert!(decode::<Result<(), Error>>(&[2]).is_err());
+    }
+
+    #[test]
+    fn lengths () {
+        let mut data: Vec<u8> = encode(&"text".to_string()).unwrap();
+        data[..8].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(matches!(decode::<String>(&data), Err(ValueError(_))));
+
+        let mut data: Vec<u8> = encode(&vec![1usize, 2]).unwrap();
+        data[..8].copy_from_slice(&(1u64 << 40).to_le_bytes());
+        assert!(matches!(decode::<Vec<usize>>(&data), Err(ValueError(_))));
+
+        // A length, that fits the data, but is followed by too few
+        // elements
+        let mut data: Vec<u8> = encode(&vec![1usize, 2]).unwrap();
+        data.truncate(data.len() - 8);
+        assert!(matches!(decode::<Vec<usize>>(&data), Err(ValueError(_))));
+    }
+
+    #[test]
+    fn nesting_limit () {
+        let request: InterplugRequest = PlugRequestAll {
+            plugin: "test".to_string(),
+            version: VersionReq::any(),
+        };
+
+        let allowed: InterplugRequest =
+            nest(request.clone(), MAX_WIRE_DEPTH - 1);
+        assert_eq!(
+            decode::<InterplugRequest>(&encode(&allowed).unwrap()).unwrap(),
+            allowed);
+
+        // Deep enough to overflow the stack, if it was decoded
+        let mut data: Vec<u8> = vec![6; 1_000_000];
+        data.extend_from_slice(&encode(&request).unwrap());
+        assert!(matches!(
+                decode::<InterplugRequest>(&data),
+                Err(ValueError(_))));
+        assert!(decode::<InterplugRequest>(
+                &encode(&nest(request, MAX_WIRE_DEPTH)).unwrap()).is_err());
+
+        let mut module: Module = Module::default();
+        for _ in 0..MAX_WIRE_DEPTH {
+            module = Module {
+                submodules: vec![module],
+                ..Module::default()
+            };
+        }
+        assert!(matches!(
+                decode::<Module>(&encode(&module).unwrap()),
+                Err(ValueError(_))));
+    }
+}